@@ -1,55 +1,99 @@
+use crate::editor::{EditorRequest, EditorTarget};
+use crate::jira::agile::{Rank, MOVE_MAX_TICKETS};
+use crate::jira::bulk::{self, BulkOperation, BulkResult};
+use crate::jira::clone::{self, CloneSource};
+use crate::jira::createmeta::CreateOutcome;
+use crate::jira::jql::{Jql, JqlAutocomplete, JqlQuery, JqlValue, SortOrder};
+use crate::jira::notifications::{Notification, NotificationPoller, DEFAULT_POLL_INTERVAL_SECS};
 use crate::jira::projects::Project;
-use crate::jira::tickets::{PostTicketTransition, TicketTransition};
+use crate::jira::tickets::{PostTicketTransition, TicketData, TicketTransition};
+use crate::jira::TicketSource;
+use crate::templates::{self, TemplateVars};
+use crate::theme;
+use crate::widgets::boards::{BoardsFocus, BoardsWidget};
+use crate::widgets::bulk::{BulkRequest, BulkWidget};
 use crate::widgets::commands::CommandText;
 use crate::widgets::commands::{self, CommandInfo};
 use crate::widgets::comments::CommentsList;
 use crate::widgets::comments_add::CommentAdd;
 use crate::widgets::components::ComponentsWidget;
 use crate::widgets::create_ticket::CreateTicketWidget;
+use crate::widgets::epics::EpicsWidget;
 use crate::widgets::error::ErrorComponent;
+use crate::widgets::filters::FiltersWidget;
 use crate::widgets::help::HelpWidget;
+use crate::widgets::history::HistoryWidget;
+use crate::widgets::jql::JqlWidget;
+use crate::widgets::kanban::KanbanWidget;
 use crate::widgets::labels::LabelsWidget;
+use crate::widgets::my_work::MyWorkWidget;
+use crate::widgets::notifications::NotificationsWidget;
+use crate::widgets::palette::{CommandPalette, PaletteEntry};
+use crate::widgets::pane_layout::{self, Pane, PaneLayout};
 use crate::widgets::parent::TicketParentWidget;
 use crate::widgets::search_projects::SearchProjectsWidget;
 use crate::widgets::search_tickets::SearchTicketsWidget;
+use crate::widgets::ticket_columns;
+use crate::widgets::ticket_finder::{FinderRequest, TicketFinderWidget};
 use crate::widgets::ticket_relation::RelationWidget;
 use crate::widgets::ticket_transition::TransitionWidget;
 use crate::widgets::tickets::TicketWidget;
 use crate::widgets::{DrawableComponent, InputMode};
 use crate::{
-    config::Config,
-    events::key::Key,
-    widgets::{Component, EventState},
+    config::{self, Config, KeyConfig},
+    events::{
+        key::Key,
+        mouse::{Mouse, MouseAction},
+    },
+    widgets::{self, Component, EventState},
 };
 use crate::{jira::Jira, widgets::projects::ProjectsWidget};
 use log::debug;
 use std::collections::HashMap;
+use std::time::{Duration, Instant};
+use tokio::sync::mpsc::{self, error::TryRecvError, UnboundedReceiver};
 use tui::layout::Rect;
-use tui::{
-    backend::Backend,
-    layout::{Constraint, Direction, Layout},
-    Frame,
-};
+use tui::{backend::Backend, Frame};
+
+// A key that starts a sequence like "g g" is handed on by itself when the
+// sequence is not finished within SEQUENCE_TIMEOUT
+const SEQUENCE_TIMEOUT: Duration = Duration::from_secs(1);
 
 pub enum Focus {
+    Boards,
+    Bulk,
     CommentsAdd,
     CommentsList,
     Components,
     CreateTicket,
+    Epics,
+    Filters,
+    History,
+    Jql,
+    MyWork,
+    Notifications,
+    Kanban,
     Labels,
     Projects,
     SearchProjects,
     SearchTickets,
+    TicketFinder,
     TicketParent,
     TicketRelation,
     TicketTransition,
     Tickets,
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 enum ProjectsAction {
     NextPage,
+    OpenBoards,
+    OpenEpics,
+    OpenFilters,
     OpenHelp,
+    OpenJql,
+    OpenMyWork,
+    OpenNotifications,
     PreviousPage,
     Reset,
     SearchProjects,
@@ -57,10 +101,63 @@ enum ProjectsAction {
 }
 
 impl ProjectsAction {
+    // ALL lists every action for the command palette, bound to a key or not
+    const ALL: [Self; 12] = [
+        Self::NextPage,
+        Self::OpenBoards,
+        Self::OpenEpics,
+        Self::OpenFilters,
+        Self::OpenHelp,
+        Self::OpenJql,
+        Self::OpenMyWork,
+        Self::OpenNotifications,
+        Self::PreviousPage,
+        Self::Reset,
+        Self::SearchProjects,
+        Self::SelectProject,
+    ];
+
+    fn key_mappings(key_config: &KeyConfig) -> HashMap<Key, Self> {
+        let mut map = HashMap::new();
+        map.insert(key_config.open_help, Self::OpenHelp);
+        map.insert(key_config.open_boards, Self::OpenBoards);
+        map.insert(key_config.open_epics, Self::OpenEpics);
+        map.insert(key_config.open_filters, Self::OpenFilters);
+        map.insert(key_config.open_jql, Self::OpenJql);
+        map.insert(key_config.open_my_work, Self::OpenMyWork);
+        map.insert(key_config.open_notifications, Self::OpenNotifications);
+        map.insert(key_config.enter, Self::SelectProject);
+        map.insert(key_config.filter, Self::SearchProjects);
+        map.insert(key_config.next_page, Self::NextPage);
+        map.insert(key_config.previous_page, Self::PreviousPage);
+        map.insert(key_config.reset, Self::Reset);
+        map
+    }
+
     fn to_command_text(self, key: Key) -> CommandText {
         const CMD_GROUP_GENERAL: &str = "-- Projects Help --";
         match self {
             Self::OpenHelp => CommandText::new(format!("Open Help [{key}]"), CMD_GROUP_GENERAL),
+            Self::OpenBoards => CommandText::new(
+                format!("Open boards and sprints of project [{key}]"),
+                CMD_GROUP_GENERAL,
+            ),
+            Self::OpenEpics => {
+                CommandText::new(format!("Open epics of project [{key}]"), CMD_GROUP_GENERAL)
+            }
+            Self::OpenFilters => {
+                CommandText::new(format!("Open saved filters [{key}]"), CMD_GROUP_GENERAL)
+            }
+            Self::OpenJql => {
+                CommandText::new(format!("Run a JQL query [{key}]"), CMD_GROUP_GENERAL)
+            }
+            Self::OpenMyWork => CommandText::new(
+                format!("Open my work across projects [{key}]"),
+                CMD_GROUP_GENERAL,
+            ),
+            Self::OpenNotifications => {
+                CommandText::new(format!("Open notifications [{key}]"), CMD_GROUP_GENERAL)
+            }
             Self::SelectProject => {
                 CommandText::new(format!("Select project [{key}]"), CMD_GROUP_GENERAL)
             }
@@ -85,6 +182,14 @@ enum ParentAction {
 }
 
 impl ParentAction {
+    fn key_mappings(key_config: &KeyConfig) -> HashMap<Key, Self> {
+        let mut map = HashMap::new();
+        map.insert(key_config.open_help, Self::OpenHelp);
+        map.insert(key_config.previous, Self::FocusComponent);
+        map.insert(key_config.next, Self::FocusRelation);
+        map
+    }
+
     fn to_command_text(self, key: Key) -> CommandText {
         const CMD_GROUP_GENERAL: &str = "-- Tickets Help --";
         match self {
@@ -99,22 +204,100 @@ impl ParentAction {
     }
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 enum TicketsAction {
     FocusLabels,
     FocusRelation,
+    MoveToBacklog,
+    MoveToSprint,
     NextPage,
+    OpenBoards,
+    OpenBulk,
     OpenComments,
+    OpenEpics,
+    OpenFilters,
+    OpenHistory,
+    OpenKanban,
     OpenCreateTicket,
+    CloneTicket,
     OpenHelp,
+    OpenJql,
+    OpenMyWork,
+    OpenNotifications,
     OpenProjects,
     OpenTicketTransition,
     PreviousPage,
+    RankBottom,
+    RankDown,
+    RankTop,
+    RankUp,
     Reset,
     SearchTickets,
 }
 
 impl TicketsAction {
+    const ALL: [Self; 27] = [
+        Self::FocusLabels,
+        Self::FocusRelation,
+        Self::MoveToBacklog,
+        Self::MoveToSprint,
+        Self::NextPage,
+        Self::OpenBoards,
+        Self::OpenBulk,
+        Self::OpenComments,
+        Self::OpenEpics,
+        Self::OpenFilters,
+        Self::OpenHistory,
+        Self::OpenKanban,
+        Self::OpenCreateTicket,
+        Self::CloneTicket,
+        Self::OpenHelp,
+        Self::OpenJql,
+        Self::OpenMyWork,
+        Self::OpenNotifications,
+        Self::OpenProjects,
+        Self::OpenTicketTransition,
+        Self::PreviousPage,
+        Self::RankBottom,
+        Self::RankDown,
+        Self::RankTop,
+        Self::RankUp,
+        Self::Reset,
+        Self::SearchTickets,
+    ];
+
+    fn key_mappings(key_config: &KeyConfig) -> HashMap<Key, Self> {
+        let mut map = HashMap::new();
+        map.insert(key_config.previous, Self::FocusRelation);
+        map.insert(key_config.next, Self::FocusLabels);
+        map.insert(key_config.ticket_view_comments, Self::OpenComments);
+        map.insert(key_config.ticket_view_history, Self::OpenHistory);
+        map.insert(key_config.open_create_ticket, Self::OpenCreateTicket);
+        map.insert(key_config.clone_ticket, Self::CloneTicket);
+        map.insert(key_config.esc, Self::OpenProjects);
+        map.insert(key_config.open_boards, Self::OpenBoards);
+        map.insert(key_config.open_bulk, Self::OpenBulk);
+        map.insert(key_config.open_kanban, Self::OpenKanban);
+        map.insert(key_config.open_epics, Self::OpenEpics);
+        map.insert(key_config.open_filters, Self::OpenFilters);
+        map.insert(key_config.open_jql, Self::OpenJql);
+        map.insert(key_config.open_my_work, Self::OpenMyWork);
+        map.insert(key_config.open_notifications, Self::OpenNotifications);
+        map.insert(key_config.move_to_sprint, Self::MoveToSprint);
+        map.insert(key_config.move_to_backlog, Self::MoveToBacklog);
+        map.insert(key_config.ticket_transition, Self::OpenTicketTransition);
+        map.insert(key_config.open_help, Self::OpenHelp);
+        map.insert(key_config.filter, Self::SearchTickets);
+        map.insert(key_config.next_page, Self::NextPage);
+        map.insert(key_config.previous_page, Self::PreviousPage);
+        map.insert(key_config.rank_bottom, Self::RankBottom);
+        map.insert(key_config.rank_down, Self::RankDown);
+        map.insert(key_config.rank_top, Self::RankTop);
+        map.insert(key_config.rank_up, Self::RankUp);
+        map.insert(key_config.reset, Self::Reset);
+        map
+    }
+
     fn to_command_text(self, key: Key) -> CommandText {
         const CMD_GROUP_GENERAL: &str = "-- Tickets Help --";
         match self {
@@ -125,12 +308,50 @@ impl TicketsAction {
                 CommandText::new(format!("Focus on Labels pane [{key}]"), CMD_GROUP_GENERAL)
             }
             Self::OpenHelp => CommandText::new(format!("Open Help [{key}]"), CMD_GROUP_GENERAL),
+            Self::MoveToBacklog => {
+                CommandText::new(format!("Move ticket to backlog [{key}]"), CMD_GROUP_GENERAL)
+            }
+            Self::MoveToSprint => {
+                CommandText::new(format!("Move ticket to sprint [{key}]"), CMD_GROUP_GENERAL)
+            }
+            Self::OpenBoards => CommandText::new(
+                format!("Open boards and sprints [{key}]"),
+                CMD_GROUP_GENERAL,
+            ),
+            Self::OpenBulk => CommandText::new(
+                format!("Bulk actions on selected tickets [{key}]"),
+                CMD_GROUP_GENERAL,
+            ),
             Self::OpenCreateTicket => {
                 CommandText::new(format!("Create ticket [{key}]"), CMD_GROUP_GENERAL)
             }
+            Self::CloneTicket => {
+                CommandText::new(format!("Clone ticket [{key}]"), CMD_GROUP_GENERAL)
+            }
             Self::OpenComments => {
                 CommandText::new(format!("Open Comments View [{key}]"), CMD_GROUP_GENERAL)
             }
+            Self::OpenEpics => {
+                CommandText::new(format!("Open Epics View [{key}]"), CMD_GROUP_GENERAL)
+            }
+            Self::OpenFilters => {
+                CommandText::new(format!("Open Filters View [{key}]"), CMD_GROUP_GENERAL)
+            }
+            Self::OpenHistory => {
+                CommandText::new(format!("Open History View [{key}]"), CMD_GROUP_GENERAL)
+            }
+            Self::OpenJql => {
+                CommandText::new(format!("Run a JQL query [{key}]"), CMD_GROUP_GENERAL)
+            }
+            Self::OpenKanban => {
+                CommandText::new(format!("Open Kanban View [{key}]"), CMD_GROUP_GENERAL)
+            }
+            Self::OpenMyWork => {
+                CommandText::new(format!("Open My Work View [{key}]"), CMD_GROUP_GENERAL)
+            }
+            Self::OpenNotifications => {
+                CommandText::new(format!("Open Notifications [{key}]"), CMD_GROUP_GENERAL)
+            }
             Self::SearchTickets => CommandText::new(format!("Filter [{key}]"), CMD_GROUP_GENERAL),
             Self::NextPage => CommandText::new(format!("Next page [{key}]"), CMD_GROUP_GENERAL),
             Self::PreviousPage => {
@@ -143,6 +364,16 @@ impl TicketsAction {
                 format!("Open Ticket transition view [{key}]"),
                 CMD_GROUP_GENERAL,
             ),
+            Self::RankBottom => {
+                CommandText::new(format!("Rank ticket last [{key}]"), CMD_GROUP_GENERAL)
+            }
+            Self::RankDown => {
+                CommandText::new(format!("Rank ticket down [{key}]"), CMD_GROUP_GENERAL)
+            }
+            Self::RankTop => {
+                CommandText::new(format!("Rank ticket first [{key}]"), CMD_GROUP_GENERAL)
+            }
+            Self::RankUp => CommandText::new(format!("Rank ticket up [{key}]"), CMD_GROUP_GENERAL),
             Self::Reset => CommandText::new(
                 format!("Clear out tickets cache table and pull from Jira [{key}]"),
                 CMD_GROUP_GENERAL,
@@ -151,7 +382,7 @@ impl TicketsAction {
     }
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 enum CommentsAction {
     OpenHelp,
     AddComment,
@@ -159,6 +390,16 @@ enum CommentsAction {
 }
 
 impl CommentsAction {
+    const ALL: [Self; 3] = [Self::OpenHelp, Self::AddComment, Self::FocusTickets];
+
+    fn key_mappings(key_config: &KeyConfig) -> HashMap<Key, Self> {
+        let mut map = HashMap::new();
+        map.insert(key_config.open_help, Self::OpenHelp);
+        map.insert(key_config.ticket_add_comments, Self::AddComment);
+        map.insert(key_config.esc, Self::FocusTickets);
+        map
+    }
+
     fn to_command_text(self, key: Key) -> CommandText {
         const CMD_GROUP_GENERAL: &str = "-- Comments Help --";
         match self {
@@ -173,30 +414,353 @@ impl CommentsAction {
     }
 }
 
+#[derive(Debug, Clone, Copy)]
+enum BulkAction {
+    FocusTickets,
+    OpenHelp,
+}
+
+impl BulkAction {
+    fn key_mappings(key_config: &KeyConfig) -> HashMap<Key, Self> {
+        let mut map = HashMap::new();
+        map.insert(key_config.open_help, Self::OpenHelp);
+        map.insert(key_config.esc, Self::FocusTickets);
+        map
+    }
+
+    fn to_command_text(self, key: Key) -> CommandText {
+        const CMD_GROUP_GENERAL: &str = "-- Bulk Help --";
+        match self {
+            Self::FocusTickets => CommandText::new(format!("Go back [{key}]"), CMD_GROUP_GENERAL),
+            Self::OpenHelp => CommandText::new(format!("Open Help [{key}]"), CMD_GROUP_GENERAL),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum BoardsAction {
+    FocusTickets,
+    OpenHelp,
+    Select,
+}
+
+impl BoardsAction {
+    fn key_mappings(key_config: &KeyConfig) -> HashMap<Key, Self> {
+        let mut map = HashMap::new();
+        map.insert(key_config.open_help, Self::OpenHelp);
+        map.insert(key_config.enter, Self::Select);
+        map.insert(key_config.esc, Self::FocusTickets);
+        map
+    }
+
+    fn to_command_text(self, key: Key) -> CommandText {
+        const CMD_GROUP_GENERAL: &str = "-- Boards Help --";
+        match self {
+            Self::FocusTickets => CommandText::new(format!("Go back [{key}]"), CMD_GROUP_GENERAL),
+            Self::OpenHelp => CommandText::new(format!("Open Help [{key}]"), CMD_GROUP_GENERAL),
+            Self::Select => CommandText::new(
+                format!("Open board sprints / sprint tickets [{key}]"),
+                CMD_GROUP_GENERAL,
+            ),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum EpicsAction {
+    FocusTickets,
+    OpenHelp,
+    Reset,
+    Select,
+}
+
+impl EpicsAction {
+    fn key_mappings(key_config: &KeyConfig) -> HashMap<Key, Self> {
+        let mut map = HashMap::new();
+        map.insert(key_config.open_help, Self::OpenHelp);
+        map.insert(key_config.enter, Self::Select);
+        map.insert(key_config.esc, Self::FocusTickets);
+        map.insert(key_config.reset, Self::Reset);
+        map
+    }
+
+    fn to_command_text(self, key: Key) -> CommandText {
+        const CMD_GROUP_GENERAL: &str = "-- Epics Help --";
+        match self {
+            Self::FocusTickets => CommandText::new(format!("Go back [{key}]"), CMD_GROUP_GENERAL),
+            Self::OpenHelp => CommandText::new(format!("Open Help [{key}]"), CMD_GROUP_GENERAL),
+            Self::Reset => CommandText::new(
+                format!("Pull epics and progress from Jira again [{key}]"),
+                CMD_GROUP_GENERAL,
+            ),
+            Self::Select => {
+                CommandText::new(format!("Open epic children [{key}]"), CMD_GROUP_GENERAL)
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum FiltersAction {
+    FocusTickets,
+    OpenHelp,
+    Select,
+}
+
+impl FiltersAction {
+    fn key_mappings(key_config: &KeyConfig) -> HashMap<Key, Self> {
+        let mut map = HashMap::new();
+        map.insert(key_config.open_help, Self::OpenHelp);
+        map.insert(key_config.enter, Self::Select);
+        map.insert(key_config.esc, Self::FocusTickets);
+        map
+    }
+
+    fn to_command_text(self, key: Key) -> CommandText {
+        const CMD_GROUP_GENERAL: &str = "-- Filters Help --";
+        match self {
+            Self::FocusTickets => CommandText::new(format!("Go back [{key}]"), CMD_GROUP_GENERAL),
+            Self::OpenHelp => CommandText::new(format!("Open Help [{key}]"), CMD_GROUP_GENERAL),
+            Self::Select => {
+                CommandText::new(format!("Open filter tickets [{key}]"), CMD_GROUP_GENERAL)
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum MyWorkAction {
+    FocusTickets,
+    OpenHelp,
+    Reset,
+    Select,
+}
+
+impl MyWorkAction {
+    fn key_mappings(key_config: &KeyConfig) -> HashMap<Key, Self> {
+        let mut map = HashMap::new();
+        map.insert(key_config.open_help, Self::OpenHelp);
+        map.insert(key_config.enter, Self::Select);
+        map.insert(key_config.esc, Self::FocusTickets);
+        map.insert(key_config.reset, Self::Reset);
+        map
+    }
+
+    fn to_command_text(self, key: Key) -> CommandText {
+        const CMD_GROUP_GENERAL: &str = "-- My Work Help --";
+        match self {
+            Self::FocusTickets => CommandText::new(format!("Go back [{key}]"), CMD_GROUP_GENERAL),
+            Self::OpenHelp => CommandText::new(format!("Open Help [{key}]"), CMD_GROUP_GENERAL),
+            Self::Reset => CommandText::new(
+                format!("Pull my work from Jira again [{key}]"),
+                CMD_GROUP_GENERAL,
+            ),
+            Self::Select => CommandText::new(format!("Open ticket [{key}]"), CMD_GROUP_GENERAL),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum NotificationsAction {
+    FocusTickets,
+    MarkAllRead,
+    OpenHelp,
+    Select,
+}
+
+impl NotificationsAction {
+    fn key_mappings(key_config: &KeyConfig) -> HashMap<Key, Self> {
+        let mut map = HashMap::new();
+        map.insert(key_config.open_help, Self::OpenHelp);
+        map.insert(key_config.enter, Self::Select);
+        map.insert(key_config.esc, Self::FocusTickets);
+        map.insert(key_config.mark_all_read, Self::MarkAllRead);
+        map
+    }
+
+    fn to_command_text(self, key: Key) -> CommandText {
+        const CMD_GROUP_GENERAL: &str = "-- Notifications Help --";
+        match self {
+            Self::FocusTickets => CommandText::new(format!("Go back [{key}]"), CMD_GROUP_GENERAL),
+            Self::MarkAllRead => {
+                CommandText::new(format!("Mark all as read [{key}]"), CMD_GROUP_GENERAL)
+            }
+            Self::OpenHelp => CommandText::new(format!("Open Help [{key}]"), CMD_GROUP_GENERAL),
+            Self::Select => CommandText::new(format!("Open ticket [{key}]"), CMD_GROUP_GENERAL),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum HistoryAction {
+    OpenHelp,
+    FocusTickets,
+}
+
+impl HistoryAction {
+    fn key_mappings(key_config: &KeyConfig) -> HashMap<Key, Self> {
+        let mut map = HashMap::new();
+        map.insert(key_config.open_help, Self::OpenHelp);
+        map.insert(key_config.esc, Self::FocusTickets);
+        map
+    }
+
+    fn to_command_text(self, key: Key) -> CommandText {
+        const CMD_GROUP_GENERAL: &str = "-- History Help --";
+        match self {
+            Self::OpenHelp => CommandText::new(format!("Open Help [{key}]"), CMD_GROUP_GENERAL),
+            Self::FocusTickets => {
+                CommandText::new(format!("Go back to tickets [{key}]"), CMD_GROUP_GENERAL)
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum KanbanAction {
+    FocusTickets,
+    OpenHelp,
+    Select,
+    TransitionLeft,
+    TransitionRight,
+}
+
+impl KanbanAction {
+    fn key_mappings(key_config: &KeyConfig) -> HashMap<Key, Self> {
+        let mut map = HashMap::new();
+        map.insert(key_config.open_help, Self::OpenHelp);
+        map.insert(key_config.enter, Self::Select);
+        map.insert(key_config.esc, Self::FocusTickets);
+        map.insert(key_config.transition_left, Self::TransitionLeft);
+        map.insert(key_config.transition_right, Self::TransitionRight);
+        map
+    }
+
+    fn to_command_text(self, key: Key) -> CommandText {
+        const CMD_GROUP_GENERAL: &str = "-- Kanban Help --";
+        match self {
+            Self::FocusTickets => {
+                CommandText::new(format!("Go back to tickets [{key}]"), CMD_GROUP_GENERAL)
+            }
+            Self::OpenHelp => CommandText::new(format!("Open Help [{key}]"), CMD_GROUP_GENERAL),
+            Self::Select => {
+                CommandText::new(format!("Open ticket in list [{key}]"), CMD_GROUP_GENERAL)
+            }
+            Self::TransitionLeft => CommandText::new(
+                format!("Move ticket to the column on the left [{key}]"),
+                CMD_GROUP_GENERAL,
+            ),
+            Self::TransitionRight => CommandText::new(
+                format!("Move ticket to the column on the right [{key}]"),
+                CMD_GROUP_GENERAL,
+            ),
+        }
+    }
+}
+
 pub struct App {
+    boards: BoardsWidget,
+    boards_key_mappings: HashMap<Key, BoardsAction>,
+    bulk: BulkWidget,
+    bulk_key_mappings: HashMap<Key, BulkAction>,
+    bulk_rx: Option<UnboundedReceiver<BulkResult>>,
     comment_add: CommentAdd,
     comments_list: CommentsList,
     comments_key_mappings: HashMap<Key, CommentsAction>,
     components: ComponentsWidget,
     create_ticket: CreateTicketWidget,
+    epics: EpicsWidget,
+    epics_key_mappings: HashMap<Key, EpicsAction>,
+    filters: FiltersWidget,
+    filters_key_mappings: HashMap<Key, FiltersAction>,
     focus: Focus,
     help: HelpWidget,
+    history: HistoryWidget,
+    history_key_mappings: HashMap<Key, HistoryAction>,
     jira: Jira,
+    jql: JqlWidget,
+    kanban: KanbanWidget,
+    kanban_key_mappings: HashMap<Key, KanbanAction>,
     labels: LabelsWidget,
+    my_work: MyWorkWidget,
+    my_work_key_mappings: HashMap<Key, MyWorkAction>,
+    notifications: NotificationsWidget,
+    notifications_key_mappings: HashMap<Key, NotificationsAction>,
+    notifications_rx: Option<UnboundedReceiver<Vec<Notification>>>,
+    layout: PaneLayout,
+    // panes are where the last draw put the panes of the tickets screen
+    panes: Vec<(Pane, Rect)>,
     parent: TicketParentWidget,
+    // pending_key is the first key of a sequence waiting for the second one
+    pending_key: Option<(char, Instant)>,
+    palette: CommandPalette<PaletteCommand>,
+    // quit is set when quit or exit is pressed and no widget took the key
+    pub quit: bool,
     parent_key_mappings: HashMap<Key, ParentAction>,
     projects: ProjectsWidget,
     projects_key_mappings: HashMap<Key, ProjectsAction>,
     relation: RelationWidget,
     search_projects: SearchProjectsWidget,
     search_tickets: SearchTicketsWidget,
+    ticket_finder: TicketFinderWidget,
+    ticket_source: TicketSource,
     ticket_transition: TransitionWidget,
     tickets: TicketWidget,
     tickets_key_mappings: HashMap<Key, TicketsAction>,
+    // zoomed shows the focused pane of the tickets screen full screen
+    zoomed: bool,
     pub config: Config,
+    // editor_request asks the main loop to hand the terminal to $EDITOR
+    pub editor_request: Option<EditorRequest>,
     pub error: ErrorComponent,
 }
 
+// PaletteCommand is what the command palette runs: an action of the app, or
+// the key of a widget action
+#[derive(Debug, Clone, Copy)]
+enum PaletteCommand {
+    Comments(CommentsAction),
+    Key(Key),
+    Projects(ProjectsAction),
+    Tickets(TicketsAction),
+}
+
+// keyed_entries list the actions of a key map, run by their key
+fn keyed_entries<A: Copy>(
+    key_mappings: &HashMap<Key, A>,
+    text: impl Fn(A, Key) -> CommandText,
+) -> Vec<PaletteEntry<PaletteCommand>> {
+    key_mappings
+        .iter()
+        .map(|(&key, &action)| {
+            PaletteEntry::new(text(action, key), Some(key), PaletteCommand::Key(key))
+        })
+        .collect()
+}
+
+// action_entries list every action with the key it is bound to, if any
+fn action_entries<A: Copy + PartialEq, const N: usize>(
+    actions: [A; N],
+    key_mappings: &HashMap<Key, A>,
+    text: impl Fn(A, Key) -> CommandText,
+    command: impl Fn(A) -> PaletteCommand,
+) -> Vec<PaletteEntry<PaletteCommand>> {
+    actions
+        .into_iter()
+        .map(|action| {
+            let key = key_mappings
+                .iter()
+                .find(|(_, bound)| **bound == action)
+                .map(|(key, _)| *key);
+            PaletteEntry::new(
+                text(action, key.unwrap_or(Key::Unknown)),
+                key,
+                command(action),
+            )
+        })
+        .collect()
+}
+
 impl App {
     pub async fn new(config: Config) -> anyhow::Result<App> {
         let mut jira = Jira::new(
@@ -210,88 +774,146 @@ impl App {
         )
         .await?;
         let projects = &jira.get_jira_projects().await?;
+        let unknown_fields = jira
+            .load_custom_fields(&config.jira_config.custom_fields.clone().unwrap_or_default())
+            .await?;
 
-        Ok(Self {
+        let mut notifications = NotificationsWidget::new(config.key_config.clone());
+        notifications.update(jira.get_notifications().await?);
+        let notifications_config = config.jira_config.notifications.clone().unwrap_or_default();
+        let notifications_rx = match notifications_config.enabled {
+            Some(false) => None,
+            _ => {
+                let (tx, rx) = mpsc::unbounded_channel();
+                let interval = notifications_config
+                    .poll_interval_secs
+                    .unwrap_or(DEFAULT_POLL_INTERVAL_SECS);
+                NotificationPoller::new(jira.client.clone(), jira.db.clone())
+                    .spawn(Duration::from_secs(interval), tx);
+                Some(rx)
+            }
+        };
+
+        let mut app = Self {
+            boards: BoardsWidget::new(config.key_config.clone()),
+            boards_key_mappings: BoardsAction::key_mappings(&config.key_config),
+            bulk: BulkWidget::new(config.key_config.clone()),
+            bulk_key_mappings: BulkAction::key_mappings(&config.key_config),
+            bulk_rx: None,
             comments_list: CommentsList::new(config.key_config.clone()),
             comment_add: CommentAdd::new(config.key_config.clone()),
-            comments_key_mappings: {
-                let mut map = HashMap::new();
-                map.insert(config.key_config.open_help, CommentsAction::OpenHelp);
-                map.insert(
-                    config.key_config.ticket_add_comments,
-                    CommentsAction::AddComment,
-                );
-                map.insert(config.key_config.esc, CommentsAction::FocusTickets);
-                map
-            },
+            comments_key_mappings: CommentsAction::key_mappings(&config.key_config),
             components: ComponentsWidget::new(config.key_config.clone()),
             create_ticket: CreateTicketWidget::new(config.key_config.clone()),
             config: config.clone(),
+            epics: EpicsWidget::new(config.key_config.clone()),
+            epics_key_mappings: EpicsAction::key_mappings(&config.key_config),
+            editor_request: None,
             error: ErrorComponent::new(config.key_config.clone()),
+            filters: FiltersWidget::new(config.key_config.clone()),
+            filters_key_mappings: FiltersAction::key_mappings(&config.key_config),
             focus: Focus::Projects,
             help: HelpWidget::new(config.key_config.clone()),
+            history: HistoryWidget::new(config.key_config.clone()),
+            history_key_mappings: HistoryAction::key_mappings(&config.key_config),
             jira,
+            jql: JqlWidget::new(config.key_config.clone()),
+            kanban: KanbanWidget::new(config.key_config.clone()),
+            kanban_key_mappings: KanbanAction::key_mappings(&config.key_config),
             labels: LabelsWidget::new(config.key_config.clone()),
-            parent_key_mappings: {
-                let mut map = HashMap::new();
-                map.insert(config.key_config.open_help, ParentAction::OpenHelp);
-                map.insert(config.key_config.previous, ParentAction::FocusComponent);
-                map.insert(config.key_config.next, ParentAction::FocusRelation);
-                map
-            },
+            my_work: MyWorkWidget::new(config.key_config.clone()),
+            notifications,
+            notifications_key_mappings: NotificationsAction::key_mappings(&config.key_config),
+            notifications_rx,
+            my_work_key_mappings: MyWorkAction::key_mappings(&config.key_config),
+            parent_key_mappings: ParentAction::key_mappings(&config.key_config),
+            layout: PaneLayout::default(),
+            panes: vec![],
+            pending_key: None,
+            palette: CommandPalette::new(config.key_config.clone()),
+            quit: false,
             parent: TicketParentWidget::new(config.key_config.clone(), &config.jira_config.domain),
 
             projects: ProjectsWidget::new(projects, config.key_config.clone()),
-            projects_key_mappings: {
-                let mut map = HashMap::new();
-                map.insert(config.key_config.open_help, ProjectsAction::OpenHelp);
-                map.insert(config.key_config.enter, ProjectsAction::SelectProject);
-                map.insert(config.key_config.filter, ProjectsAction::SearchProjects);
-                map.insert(config.key_config.next_page, ProjectsAction::NextPage);
-                map.insert(
-                    config.key_config.previous_page,
-                    ProjectsAction::PreviousPage,
-                );
-                map.insert(config.key_config.reset, ProjectsAction::Reset);
-                map
-            },
+            projects_key_mappings: ProjectsAction::key_mappings(&config.key_config),
 
             relation: RelationWidget::new(config.key_config.clone(), &config.jira_config.domain),
             search_projects: SearchProjectsWidget::new(projects),
             search_tickets: SearchTicketsWidget::new(),
+            ticket_finder: TicketFinderWidget::new(config.key_config.clone()),
+            ticket_source: TicketSource::Project,
             tickets: TicketWidget::new(
                 config.key_config.clone(),
                 config.jira_config.domain.clone(),
             ),
-            tickets_key_mappings: {
-                let mut map = HashMap::new();
-                map.insert(config.key_config.previous, TicketsAction::FocusRelation);
-                map.insert(config.key_config.next, TicketsAction::FocusLabels);
-                map.insert(
-                    config.key_config.ticket_view_comments,
-                    TicketsAction::OpenComments,
-                );
-                map.insert(
-                    config.key_config.open_create_ticket,
-                    TicketsAction::OpenCreateTicket,
-                );
-                map.insert(config.key_config.esc, TicketsAction::OpenProjects);
-                map.insert(
-                    config.key_config.ticket_transition,
-                    TicketsAction::OpenTicketTransition,
-                );
-                map.insert(config.key_config.open_help, TicketsAction::OpenHelp);
-                map.insert(config.key_config.filter, TicketsAction::SearchTickets);
-                map.insert(config.key_config.next_page, TicketsAction::NextPage);
-                map.insert(config.key_config.previous_page, TicketsAction::PreviousPage);
-                map.insert(config.key_config.reset, TicketsAction::Reset);
-                map
-            },
+            tickets_key_mappings: TicketsAction::key_mappings(&config.key_config),
             ticket_transition: TransitionWidget::new(Vec::new(), config.key_config.clone()),
-        })
+            zoomed: false,
+        };
+        app.sync_notification_markers();
+        app.tickets.custom_fields = app.jira.custom_fields.clone();
+        let (columns, sort, column_problems) = ticket_columns::from_config(
+            &config.jira_config.ticket_table.clone().unwrap_or_default(),
+            &app.jira.custom_fields,
+        );
+        app.tickets.columns = columns;
+        app.tickets.sort = sort;
+        if !column_problems.is_empty() {
+            app.error.set(format!(
+                "Invalid [ticket_table] config: {}",
+                column_problems.join(", ")
+            ))?;
+        }
+        let (layout, layout_problems) =
+            pane_layout::from_config(&config.jira_config.layout.clone().unwrap_or_default());
+        app.layout = layout;
+        if !layout_problems.is_empty() {
+            app.error.set(format!(
+                "Invalid [layout] config: {}",
+                layout_problems.join(", ")
+            ))?;
+        }
+        if !config.key_config_problems.is_empty() {
+            app.error.set(format!(
+                "Invalid [key_config] config: {}",
+                config.key_config_problems.join(", ")
+            ))?;
+        }
+        let (theme, mut theme_problems) =
+            theme::from_config(&config.jira_config.theme.clone().unwrap_or_default());
+        if let Err(e) = theme::set_theme(theme) {
+            theme_problems.push(e);
+        }
+        if !theme_problems.is_empty() {
+            app.error.set(format!(
+                "Invalid [theme] config: {}",
+                theme_problems.join(", ")
+            ))?;
+        }
+        let (templates, template_problems) = templates::load_templates();
+        app.create_ticket.set_templates(templates);
+        if !template_problems.is_empty() {
+            app.error.set(format!(
+                "Invalid ticket templates: {}",
+                template_problems.join(", ")
+            ))?;
+        }
+        if !unknown_fields.is_empty() {
+            app.error.set(format!(
+                "Unknown custom fields in config: {}",
+                unknown_fields.join(", ")
+            ))?;
+        }
+        Ok(app)
     }
 
     pub fn draw<B: Backend>(&mut self, f: &mut Frame<'_, B>) -> anyhow::Result<()> {
+        self.draw_screen(f)?;
+        self.palette.draw(f);
+        Ok(())
+    }
+
+    fn draw_screen<B: Backend>(&mut self, f: &mut Frame<'_, B>) -> anyhow::Result<()> {
         if let Focus::Projects = self.focus {
             self.projects
                 .draw(f, matches!(self.focus, Focus::Projects), f.size())?;
@@ -301,6 +923,67 @@ impl App {
             return Ok(());
         }
 
+        if let Focus::Boards = self.focus {
+            self.boards
+                .draw(f, matches!(self.focus, Focus::Boards), f.size())?;
+            self.help.draw(f, Rect::default(), false)?;
+            self.error.draw(f, Rect::default(), false)?;
+            return Ok(());
+        }
+
+        if let Focus::Epics = self.focus {
+            self.epics
+                .draw(f, matches!(self.focus, Focus::Epics), f.size())?;
+            self.help.draw(f, Rect::default(), false)?;
+            self.error.draw(f, Rect::default(), false)?;
+            return Ok(());
+        }
+
+        if let Focus::Bulk = self.focus {
+            let selected_count = self.tickets.marked_keys.len();
+            self.bulk.draw(
+                f,
+                matches!(self.focus, Focus::Bulk),
+                selected_count,
+                f.size(),
+            )?;
+            self.help.draw(f, Rect::default(), false)?;
+            self.error.draw(f, Rect::default(), false)?;
+            return Ok(());
+        }
+
+        if let Focus::Filters = self.focus {
+            self.filters
+                .draw(f, matches!(self.focus, Focus::Filters), f.size())?;
+            self.help.draw(f, Rect::default(), false)?;
+            self.error.draw(f, Rect::default(), false)?;
+            return Ok(());
+        }
+
+        if let Focus::Kanban = self.focus {
+            self.kanban
+                .draw(f, matches!(self.focus, Focus::Kanban), f.size())?;
+            self.help.draw(f, Rect::default(), false)?;
+            self.error.draw(f, Rect::default(), false)?;
+            return Ok(());
+        }
+
+        if let Focus::MyWork = self.focus {
+            self.my_work
+                .draw(f, matches!(self.focus, Focus::MyWork), f.size())?;
+            self.help.draw(f, Rect::default(), false)?;
+            self.error.draw(f, Rect::default(), false)?;
+            return Ok(());
+        }
+
+        if let Focus::Notifications = self.focus {
+            self.notifications
+                .draw(f, matches!(self.focus, Focus::Notifications), f.size())?;
+            self.help.draw(f, Rect::default(), false)?;
+            self.error.draw(f, Rect::default(), false)?;
+            return Ok(());
+        }
+
         if let Focus::SearchProjects = self.focus {
             self.search_projects.draw(f)?;
             self.help.draw(f, Rect::default(), false)?;
@@ -308,6 +991,19 @@ impl App {
             return Ok(());
         }
 
+        if let Focus::Jql = self.focus {
+            self.jql.draw(f)?;
+            self.help.draw(f, Rect::default(), false)?;
+            self.error.draw(f, Rect::default(), false)?;
+            return Ok(());
+        }
+
+        if let Focus::TicketFinder = self.focus {
+            self.ticket_finder.draw(f)?;
+            self.error.draw(f, Rect::default(), false)?;
+            return Ok(());
+        }
+
         if let Focus::SearchTickets = self.focus {
             self.search_tickets.draw(f)?;
             self.help.draw(f, Rect::default(), false)?;
@@ -338,79 +1034,69 @@ impl App {
             return Ok(());
         }
 
-        let main_chunks = Layout::default()
-            .direction(Direction::Vertical)
-            .constraints([Constraint::Percentage(85), Constraint::Percentage(15)])
-            .split(f.size());
-
-        let description_metadata = Layout::default()
-            .direction(Direction::Horizontal)
-            .constraints([Constraint::Percentage(40), Constraint::Percentage(60)])
-            .split(main_chunks[0]);
-
-        let ticket_relation = main_chunks[1];
-
-        let ticket_left_chunks = Layout::default()
-            .direction(Direction::Vertical)
-            .constraints([Constraint::Percentage(45), Constraint::Percentage(40)])
-            .split(description_metadata[0]);
-
-        let ticket_metadata_chunks = Layout::default()
-            .direction(Direction::Vertical)
-            .constraints([
-                Constraint::Percentage(40),
-                Constraint::Percentage(40),
-                Constraint::Percentage(20),
-            ])
-            .split(ticket_left_chunks[1]);
-
-        let ticket_list = ticket_left_chunks[0];
-        let ticket_labels = ticket_metadata_chunks[0];
-        let ticket_component = ticket_metadata_chunks[1];
-        let ticket_parent = ticket_metadata_chunks[2];
-
-        let ticket_right_chunks = Layout::default()
-            .direction(Direction::Vertical)
-            .constraints([Constraint::Percentage(100)])
-            .split(description_metadata[1]);
-
-        let ticket_description = ticket_right_chunks[0];
-
-        self.tickets.draw(
-            f,
-            ticket_description,
-            matches!(self.focus, Focus::Tickets),
-            ticket_list,
-        )?;
-
-        self.labels.draw(
-            f,
-            matches!(self.focus, Focus::Labels),
-            ticket_labels,
-            self.tickets.selected(),
-        )?;
-
-        self.components.draw(
-            f,
-            matches!(self.focus, Focus::Components),
-            ticket_component,
-            self.tickets.selected(),
-        )?;
-
-        self.parent.draw(
-            f,
-            matches!(self.focus, Focus::TicketParent),
-            ticket_parent,
-            self.tickets.selected(),
-        )?;
-
-        self.relation.draw(
-            f,
-            matches!(self.focus, Focus::TicketRelation),
-            ticket_relation,
-            self.tickets.selected(),
-        )?;
-
+        // A pane the layout leaves out is shown zoomed while it has the focus
+        let zoomed = self
+            .focused_pane()
+            .filter(|pane| self.zoomed || !self.layout.contains(*pane));
+        self.panes = self.layout.split(f.size(), zoomed);
+        let pane_rect = |pane: Pane| {
+            self.panes
+                .iter()
+                .find(|(p, _)| *p == pane)
+                .map(|(_, rect)| *rect)
+        };
+        let ticket_list = pane_rect(Pane::Tickets);
+        let ticket_description = pane_rect(Pane::Description).unwrap_or_default();
+        let ticket_labels = pane_rect(Pane::Labels);
+        let ticket_component = pane_rect(Pane::Components);
+        let ticket_parent = pane_rect(Pane::Parent);
+        let ticket_relation = pane_rect(Pane::Relation);
+
+        if let Some(ticket_list) = ticket_list {
+            self.tickets.draw(
+                f,
+                ticket_description,
+                matches!(self.focus, Focus::Tickets),
+                ticket_list,
+            )?;
+        }
+
+        if let Some(ticket_labels) = ticket_labels {
+            self.labels.draw(
+                f,
+                matches!(self.focus, Focus::Labels),
+                ticket_labels,
+                self.tickets.selected(),
+            )?;
+        }
+
+        if let Some(ticket_component) = ticket_component {
+            self.components.draw(
+                f,
+                matches!(self.focus, Focus::Components),
+                ticket_component,
+                self.tickets.selected(),
+            )?;
+        }
+
+        if let Some(ticket_parent) = ticket_parent {
+            self.parent.draw(
+                f,
+                matches!(self.focus, Focus::TicketParent),
+                ticket_parent,
+                self.tickets.selected(),
+            )?;
+        }
+
+        if let Some(ticket_relation) = ticket_relation {
+            self.relation.draw(
+                f,
+                matches!(self.focus, Focus::TicketRelation),
+                ticket_relation,
+                self.tickets.selected(),
+            )?;
+        }
+
         if let Focus::CommentsList = self.focus {
             self.comments_list
                 .draw(f, matches!(self.focus, Focus::Projects), f.size())?;
@@ -418,6 +1104,14 @@ impl App {
             return Ok(());
         }
 
+        if let Focus::History = self.focus {
+            self.history
+                .draw(f, matches!(self.focus, Focus::History), f.size())?;
+            self.help.draw(f, Rect::default(), false)?;
+            self.error.draw(f, Rect::default(), false)?;
+            return Ok(());
+        }
+
         self.help.draw(f, Rect::default(), false)?;
         self.error.draw(f, Rect::default(), false)?;
 
@@ -445,18 +1139,279 @@ impl App {
         res
     }
 
+    // event waits for the second key of a sequence like "g g" before handing
+    // it on, keys that do not finish a sequence are handed on one by one
     pub async fn event(&mut self, key: Key) -> anyhow::Result<EventState> {
+        if self.palette.is_visible() {
+            self.palette.event(key);
+            return match self.palette.take_chosen() {
+                Some(command) => self.run_command(command).await,
+                None => Ok(EventState::Consumed),
+            };
+        }
+        if let Some((first, _)) = self.pending_key.take() {
+            if let Key::Char(second) = key {
+                let state = self.key_event(Key::Chord(first, second)).await?;
+                if state.is_consumed() || self.quit {
+                    return Ok(state);
+                }
+            }
+            self.key_event(Key::Char(first)).await?;
+        }
+        match key {
+            Key::Char(c) if !self.editing() && self.config.key_config.starts_sequence(c) => {
+                self.pending_key = Some((c, Instant::now()));
+                Ok(EventState::Consumed)
+            }
+            _ => self.key_event(key).await,
+        }
+    }
+
+    // editing tells whether the focused widget takes the typed keys as text,
+    // the first key of a sequence is not held back then
+    fn editing(&self) -> bool {
+        match self.focus {
+            Focus::Bulk => self.bulk.is_editing(),
+            Focus::CommentsAdd => self.comment_add.is_editing(),
+            Focus::CreateTicket => self.create_ticket.is_editing(),
+            Focus::Filters => self.filters.input_mode == InputMode::Editing,
+            Focus::Jql => self.jql.is_editing(),
+            Focus::SearchProjects => self.search_projects.input_mode == InputMode::Editing,
+            Focus::SearchTickets => self.search_tickets.input_mode == InputMode::Editing,
+            Focus::TicketFinder => true,
+            Focus::TicketTransition => self.ticket_transition.input_mode == InputMode::Editing,
+            _ => false,
+        }
+    }
+
+    async fn key_event(&mut self, key: Key) -> anyhow::Result<EventState> {
         if self.widget_event(key).await?.is_consumed() {
             return Ok(EventState::Consumed);
         }
+        if key == self.config.key_config.zoom && self.focused_pane().is_some() {
+            self.zoomed = !self.zoomed;
+            return Ok(EventState::Consumed);
+        }
 
         if self.move_focus(key).await?.is_consumed() {
             return Ok(EventState::Consumed);
         };
 
+        if key == self.config.key_config.open_finder {
+            let tickets = self.jira.get_cached_tickets().await?;
+            self.ticket_finder.open(tickets);
+            self.focus = Focus::TicketFinder;
+            return Ok(EventState::Consumed);
+        }
+
+        if key == self.config.key_config.open_palette {
+            let entries = self.palette_entries();
+            if !entries.is_empty() {
+                self.palette.open(entries);
+                return Ok(EventState::Consumed);
+            }
+        }
+
+        if key == self.config.key_config.quit || key == self.config.key_config.exit {
+            self.quit = true;
+        }
         Ok(EventState::NotConsumed)
     }
 
+    // palette_entries are the actions of the focused screen. The actions of
+    // the app are listed even without a key, widget actions run by their key.
+    fn palette_entries(&self) -> Vec<PaletteEntry<PaletteCommand>> {
+        match self.focus {
+            Focus::Projects => [
+                action_entries(
+                    ProjectsAction::ALL,
+                    &self.projects_key_mappings,
+                    ProjectsAction::to_command_text,
+                    PaletteCommand::Projects,
+                ),
+                keyed_entries(&self.projects.key_mappings, |a, k| a.to_command_text(k)),
+            ]
+            .concat(),
+            Focus::Tickets => [
+                action_entries(
+                    TicketsAction::ALL,
+                    &self.tickets_key_mappings,
+                    TicketsAction::to_command_text,
+                    PaletteCommand::Tickets,
+                ),
+                keyed_entries(&self.tickets.key_mappings, |a, k| a.to_command_text(k)),
+            ]
+            .concat(),
+            Focus::CommentsList => [
+                action_entries(
+                    CommentsAction::ALL,
+                    &self.comments_key_mappings,
+                    CommentsAction::to_command_text,
+                    PaletteCommand::Comments,
+                ),
+                keyed_entries(&self.comments_list.key_mappings, |a, k| {
+                    a.to_command_text(k)
+                }),
+            ]
+            .concat(),
+            Focus::Boards => [
+                keyed_entries(&self.boards_key_mappings, BoardsAction::to_command_text),
+                keyed_entries(&self.boards.key_mappings, |a, k| a.to_command_text(k)),
+            ]
+            .concat(),
+            Focus::Bulk => [
+                keyed_entries(&self.bulk_key_mappings, BulkAction::to_command_text),
+                keyed_entries(&self.bulk.key_mappings, |a, k| a.to_command_text(k)),
+            ]
+            .concat(),
+            Focus::Epics => [
+                keyed_entries(&self.epics_key_mappings, EpicsAction::to_command_text),
+                keyed_entries(&self.epics.key_mappings, |a, k| a.to_command_text(k)),
+            ]
+            .concat(),
+            Focus::Filters => [
+                keyed_entries(&self.filters_key_mappings, FiltersAction::to_command_text),
+                keyed_entries(&self.filters.key_mappings, |a, k| a.to_command_text(k)),
+            ]
+            .concat(),
+            Focus::History => [
+                keyed_entries(&self.history_key_mappings, HistoryAction::to_command_text),
+                keyed_entries(&self.history.key_mappings, |a, k| a.to_command_text(k)),
+            ]
+            .concat(),
+            Focus::Kanban => [
+                keyed_entries(&self.kanban_key_mappings, KanbanAction::to_command_text),
+                keyed_entries(&self.kanban.key_mappings, |a, k| a.to_command_text(k)),
+            ]
+            .concat(),
+            Focus::MyWork => [
+                keyed_entries(&self.my_work_key_mappings, MyWorkAction::to_command_text),
+                keyed_entries(&self.my_work.key_mappings, |a, k| a.to_command_text(k)),
+            ]
+            .concat(),
+            Focus::Notifications => [
+                keyed_entries(
+                    &self.notifications_key_mappings,
+                    NotificationsAction::to_command_text,
+                ),
+                keyed_entries(&self.notifications.key_mappings, |a, k| {
+                    a.to_command_text(k)
+                }),
+            ]
+            .concat(),
+            Focus::TicketParent => [
+                keyed_entries(&self.parent_key_mappings, ParentAction::to_command_text),
+                keyed_entries(&self.parent.key_mappings, |a, k| a.to_command_text(k)),
+            ]
+            .concat(),
+            _ => vec![],
+        }
+    }
+
+    async fn run_command(&mut self, command: PaletteCommand) -> anyhow::Result<EventState> {
+        match command {
+            PaletteCommand::Key(key) => self.key_event(key).await,
+            PaletteCommand::Comments(action) => self.comments_action(action).await,
+            PaletteCommand::Projects(action) => self.projects_action(action).await,
+            PaletteCommand::Tickets(action) => self.tickets_action(action).await,
+        }
+    }
+
+    // focused_pane is the pane of the tickets screen that has the focus
+    fn focused_pane(&self) -> Option<Pane> {
+        match self.focus {
+            Focus::Tickets => Some(Pane::Tickets),
+            Focus::Labels => Some(Pane::Labels),
+            Focus::Components => Some(Pane::Components),
+            Focus::TicketParent => Some(Pane::Parent),
+            Focus::TicketRelation => Some(Pane::Relation),
+            _ => None,
+        }
+    }
+
+    // mouse focuses the pane under the pointer and selects the clicked row,
+    // double clicks open the ticket in the browser and the wheel scrolls the
+    // description and comments
+    pub async fn mouse(&mut self, mouse: Mouse) -> anyhow::Result<EventState> {
+        if let Focus::CommentsList = self.focus {
+            match mouse.action {
+                MouseAction::ScrollDown => self.comments_list.comment_contents_down(1),
+                MouseAction::ScrollUp => self.comments_list.comment_contents_up(1),
+                _ => return Ok(EventState::NotConsumed),
+            }
+            return Ok(EventState::Consumed);
+        }
+        if self.focused_pane().is_none() {
+            return Ok(EventState::NotConsumed);
+        }
+        let Some(&(pane, rect)) = self
+            .panes
+            .iter()
+            .find(|(_, rect)| widgets::contains(*rect, mouse.column, mouse.row))
+        else {
+            return Ok(EventState::NotConsumed);
+        };
+        match (pane, mouse.action) {
+            (Pane::Description, MouseAction::ScrollDown) => self.tickets.scroll_down_description(1),
+            (Pane::Description, MouseAction::ScrollUp) => self.tickets.scroll_up_description(1),
+            (_, MouseAction::ScrollDown | MouseAction::ScrollUp) => {
+                return Ok(EventState::NotConsumed)
+            }
+            (Pane::Tickets, action) => {
+                self.focus = Focus::Tickets;
+                if self.tickets.click(rect, mouse.row) {
+                    self.mark_selected_ticket_read().await?;
+                    if action == MouseAction::DoubleClick {
+                        self.tickets.open_browser();
+                    }
+                }
+            }
+            (Pane::Description, _) => self.focus = Focus::Tickets,
+            (Pane::Labels, _) => {
+                self.focus = Focus::Labels;
+                self.labels.click(rect, mouse.row);
+            }
+            (Pane::Components, _) => {
+                self.focus = Focus::Components;
+                self.components.click(rect, mouse.row);
+            }
+            (Pane::Parent, action) => {
+                self.focus = Focus::TicketParent;
+                if action == MouseAction::DoubleClick {
+                    self.parent.open_browser();
+                }
+            }
+            (Pane::Relation, action) => {
+                self.focus = Focus::TicketRelation;
+                if self.relation.click(rect, mouse.row) && action == MouseAction::DoubleClick {
+                    self.relation.open_browser();
+                }
+            }
+        }
+        Ok(EventState::Consumed)
+    }
+
+    // paste hands pasted text to the focused input, widgets that are not
+    // being edited ignore it
+    pub fn paste(&mut self, text: &str) -> anyhow::Result<EventState> {
+        if self.palette.is_visible() {
+            return Ok(self.palette.paste(text));
+        }
+        let state = match self.focus {
+            Focus::Bulk => self.bulk.paste(text),
+            Focus::CommentsAdd => self.comment_add.paste(text),
+            Focus::CreateTicket => self.create_ticket.paste(text),
+            Focus::Filters => self.filters.paste(text),
+            Focus::Jql => self.jql.paste(text),
+            Focus::SearchProjects => self.search_projects.paste(text),
+            Focus::SearchTickets => self.search_tickets.paste(text),
+            Focus::TicketFinder => self.ticket_finder.paste(text),
+            Focus::TicketTransition => self.ticket_transition.paste(text),
+            _ => EventState::NotConsumed,
+        };
+        Ok(state)
+    }
+
     pub async fn next_project_page(&mut self) -> anyhow::Result<()> {
         self.jira.get_next_project_page().await?;
         Ok(())
@@ -478,7 +1433,18 @@ impl App {
     }
 
     pub async fn next_ticket_page(&mut self) -> anyhow::Result<()> {
-        let project = self.projects.selected().unwrap();
+        self.tickets.marked_keys.clear();
+        if self.ticket_source != TicketSource::Project {
+            let tickets = self.jira.get_next_source_page(&self.ticket_source).await?;
+            self.tickets.rank_offset = self
+                .ticket_source
+                .is_ranked()
+                .then_some(self.jira.tickets_start_at);
+            return self.tickets.update(tickets, true).await;
+        }
+        let Some(project) = self.ticket_project()? else {
+            return Ok(());
+        };
         self.jira.get_next_ticket_page(&project.key).await?;
         self.tickets
             .update(self.jira.tickets_api.issues.clone(), true)
@@ -487,7 +1453,21 @@ impl App {
     }
 
     pub async fn previous_ticket_page(&mut self) -> anyhow::Result<()> {
-        let project = self.projects.selected().unwrap();
+        self.tickets.marked_keys.clear();
+        if self.ticket_source != TicketSource::Project {
+            let tickets = self
+                .jira
+                .get_previous_source_page(&self.ticket_source)
+                .await?;
+            self.tickets.rank_offset = self
+                .ticket_source
+                .is_ranked()
+                .then_some(self.jira.tickets_start_at);
+            return self.tickets.update(tickets, true).await;
+        }
+        let Some(project) = self.ticket_project()? else {
+            return Ok(());
+        };
         self.jira.get_previous_tickets_page(&project.key).await?;
         self.tickets
             .update(self.jira.tickets_api.issues.clone(), true)
@@ -496,7 +1476,17 @@ impl App {
     }
 
     pub async fn update_all_tickets(&mut self) -> anyhow::Result<()> {
-        let project = self.projects.selected().unwrap();
+        if self.ticket_source != TicketSource::Project {
+            let tickets = self.jira.get_source_tickets(&self.ticket_source).await?;
+            self.tickets.rank_offset = self
+                .ticket_source
+                .is_ranked()
+                .then_some(self.jira.tickets_start_at);
+            return self.tickets.update(tickets, true).await;
+        }
+        let Some(project) = self.ticket_project()? else {
+            return Ok(());
+        };
         self.jira.get_jira_tickets(&project.key).await?;
         self.tickets
             .update(self.jira.tickets_api.issues.clone(), true)
@@ -504,130 +1494,1129 @@ impl App {
         Ok(())
     }
 
-    pub async fn update_single_ticket(&mut self, ticket_key: &str) -> anyhow::Result<()> {
-        let ticket = self.jira.search_cache_ticket(ticket_key).await?;
-        self.tickets.remove_ticket(ticket_key)?;
-        self.tickets.update(vec![ticket], false).await?;
-        self.tickets.select_ticket(ticket_key)
+    // use_project_tickets switches the ticket list back to the selected project
+    fn use_project_tickets(&mut self) {
+        if self.ticket_source != TicketSource::Project {
+            self.ticket_source = TicketSource::Project;
+            self.jira.tickets_start_at = 0;
+        }
+        self.tickets.context = None;
+        self.tickets.rank_offset = None;
+        self.tickets.marked_keys.clear();
     }
 
-    pub async fn update_search_tickets(&mut self) -> anyhow::Result<()> {
-        self.search_tickets.update(self.tickets.tickets.clone());
+    // ticket_project is the project the listed tickets belong to. Outside the
+    // project list it is the project of the selected ticket, so screens reached
+    // from My Work, the finder or a JQL search work without a project chosen.
+    fn ticket_project(&mut self) -> anyhow::Result<Option<Project>> {
+        let ticket_project = match self.ticket_source {
+            TicketSource::Project => None,
+            _ => self
+                .tickets
+                .selected()
+                .map(|t| t.fields.project.key.clone()),
+        };
+        let Some(key) = ticket_project else {
+            let project = self.projects.selected().cloned();
+            if project.is_none() {
+                self.error.set("Select a project first".to_string())?;
+            }
+            return Ok(project);
+        };
+        self.projects.select_project(&key)?;
+        match self.projects.selected().cloned() {
+            Some(project) if project.key == key => Ok(Some(project)),
+            _ => {
+                self.error
+                    .set(format!("Project {key} is not in the projects list"))?;
+                Ok(None)
+            }
+        }
+    }
+
+    pub async fn update_boards(&mut self) -> anyhow::Result<()> {
+        let Some(project) = self.projects.selected() else {
+            return Ok(());
+        };
+        let boards = self
+            .jira
+            .agile_api
+            .get_boards(&self.jira.client, &project.key)
+            .await?;
+        self.boards.update_boards(&project.key, boards);
         Ok(())
     }
 
-    pub async fn update_comments_view(&mut self) -> anyhow::Result<()> {
-        let comments = match self.tickets.selected() {
-            None => return Ok(()),
-            Some(t) => t.get_comments(&self.jira.db, &self.jira.client).await?,
+    pub async fn update_sprints(&mut self) -> anyhow::Result<()> {
+        let Some(board) = self.boards.selected_board().cloned() else {
+            return Ok(());
         };
-        self.comments_list.comments = Some(comments);
+        let sprints = self
+            .jira
+            .agile_api
+            .get_sprints(&self.jira.client, &board)
+            .await?;
+        self.boards.update_sprints(sprints);
         Ok(())
     }
 
-    pub async fn add_comment(&mut self, comments: &[String]) -> anyhow::Result<()> {
-        let ticket = match self.tickets.selected() {
+    // open_boards shows the boards of the selected project, optionally to pick
+    // the sprint the given tickets should be moved to
+    pub async fn open_boards(&mut self, move_tickets: Vec<String>) -> anyhow::Result<()> {
+        let project_key = self.projects.selected().map(|p| p.key.clone());
+        if self.boards.project_key != project_key {
+            self.update_boards().await?;
+        }
+        if !self.boards.has_boards() {
+            self.error
+                .set("No agile boards found for this project".to_string())?;
+            return Ok(());
+        }
+        self.boards.move_tickets = move_tickets;
+        self.focus = Focus::Boards;
+        Ok(())
+    }
+
+    pub async fn select_sprint(&mut self) -> anyhow::Result<()> {
+        let Some(board) = self.boards.selected_board().cloned() else {
+            return Ok(());
+        };
+        let sprint = self.boards.selected_sprint().cloned();
+
+        if !self.boards.move_tickets.is_empty() {
+            let ticket_keys = std::mem::take(&mut self.boards.move_tickets);
+            let bulk_move = std::mem::take(&mut self.bulk.sprint_move);
+            if bulk_move {
+                let operation = match &sprint {
+                    Some(s) => format!("Move to {}", s.name),
+                    None => String::from("Move to backlog"),
+                };
+                self.bulk.start(operation, ticket_keys.len(), None);
+            }
+            // Jira moves a chunk of tickets in a single request, so each
+            // ticket of a chunk gets the outcome of its request
+            let mut moved_keys = vec![];
+            let mut failed = None;
+            for chunk in ticket_keys.chunks(MOVE_MAX_TICKETS) {
+                let moved = match &sprint {
+                    Some(s) => {
+                        self.jira
+                            .agile_api
+                            .move_to_sprint(&self.jira.client, s.id, chunk)
+                            .await
+                    }
+                    None => {
+                        self.jira
+                            .agile_api
+                            .move_to_backlog(&self.jira.client, chunk)
+                            .await
+                    }
+                };
+                if bulk_move {
+                    for ticket_key in chunk {
+                        self.bulk.push(BulkResult {
+                            ticket_key: ticket_key.clone(),
+                            outcome: moved.as_ref().map(|_| ()).map_err(|e| e.to_string()),
+                        });
+                    }
+                }
+                match moved {
+                    Ok(()) => moved_keys.extend_from_slice(chunk),
+                    Err(e) if !bulk_move => {
+                        failed = Some(e);
+                        break;
+                    }
+                    Err(_) => {}
+                }
+            }
+            self.refresh_tickets(&moved_keys).await?;
+            if let Some(e) = failed {
+                return Err(e);
+            }
+            self.focus = match bulk_move {
+                true => Focus::Bulk,
+                false => Focus::Tickets,
+            };
+            return Ok(());
+        }
+
+        (self.ticket_source, self.tickets.context) = match sprint {
+            Some(s) => {
+                let context = format!("{} ({})", s.name, s.date_range());
+                (TicketSource::Sprint(s), Some(context))
+            }
+            None if self.boards.backlog_selected() => {
+                let context = format!("{} backlog", board.name);
+                (TicketSource::Backlog(board), Some(context))
+            }
             None => return Ok(()),
-            Some(t) => t,
         };
-        let comment = comments.join(" \n ");
-        ticket
-            .add_comment(&self.jira.db, &comment, &self.jira.client)
+        self.jira.tickets_start_at = 0;
+        self.tickets.ticket_description = None;
+        self.tickets.marked_keys.clear();
+        self.update_all_tickets().await?;
+        self.tickets.select(Some(0));
+        self.focus = Focus::Tickets;
+        Ok(())
+    }
+
+    pub async fn move_ticket_to_backlog(&mut self) -> anyhow::Result<()> {
+        let Some(ticket) = self.tickets.selected() else {
+            return Ok(());
+        };
+        let ticket_key = ticket.key.clone();
+        self.jira
+            .agile_api
+            .move_to_backlog(&self.jira.client, std::slice::from_ref(&ticket_key))
+            .await?;
+        if self.ticket_source == TicketSource::Project {
+            return self.update_single_ticket(&ticket_key).await;
+        }
+        self.jira.jira_ticket_api(&ticket_key).await?;
+        self.update_all_tickets().await
+    }
+
+    pub async fn update_epics(&mut self) -> anyhow::Result<()> {
+        let Some(project) = self.projects.selected() else {
+            return Ok(());
+        };
+        let epics = self
+            .jira
+            .epics_api
+            .get_epics(&self.jira.client, &project.key)
             .await?;
+        self.epics.update(&project.key, epics);
         Ok(())
     }
 
-    pub async fn update_ticket_transitions(&mut self) -> anyhow::Result<()> {
-        let ticket = match self.tickets.selected() {
-            None => return Ok(()),
-            Some(t) => t,
+    pub async fn open_epics(&mut self) -> anyhow::Result<()> {
+        let project_key = self.projects.selected().map(|p| p.key.clone());
+        if self.epics.project_key != project_key {
+            self.update_epics().await?;
+        }
+        if !self.epics.has_epics() {
+            self.error
+                .set("No epics found for this project".to_string())?;
+            return Ok(());
+        }
+        self.focus = Focus::Epics;
+        Ok(())
+    }
+
+    // select_epic lists the children of the selected epic in the tickets layout
+    pub async fn select_epic(&mut self) -> anyhow::Result<()> {
+        let Some(epic) = self.epics.selected().cloned() else {
+            return Ok(());
         };
+        self.tickets.context = Some(format!("{} {}", epic.key, epic.summary));
+        self.ticket_source = TicketSource::Epic(epic.key);
+        self.jira.tickets_start_at = 0;
+        self.tickets.ticket_description = None;
+        self.tickets.marked_keys.clear();
+        self.update_all_tickets().await?;
+        self.tickets.select(Some(0));
+        self.focus = Focus::Tickets;
+        Ok(())
+    }
 
-        let transitions = ticket.get_transitions(&self.jira.client).await?;
-        self.ticket_transition.update(&transitions);
+    // open_filters lists the favourite filters, they are only pulled once
+    pub async fn open_filters(&mut self) -> anyhow::Result<()> {
+        if !self.filters.loaded {
+            self.show_favourite_filters().await?;
+        }
+        self.focus = Focus::Filters;
         Ok(())
     }
 
-    pub async fn move_ticket(&mut self) -> anyhow::Result<()> {
-        let ticket = match self.tickets.selected() {
-            Some(t) => t,
-            None => return Ok(()),
+    pub async fn show_favourite_filters(&mut self) -> anyhow::Result<()> {
+        let filters = self
+            .jira
+            .filters_api
+            .get_favourites(&self.jira.client)
+            .await?;
+        self.filters.update(filters);
+        Ok(())
+    }
+
+    // search_filters looks up every filter matching the search input, an empty
+    // search goes back to the favourites
+    pub async fn search_filters(&mut self) -> anyhow::Result<()> {
+        let filter_name = self.filters.input().trim().to_string();
+        if filter_name.is_empty() {
+            return self.show_favourite_filters().await;
+        }
+        let filters = self
+            .jira
+            .filters_api
+            .search_filters(&self.jira.client, &filter_name)
+            .await?;
+        if filters.is_empty() {
+            self.error
+                .set(format!("No filters found matching {filter_name}"))?;
+        }
+        self.filters.update(filters);
+        Ok(())
+    }
+
+    // select_filter lists the results of the selected filter in the tickets layout
+    pub async fn select_filter(&mut self) -> anyhow::Result<()> {
+        let Some(filter) = self.filters.selected().cloned() else {
+            return Ok(());
         };
-        let transition = self.ticket_transition.selected_transition().unwrap();
-        let data = PostTicketTransition {
-            transition: TicketTransition {
-                fields: None,
-                has_screen: None,
-                id: transition.id.clone(),
-                name: transition.name.clone(),
-            },
+        self.tickets.context = Some(format!("Filter: {}", filter.name));
+        self.ticket_source = TicketSource::Filter(filter);
+        self.jira.tickets_start_at = 0;
+        self.tickets.ticket_description = None;
+        self.tickets.marked_keys.clear();
+        self.update_all_tickets().await?;
+        self.tickets.select(Some(0));
+        self.focus = Focus::Tickets;
+        Ok(())
+    }
+
+    pub async fn update_my_work(&mut self) -> anyhow::Result<()> {
+        let lists = self.jira.my_work_api.get_my_work(&self.jira.client).await?;
+        self.my_work.update(lists);
+        Ok(())
+    }
+
+    pub async fn open_my_work(&mut self) -> anyhow::Result<()> {
+        if !self.my_work.has_lists() {
+            self.update_my_work().await?;
+        }
+        self.focus = Focus::MyWork;
+        Ok(())
+    }
+
+    // select_my_work lists the section of the selected ticket in the tickets
+    // layout and keeps that ticket selected
+    pub async fn select_my_work(&mut self) -> anyhow::Result<()> {
+        let Some((list, ticket)) = self.my_work.selected() else {
+            return Ok(());
         };
-        ticket.transition_ticket(data, &self.jira.client).await?;
-        self.jira.jira_ticket_api(&ticket.key).await?;
+        let (context, jql, ticket_key) = (
+            format!("My Work: {}", list.section.title()),
+            list.jql.clone(),
+            ticket.key.clone(),
+        );
+        self.ticket_source = TicketSource::Jql(jql);
+        self.tickets.context = Some(context);
+        self.jira.tickets_start_at = 0;
+        self.tickets.ticket_description = None;
+        self.tickets.marked_keys.clear();
+        self.update_all_tickets().await?;
+        let index = self
+            .tickets
+            .tickets
+            .iter()
+            .position(|t| t.key == ticket_key)
+            .unwrap_or(0);
+        self.tickets.select(Some(index));
+        self.focus = Focus::Tickets;
         Ok(())
     }
 
-    pub async fn widget_event(&mut self, key: Key) -> anyhow::Result<EventState> {
-        if self.error.event(key)?.is_consumed() {
-            return Ok(EventState::Consumed);
+    // jump_to_ticket lists the tickets of the project of the ticket with the
+    // ticket selected. A project missing from the projects list is queried
+    // with JQL instead.
+    async fn jump_to_ticket(&mut self, ticket: TicketData) -> anyhow::Result<()> {
+        let project_key = ticket.fields.project.key.clone();
+        self.projects.select_project(&project_key)?;
+        match self.projects.selected() {
+            Some(project) if project.key == project_key => {
+                self.use_project_tickets();
+            }
+            _ => {
+                let jql = JqlQuery::new(Jql::equals("project", project_key.as_str()))
+                    .order_by("updated", SortOrder::Desc)
+                    .to_string();
+                self.ticket_source = TicketSource::Jql(jql);
+                self.tickets.context = Some(format!("Project: {project_key}"));
+                self.jira.tickets_start_at = 0;
+            }
         }
+        self.tickets.ticket_description = None;
+        self.tickets.marked_keys.clear();
+        self.update_all_tickets().await?;
+        match self.tickets.tickets.iter().any(|t| t.key == ticket.key) {
+            true => self.tickets.select_ticket(&ticket.key)?,
+            false => self.update_single_ticket(&ticket.key).await?,
+        }
+        self.focus = Focus::Tickets;
+        Ok(())
+    }
 
-        match self.focus {
-            Focus::CommentsList => {
-                if self.help.event(key)?.is_consumed() {
-                    self.update_comments_list_commands();
-                    return Ok(EventState::Consumed);
-                }
-                if self.comments_list.event(key)?.is_consumed() {
-                    return Ok(EventState::Consumed);
+    // bulk_request carries out what was picked in the bulk actions view
+    async fn bulk_request(&mut self, request: BulkRequest) -> anyhow::Result<()> {
+        let ticket_keys: Vec<String> = self.tickets.marked_keys.iter().cloned().collect();
+        match request {
+            BulkRequest::SelectMatching(text) => {
+                if self.tickets.mark_matching(&text) == 0 {
+                    self.error
+                        .set(format!("No loaded tickets match \"{text}\""))?;
                 }
+                self.focus = Focus::Tickets;
             }
-            Focus::CommentsAdd => {
-                if self.comment_add.event(key)?.is_consumed() {
-                    if self.comment_add.push_comment && !self.comment_add.messages.is_empty() {
-                        let comments = &self.comment_add.messages.clone();
-                        self.add_comment(comments).await?;
-                        self.comment_add.messages.clear();
-                        self.comment_add.push_comment = false;
-                    }
-                    return Ok(EventState::Consumed);
-                }
+            BulkRequest::Retry(operation, failed_keys) => self.run_bulk(operation, failed_keys),
+            _ if ticket_keys.is_empty() => {
+                self.error
+                    .set("Select tickets before running a bulk action".to_string())?;
             }
-            Focus::Components => {
-                if self.components.event(key)?.is_consumed() {
-                    return Ok(EventState::Consumed);
+            BulkRequest::LoadTransitions => {
+                let transitions =
+                    bulk::get_common_transitions(&self.jira.client, &ticket_keys).await?;
+                if transitions.is_empty() {
+                    self.error
+                        .set("The selected tickets have no status in common".to_string())?;
+                    return Ok(());
                 }
+                self.bulk.set_transitions(transitions);
             }
-            Focus::CreateTicket => {
-                if self.create_ticket.event(key)?.is_consumed() {
-                    let project = self
-                        .projects
-                        .selected()
-                        .expect("Project should have been selected");
-                    if self.create_ticket.push_content {
-                        self.jira
-                            .tickets_api
-                            .create_ticket_api(
-                                &self.jira.client,
-                                self.create_ticket.contents.clone(),
-                                self.create_ticket.get_ticket_type(),
-                                &project.project_id,
-                            )
-                            .await?;
-                        self.create_ticket.push_content = false;
-                        self.create_ticket.contents.summary.clear();
-                        self.create_ticket.contents.description.clear();
-                        self.focus = Focus::Tickets;
-                        self.tickets.tickets.clear();
-                        self.tickets.ticket_description = None;
-                        self.jira.clear_tickets_table().await?;
-                        self.update_all_tickets().await?;
-                    }
-                    return Ok(EventState::Consumed);
-                }
+            BulkRequest::Assign(query) => {
+                let user = match query.is_empty() {
+                    true => None,
+                    false => Some(bulk::find_user(&self.jira.client, &query).await?),
+                };
+                self.run_bulk(BulkOperation::Assign(user), ticket_keys);
             }
-            Focus::Labels => {
-                if self.labels.event(key)?.is_consumed() {
+            BulkRequest::Run(operation) => self.run_bulk(operation, ticket_keys),
+            BulkRequest::MoveToSprint => {
+                self.bulk.sprint_move = true;
+                self.open_boards(ticket_keys).await?;
+            }
+        }
+        Ok(())
+    }
+
+    fn run_bulk(&mut self, operation: BulkOperation, ticket_keys: Vec<String>) {
+        let (tx, rx) = mpsc::unbounded_channel();
+        self.bulk.start(
+            operation.to_string(),
+            ticket_keys.len(),
+            Some(operation.clone()),
+        );
+        bulk::spawn_bulk(self.jira.client.clone(), operation, ticket_keys, tx);
+        self.bulk_rx = Some(rx);
+    }
+
+    // bulk_progress records the requests of the running bulk operation as they
+    // finish and reloads the changed tickets once all are done
+    async fn bulk_progress(&mut self) -> anyhow::Result<()> {
+        let Some(rx) = self.bulk_rx.as_mut() else {
+            return Ok(());
+        };
+        let disconnected = loop {
+            match rx.try_recv() {
+                Ok(result) => self.bulk.push(result),
+                Err(TryRecvError::Empty) => break false,
+                Err(TryRecvError::Disconnected) => break true,
+            }
+        };
+        if !disconnected && !self.bulk.finished() {
+            return Ok(());
+        }
+        self.bulk_rx = None;
+        self.refresh_tickets(&self.bulk.succeeded_keys()).await
+    }
+
+    async fn refresh_tickets(&mut self, ticket_keys: &[String]) -> anyhow::Result<()> {
+        self.jira.refresh_tickets(ticket_keys).await?;
+        self.update_all_tickets().await
+    }
+
+    // tick picks up the notifications found by the background poller and the
+    // progress of a running bulk operation
+    pub async fn tick(&mut self) -> anyhow::Result<()> {
+        if let Some((first, at)) = self.pending_key {
+            if at.elapsed() >= SEQUENCE_TIMEOUT {
+                self.pending_key = None;
+                self.key_event(Key::Char(first)).await?;
+            }
+        }
+        if let Some(rx) = self.notifications_rx.as_mut() {
+            let mut received = false;
+            while let Ok(notifications) = rx.try_recv() {
+                self.notifications.push(notifications);
+                received = true;
+            }
+            if received {
+                self.sync_notification_markers();
+            }
+        }
+        self.bulk_progress().await
+    }
+
+    fn sync_notification_markers(&mut self) {
+        self.tickets.updated_keys = self.notifications.unread_keys();
+        self.tickets.unread_notifications = self.notifications.unread_count();
+    }
+
+    pub async fn mark_notifications_read(
+        &mut self,
+        ticket_key: Option<&str>,
+    ) -> anyhow::Result<()> {
+        self.jira.mark_notifications_read(ticket_key).await?;
+        self.notifications.mark_read(ticket_key);
+        self.sync_notification_markers();
+        Ok(())
+    }
+
+    // mark_selected_ticket_read clears the highlight once an updated ticket is opened
+    async fn mark_selected_ticket_read(&mut self) -> anyhow::Result<()> {
+        let Some(ticket_key) = self.tickets.selected().map(|t| t.key.clone()) else {
+            return Ok(());
+        };
+        if self.tickets.updated_keys.contains(&ticket_key) {
+            self.mark_notifications_read(Some(&ticket_key)).await?;
+        }
+        Ok(())
+    }
+
+    // select_notification lists the tickets of the inbox in the tickets layout
+    // with the notified ticket selected
+    pub async fn select_notification(&mut self) -> anyhow::Result<()> {
+        let Some(ticket_key) = self.notifications.selected().map(|n| n.ticket_key.clone()) else {
+            return Ok(());
+        };
+        let keys = self.notifications.ticket_keys();
+        let jql = JqlQuery::new(Jql::is_in("key", JqlValue::list(keys)))
+            .order_by("updated", SortOrder::Desc)
+            .to_string();
+        self.ticket_source = TicketSource::Jql(jql);
+        self.tickets.context = Some(String::from("Notifications"));
+        self.jira.tickets_start_at = 0;
+        self.tickets.ticket_description = None;
+        self.tickets.marked_keys.clear();
+        self.update_all_tickets().await?;
+        self.tickets.select_ticket(&ticket_key)?;
+        self.mark_notifications_read(Some(&ticket_key)).await?;
+        self.focus = Focus::Tickets;
+        Ok(())
+    }
+
+    // open_jql shows the JQL prompt, Jira's autocomplete data is only pulled once
+    pub async fn open_jql(&mut self) -> anyhow::Result<()> {
+        if self.jql.autocomplete.is_none() {
+            match JqlAutocomplete::from_jira_api(&self.jira.client).await {
+                Ok(autocomplete) => self.jql.autocomplete = Some(autocomplete),
+                Err(e) => debug!("Unable to load JQL autocomplete data: {e}"),
+            }
+        }
+        self.update_jql_menu().await?;
+        self.jql.input_mode = InputMode::Editing;
+        self.focus = Focus::Jql;
+        Ok(())
+    }
+
+    async fn update_jql_menu(&mut self) -> anyhow::Result<()> {
+        let history = self.jira.get_jql_history().await?;
+        let saved = self.config.jira_config.queries.clone().unwrap_or_default();
+        self.jql.update(&saved, history);
+        Ok(())
+    }
+
+    // run_jql lists the query results in the tickets layout, keeping the
+    // current list when Jira rejects the query
+    pub async fn run_jql(&mut self, jql: &str) -> anyhow::Result<()> {
+        let previous_source =
+            std::mem::replace(&mut self.ticket_source, TicketSource::Jql(jql.to_string()));
+        let previous_start_at = self.jira.tickets_start_at;
+        self.jira.tickets_start_at = 0;
+        if let Err(e) = self.update_all_tickets().await {
+            self.ticket_source = previous_source;
+            self.jira.tickets_start_at = previous_start_at;
+            self.error
+                .set(format!("Unable to run query {jql} \n {e}"))?;
+            return Ok(());
+        }
+        self.jira.record_jql_history(jql).await?;
+        self.tickets.context = Some(format!("JQL: {jql}"));
+        self.tickets.ticket_description = None;
+        self.tickets.marked_keys.clear();
+        self.tickets.select(Some(0));
+        self.focus = Focus::Tickets;
+        Ok(())
+    }
+
+    pub async fn save_jql(&mut self, name: &str, jql: &str) -> anyhow::Result<()> {
+        if let Err(e) = config::save_query(name, jql) {
+            self.error
+                .set(format!("Unable to save query {name} \n {e}"))?;
+            return Ok(());
+        }
+        self.config
+            .jira_config
+            .queries
+            .get_or_insert_with(Default::default)
+            .insert(name.to_string(), jql.to_string());
+        self.update_jql_menu().await
+    }
+
+    // update_kanban lays out the cached tickets of the selected project, using
+    // the column configuration of the board picked in the boards screen, or
+    // else the first board of the project. Without a board the columns follow
+    // the status categories, problems loading the board are shown.
+    pub async fn update_kanban(&mut self) -> anyhow::Result<()> {
+        let Some(project) = self.projects.selected().cloned() else {
+            return Ok(());
+        };
+        if self.kanban.project_key.as_ref() != Some(&project.key) {
+            let mut problems = vec![];
+            let board = match self.boards.project_key.as_ref() == Some(&project.key) {
+                true => self.boards.selected_board().cloned(),
+                false => match self
+                    .jira
+                    .agile_api
+                    .get_boards(&self.jira.client, &project.key)
+                    .await
+                {
+                    Ok(boards) => {
+                        if boards.len() > 1 {
+                            debug!(
+                                "{} has {} boards, the kanban uses the first one",
+                                project.key,
+                                boards.len()
+                            );
+                        }
+                        boards.first().cloned()
+                    }
+                    Err(e) => {
+                        problems.push(format!("could not load the boards: {e}"));
+                        None
+                    }
+                },
+            };
+            self.kanban.board_columns = None;
+            self.kanban.board_name = None;
+            if let Some(board) = board {
+                match self
+                    .jira
+                    .agile_api
+                    .get_board_columns(&self.jira.client, &board)
+                    .await
+                {
+                    Ok(columns) => {
+                        self.kanban.board_columns = Some(columns);
+                        self.kanban.board_name = Some(board.name);
+                    }
+                    Err(e) => problems.push(format!(
+                        "could not load the columns of board {}: {e}",
+                        board.name
+                    )),
+                }
+            }
+            self.kanban.project_key = Some(project.key.clone());
+            if !problems.is_empty() {
+                self.error.set(format!(
+                    "Kanban of {} follows the ticket statuses, {}",
+                    project.key,
+                    problems.join(", ")
+                ))?;
+            }
+        }
+
+        let mut tickets = self.jira.get_cached_project_tickets(&project.key).await?;
+        if tickets.is_empty() {
+            debug!(
+                "No cached tickets for {}, the kanban shows the ticket list",
+                project.key
+            );
+            tickets = self.tickets.tickets.clone();
+        }
+        self.kanban.update(&tickets);
+        Ok(())
+    }
+
+    // kanban_transition moves the selected kanban ticket into the neighbouring
+    // column through the first transition that ends in one of its statuses
+    pub async fn kanban_transition(&mut self, right: bool) -> anyhow::Result<()> {
+        let Some(ticket) = self.kanban.selected().cloned() else {
+            return Ok(());
+        };
+        let Some(column) = self.kanban.neighbour_column(right).cloned() else {
+            return Ok(());
+        };
+        let transitions = ticket.get_transitions(&self.jira.client).await?;
+        let transition = transitions.transitions.into_iter().find(|t| match &t.to {
+            Some(status) => column.holds(status.id.as_ref(), &status.name),
+            None => false,
+        });
+        let Some(transition) = transition else {
+            self.error.set(format!(
+                "No transition moves {} to {}",
+                ticket.key, column.name
+            ))?;
+            return Ok(());
+        };
+        let data = PostTicketTransition {
+            transition: TicketTransition {
+                fields: None,
+                has_screen: None,
+                id: transition.id,
+                name: transition.name,
+                to: None,
+            },
+        };
+        ticket.transition_ticket(data, &self.jira.client).await?;
+        self.jira.jira_ticket_api(&ticket.key).await?;
+        self.update_kanban().await?;
+        self.kanban.select_ticket(&ticket.key);
+        Ok(())
+    }
+
+    // rank_ticket reorders the selected ticket of a sprint or backlog list, either
+    // one step up/down or to the very top/bottom of the list
+    pub async fn rank_ticket(&mut self, up: bool, to_edge: bool) -> anyhow::Result<()> {
+        if !self.ticket_source.is_ranked() {
+            self.error
+                .set("Ranking is only available in sprint and backlog lists".to_string())?;
+            return Ok(());
+        }
+        let Some(index) = self.tickets.selected_index() else {
+            return Ok(());
+        };
+        let ticket_key = self.tickets.tickets[index].key.clone();
+        // The rank of the other ticket in the whole list, a neighbour off the
+        // loaded page is fetched from the next or previous page
+        let rank = self.jira.tickets_start_at + index as u32;
+        let other_rank = match (to_edge, up) {
+            (true, true) => Some(0),
+            (true, false) => self.jira.tickets_api.total.checked_sub(1),
+            (false, true) => rank.checked_sub(1),
+            (false, false) => Some(rank + 1).filter(|r| *r < self.jira.tickets_api.total),
+        };
+        let Some(other_rank) = other_rank else {
+            return Ok(());
+        };
+        let other_key = match other_rank.checked_sub(self.jira.tickets_start_at) {
+            Some(i) if (i as usize) < self.tickets.tickets.len() => {
+                Some(self.tickets.tickets[i as usize].key.clone())
+            }
+            _ => {
+                self.jira
+                    .get_agile_ticket_at(&self.ticket_source, other_rank)
+                    .await?
+            }
+        };
+        let Some(other_key) = other_key.filter(|key| *key != ticket_key) else {
+            return Ok(());
+        };
+        let rank = match up {
+            true => Rank::Before(other_key),
+            false => Rank::After(other_key),
+        };
+        self.jira
+            .agile_api
+            .rank_tickets(&self.jira.client, std::slice::from_ref(&ticket_key), &rank)
+            .await?;
+        self.update_all_tickets().await?;
+        self.tickets.select_ticket(&ticket_key)
+    }
+
+    pub async fn update_single_ticket(&mut self, ticket_key: &str) -> anyhow::Result<()> {
+        let ticket = self.jira.search_cache_ticket(ticket_key).await?;
+        if self.tickets.rank_offset.is_some() && self.tickets.replace_ticket(ticket.clone()) {
+            return self.tickets.select_ticket(ticket_key);
+        }
+        self.tickets.remove_ticket(ticket_key)?;
+        self.tickets.update(vec![ticket], false).await?;
+        self.tickets.select_ticket(ticket_key)
+    }
+
+    pub async fn update_search_tickets(&mut self) -> anyhow::Result<()> {
+        self.search_tickets.update(self.tickets.tickets.clone());
+        Ok(())
+    }
+
+    pub async fn update_comments_view(&mut self) -> anyhow::Result<()> {
+        let comments = match self.tickets.selected() {
+            None => return Ok(()),
+            Some(t) => t.get_comments(&self.jira.db, &self.jira.client).await?,
+        };
+        self.comments_list.comments = Some(comments);
+        Ok(())
+    }
+
+    pub async fn update_history_view(&mut self) -> anyhow::Result<()> {
+        let ticket = match self.tickets.selected() {
+            None => return Ok(()),
+            Some(t) => t.clone(),
+        };
+        let changelog = ticket
+            .get_changelog(&self.jira.db, &self.jira.client)
+            .await?;
+        self.history.update(&ticket.key, &changelog);
+        Ok(())
+    }
+
+    pub async fn add_comment(&mut self, comments: &[String]) -> anyhow::Result<()> {
+        let ticket = match self.tickets.selected() {
+            None => return Ok(()),
+            Some(t) => t,
+        };
+        let comment = comments.join(" \n ");
+        ticket
+            .add_comment(&self.jira.db, &comment, &self.jira.client)
+            .await?;
+        Ok(())
+    }
+
+    pub async fn update_ticket_transitions(&mut self) -> anyhow::Result<()> {
+        let ticket = match self.tickets.selected() {
+            None => return Ok(()),
+            Some(t) => t,
+        };
+
+        let transitions = ticket.get_transitions(&self.jira.client).await?;
+        self.ticket_transition.update(&transitions);
+        Ok(())
+    }
+
+    pub async fn move_ticket(&mut self) -> anyhow::Result<()> {
+        let ticket = match self.tickets.selected() {
+            Some(t) => t,
+            None => return Ok(()),
+        };
+        let transition = self.ticket_transition.selected_transition().unwrap();
+        let data = PostTicketTransition {
+            transition: TicketTransition {
+                fields: None,
+                has_screen: None,
+                id: transition.id.clone(),
+                name: transition.name.clone(),
+                to: None,
+            },
+        };
+        ticket.transition_ticket(data, &self.jira.client).await?;
+        self.jira.jira_ticket_api(&ticket.key).await?;
+        Ok(())
+    }
+
+    // load_create_fields pulls the create screen of the selected ticket type
+    async fn load_create_fields(&mut self) -> anyhow::Result<()> {
+        self.create_ticket.load_fields = false;
+        let (Some(project), Some(ticket_type)) = (
+            self.projects.selected(),
+            self.create_ticket.selected_ticket_type(),
+        ) else {
+            return Ok(());
+        };
+        let fields = self
+            .jira
+            .createmeta_api
+            .get_fields(&self.jira.client, &project.project_id, &ticket_type.id)
+            .await?;
+        self.create_ticket.set_fields(fields);
+        Ok(())
+    }
+
+    // editor_finished sends what was written in the external editor. On errors
+    // the text stays in the comment or the create form.
+    pub async fn editor_finished(
+        &mut self,
+        target: EditorTarget,
+        edited: anyhow::Result<String>,
+    ) -> anyhow::Result<()> {
+        let text = edited?;
+        match target {
+            EditorTarget::Comment => {
+                let text = text.trim();
+                if text.is_empty() {
+                    return Ok(());
+                }
+                self.comment_add.set_text(text);
+                self.add_comment(&[text.to_string()]).await?;
+                self.comment_add.clear();
+            }
+            EditorTarget::Ticket => {
+                if let Err(e) = self.create_ticket.apply_document(&text) {
+                    anyhow::bail!("Could not read the ticket: {e}");
+                }
+                if self.create_ticket.load_fields {
+                    self.load_create_fields().await?;
+                }
+                self.create_ticket().await?;
+            }
+        }
+        Ok(())
+    }
+
+    // open_create_ticket shows the create form for the selected project, filled
+    // from the given ticket when cloning it
+    async fn open_create_ticket(&mut self, clone_of: Option<TicketData>) -> anyhow::Result<()> {
+        let Some(project) = self.ticket_project()? else {
+            return Ok(());
+        };
+        let ticket_type_response = self
+            .jira
+            .tickets_api
+            .get_ticket_types(&self.jira.client, &project.project_id)
+            .await?;
+        let user = self
+            .jira
+            .client
+            .user
+            .as_ref()
+            .map(|user| user.display_name.clone())
+            .unwrap_or_default();
+        self.create_ticket.template_vars = TemplateVars::new(&user, &project.key);
+        self.create_ticket.set_ticket_types(ticket_type_response);
+        if let Some(ticket) = clone_of {
+            self.create_ticket.start_clone(&ticket);
+        }
+        self.load_create_fields().await?;
+        self.focus = Focus::CreateTicket;
+        Ok(())
+    }
+
+    // finish_clone links the clone to the original and copies the sub-tasks
+    // when asked to, failures are reported but keep the created clone
+    async fn finish_clone(
+        &mut self,
+        ticket_key: &str,
+        project_id: &str,
+        clone: CloneSource,
+    ) -> anyhow::Result<()> {
+        let mut problems = vec![];
+        if clone.link {
+            if let Err(e) =
+                clone::link_clone(&self.jira.client, ticket_key, &clone.ticket_key).await
+            {
+                problems.push(format!("link to {}: {e}", clone.ticket_key));
+            }
+        }
+        if clone.copy_subtasks {
+            let results = clone::copy_subtasks(
+                &self.jira.client,
+                project_id,
+                ticket_key,
+                &clone.subtask_keys,
+            )
+            .await;
+            problems.extend(results.into_iter().filter_map(|result| {
+                let e = result.outcome.err()?;
+                Some(format!("sub-task {}: {e}", result.ticket_key))
+            }));
+        }
+        if !problems.is_empty() {
+            self.error.set(format!(
+                "Created {ticket_key} but could not finish the clone: {}",
+                problems.join(", ")
+            ))?;
+        }
+        Ok(())
+    }
+
+    // create_ticket sends the create form, problems with the fields are shown
+    // next to them and the form stays open
+    async fn create_ticket(&mut self) -> anyhow::Result<()> {
+        let (Some(project), Some(ticket_type)) = (
+            self.projects.selected(),
+            self.create_ticket.selected_ticket_type(),
+        ) else {
+            return Ok(());
+        };
+        let outcome = self
+            .jira
+            .createmeta_api
+            .create_ticket(
+                &self.jira.client,
+                &project.project_id,
+                &ticket_type.id,
+                &self.create_ticket.fields,
+                &self.create_ticket.contents.values,
+            )
+            .await?;
+        match outcome {
+            CreateOutcome::Created(ticket_key) => {
+                debug!("Created {ticket_key}");
+                let project_id = project.project_id.clone();
+                let clone = self.create_ticket.clone.take();
+                self.create_ticket.clear();
+                if let Some(clone) = clone {
+                    self.finish_clone(&ticket_key, &project_id, clone).await?;
+                }
+                self.focus = Focus::Tickets;
+                self.tickets.tickets.clear();
+                self.tickets.ticket_description = None;
+                self.jira.clear_tickets_table().await?;
+                self.update_all_tickets().await?;
+                self.tickets.select_ticket(&ticket_key)?;
+            }
+            CreateOutcome::Rejected {
+                field_errors,
+                mut messages,
+            } => {
+                let unknown: Vec<String> = field_errors
+                    .iter()
+                    .filter(|(field_id, _)| {
+                        !self
+                            .create_ticket
+                            .fields
+                            .iter()
+                            .any(|field| field.field_id == **field_id)
+                    })
+                    .map(|(field_id, error)| format!("{field_id}: {error}"))
+                    .collect();
+                messages.extend(unknown);
+                if !field_errors.is_empty() {
+                    messages.insert(0, String::from("Fix the highlighted fields"));
+                }
+                self.create_ticket.errors = field_errors;
+                self.error.set(messages.join("\n"))?;
+            }
+        }
+        Ok(())
+    }
+
+    pub async fn widget_event(&mut self, key: Key) -> anyhow::Result<EventState> {
+        if self.error.event(key)?.is_consumed() {
+            return Ok(EventState::Consumed);
+        }
+
+        match self.focus {
+            Focus::Boards => {
+                if self.help.event(key)?.is_consumed() {
+                    return Ok(EventState::Consumed);
+                }
+                if self.boards.event(key)?.is_consumed() {
+                    return Ok(EventState::Consumed);
+                }
+            }
+            Focus::Bulk => {
+                if self.help.event(key)?.is_consumed() {
+                    return Ok(EventState::Consumed);
+                }
+                if self.bulk.event(key)?.is_consumed() {
+                    if let Some(request) = self.bulk.request.take() {
+                        self.bulk_request(request).await?;
+                    }
+                    return Ok(EventState::Consumed);
+                }
+            }
+            Focus::CommentsList => {
+                if self.help.event(key)?.is_consumed() {
+                    self.update_comments_list_commands();
+                    return Ok(EventState::Consumed);
+                }
+                if self.comments_list.event(key)?.is_consumed() {
+                    return Ok(EventState::Consumed);
+                }
+            }
+            Focus::CommentsAdd => {
+                if self.comment_add.event(key)?.is_consumed() {
+                    if self.comment_add.open_editor {
+                        self.comment_add.open_editor = false;
+                        self.editor_request = Some(EditorRequest {
+                            target: EditorTarget::Comment,
+                            text: self.comment_add.text(),
+                        });
+                    }
+                    if self.comment_add.push_comment && !self.comment_add.is_empty() {
+                        let comment = self.comment_add.text();
+                        self.add_comment(&[comment]).await?;
+                        self.comment_add.clear();
+                        self.comment_add.push_comment = false;
+                    }
+                    return Ok(EventState::Consumed);
+                }
+            }
+            Focus::Components => {
+                if self.components.event(key)?.is_consumed() {
+                    return Ok(EventState::Consumed);
+                }
+            }
+            Focus::CreateTicket => {
+                if self.create_ticket.event(key)?.is_consumed() {
+                    if self.create_ticket.load_fields {
+                        self.load_create_fields().await?;
+                    }
+                    if self.create_ticket.push_content {
+                        self.create_ticket.push_content = false;
+                        self.create_ticket().await?;
+                    }
+                    if self.create_ticket.open_editor {
+                        self.create_ticket.open_editor = false;
+                        match self.create_ticket.document() {
+                            Ok(text) => {
+                                self.editor_request = Some(EditorRequest {
+                                    target: EditorTarget::Ticket,
+                                    text,
+                                })
+                            }
+                            Err(e) => self.error.set(format!("Could not write the ticket: {e}"))?,
+                        }
+                    }
+                    return Ok(EventState::Consumed);
+                }
+            }
+            Focus::Epics => {
+                if self.help.event(key)?.is_consumed() {
+                    return Ok(EventState::Consumed);
+                }
+                if self.epics.event(key)?.is_consumed() {
+                    return Ok(EventState::Consumed);
+                }
+            }
+            Focus::Filters => {
+                if self.help.event(key)?.is_consumed() {
+                    return Ok(EventState::Consumed);
+                }
+                if self.filters.event(key)?.is_consumed() {
+                    if self.filters.search_requested {
+                        self.filters.search_requested = false;
+                        self.search_filters().await?;
+                    }
+                    return Ok(EventState::Consumed);
+                }
+            }
+            Focus::History => {
+                if self.help.event(key)?.is_consumed() {
+                    return Ok(EventState::Consumed);
+                }
+                if self.history.event(key)?.is_consumed() {
+                    return Ok(EventState::Consumed);
+                }
+            }
+            Focus::MyWork => {
+                if self.help.event(key)?.is_consumed() {
+                    return Ok(EventState::Consumed);
+                }
+                if self.my_work.event(key)?.is_consumed() {
+                    return Ok(EventState::Consumed);
+                }
+            }
+            Focus::Notifications => {
+                if self.help.event(key)?.is_consumed() {
+                    return Ok(EventState::Consumed);
+                }
+                if self.notifications.event(key)?.is_consumed() {
+                    return Ok(EventState::Consumed);
+                }
+            }
+            Focus::Jql => {
+                if self.jql.event(key)?.is_consumed() {
+                    if let Some((name, jql)) = self.jql.save_query.take() {
+                        self.save_jql(&name, &jql).await?;
+                    }
+                    if self.jql.run_query {
+                        self.jql.run_query = false;
+                        let jql = self.jql.input().trim().to_string();
+                        self.run_jql(&jql).await?;
+                    }
+                    return Ok(EventState::Consumed);
+                }
+            }
+            Focus::Kanban => {
+                if self.help.event(key)?.is_consumed() {
+                    return Ok(EventState::Consumed);
+                }
+                if self.kanban.event(key)?.is_consumed() {
+                    return Ok(EventState::Consumed);
+                }
+            }
+            Focus::Labels => {
+                if self.labels.event(key)?.is_consumed() {
                     return Ok(EventState::Consumed);
                 }
             }
@@ -635,176 +2624,498 @@ impl App {
                 if self.help.event(key)?.is_consumed() {
                     return Ok(EventState::Consumed);
                 }
-                if self.projects.event(key)?.is_consumed() {
-                    return Ok(EventState::Consumed);
+                if self.projects.event(key)?.is_consumed() {
+                    return Ok(EventState::Consumed);
+                }
+            }
+            Focus::SearchProjects => {
+                if self.search_projects.event(key)?.is_consumed() {
+                    return Ok(EventState::Consumed);
+                }
+            }
+            Focus::SearchTickets => {
+                if self.search_tickets.event(key)?.is_consumed() {
+                    return Ok(EventState::Consumed);
+                }
+            }
+            Focus::TicketFinder => {
+                if self.ticket_finder.event(key).is_consumed() {
+                    match self.ticket_finder.request.take() {
+                        Some(FinderRequest::Close) => {
+                            self.focus = match self.tickets.tickets.is_empty() {
+                                true => Focus::Projects,
+                                false => Focus::Tickets,
+                            };
+                        }
+                        Some(FinderRequest::Jump(ticket)) => self.jump_to_ticket(*ticket).await?,
+                        None => {}
+                    }
+                    return Ok(EventState::Consumed);
+                }
+            }
+            Focus::TicketRelation => {
+                if self.relation.event(key)?.is_consumed() {
+                    return Ok(EventState::Consumed);
+                }
+            }
+            Focus::Tickets => {
+                if key == self.config.key_config.reset {
+                    self.tickets.ticket_description = None;
+                    self.tickets.tickets.clear();
+                    self.jira.clear_tickets_table().await?;
+                    self.update_all_tickets().await?;
+                    return Ok(EventState::Consumed);
+                }
+
+                if self.help.event(key)?.is_consumed() {
+                    return Ok(EventState::Consumed);
+                }
+                if self.tickets.event(key)?.is_consumed() {
+                    self.mark_selected_ticket_read().await?;
+                    return Ok(EventState::Consumed);
+                }
+            }
+            Focus::TicketParent => {
+                if self.parent.event(key)?.is_consumed() {
+                    return Ok(EventState::Consumed);
+                }
+                if self.help.event(key)?.is_consumed() {
+                    return Ok(EventState::Consumed);
+                }
+            }
+            Focus::TicketTransition => {
+                if self.ticket_transition.event(key)?.is_consumed() {
+                    if self.ticket_transition.push_transition {
+                        debug!(
+                            "Transitioning {:?} to {:?}",
+                            self.tickets.selected(),
+                            self.ticket_transition.selected_transition()
+                        );
+                        if !self.ticket_transition.comment_float_screen.is_empty() {
+                            debug!(
+                                "Reason {:?}",
+                                self.ticket_transition.selected_transition_reason()
+                            );
+                            self.add_comment(
+                                vec![self.ticket_transition.comment_float_screen.text()].as_ref(),
+                            )
+                            .await?;
+                            self.ticket_transition.comment_float_screen.clear();
+                        }
+                        self.move_ticket().await?;
+                        if let Some(t) = self.tickets.selected() {
+                            let ticket = t.clone();
+                            self.update_single_ticket(&ticket.key).await?;
+                        }
+                        self.ticket_transition.push_transition = false;
+                        self.focus = Focus::Tickets;
+                    }
+                    return Ok(EventState::Consumed);
+                }
+            }
+        }
+
+        Ok(EventState::NotConsumed)
+    }
+
+    async fn project_move_focus(&mut self, key: Key) -> anyhow::Result<EventState> {
+        let Some(&action) = self.projects_key_mappings.get(&key) else {
+            return Ok(EventState::NotConsumed);
+        };
+        log::debug!("got projects focus event: {key:?}");
+        self.projects_action(action).await
+    }
+
+    async fn projects_action(&mut self, action: ProjectsAction) -> anyhow::Result<EventState> {
+        use ProjectsAction::*;
+        match action {
+            OpenHelp => {
+                let mut commands = Vec::with_capacity(
+                    self.projects_key_mappings.len() + self.projects.key_mappings.len(),
+                );
+                for (&key, action) in &self.projects_key_mappings {
+                    let command_text = action.to_command_text(key);
+                    commands.push(CommandInfo::new(command_text));
+                }
+                for (&key, action) in &self.projects.key_mappings {
+                    let command_text = action.to_command_text(key);
+                    commands.push(CommandInfo::new(command_text));
+                }
+
+                self.help.set_cmds(commands);
+                self.help.show()?;
+            }
+            OpenBoards => {
+                self.open_boards(vec![]).await?;
+            }
+            OpenEpics => {
+                self.open_epics().await?;
+            }
+            OpenFilters => {
+                self.open_filters().await?;
+            }
+            OpenJql => {
+                self.open_jql().await?;
+            }
+            OpenMyWork => {
+                self.open_my_work().await?;
+            }
+            OpenNotifications => {
+                self.focus = Focus::Notifications;
+            }
+            SelectProject => {
+                self.use_project_tickets();
+                self.update_all_tickets().await?;
+                self.focus = Focus::Tickets;
+            }
+            SearchProjects => {
+                self.focus = Focus::SearchProjects;
+                self.search_projects.input_mode = InputMode::Editing;
+            }
+            NextPage => {
+                self.next_project_page().await?;
+                self.update_projects().await?;
+                self.focus = Focus::Projects;
+            }
+            PreviousPage => {
+                self.previous_project_page().await?;
+                self.update_projects().await?;
+                self.focus = Focus::Projects;
+            }
+            Reset => {
+                self.projects.projects.clear();
+                self.tickets.tickets.clear();
+                self.jira.clear_projects_table().await?;
+                self.jira.clear_tickets_table().await?;
+                let projects = &self.jira.get_jira_projects().await?;
+                self.projects = ProjectsWidget::new(projects, self.config.key_config.clone());
+            }
+        }
+        Ok(EventState::Consumed)
+    }
+    async fn boards_move_focus(&mut self, key: Key) -> anyhow::Result<EventState> {
+        let Some(action) = self.boards_key_mappings.get(&key) else {
+            return Ok(EventState::NotConsumed);
+        };
+        log::debug!("got boards focus event: {key:?}");
+        use BoardsAction::*;
+        match *action {
+            FocusTickets => {
+                self.boards.move_tickets.clear();
+                self.focus = match self.tickets.tickets.is_empty() {
+                    true => Focus::Projects,
+                    false => Focus::Tickets,
+                };
+            }
+            OpenHelp => {
+                let mut commands = Vec::with_capacity(
+                    self.boards_key_mappings.len() + self.boards.key_mappings.len(),
+                );
+                for (&key, action) in &self.boards_key_mappings {
+                    let command_text = action.to_command_text(key);
+                    commands.push(CommandInfo::new(command_text));
+                }
+                for (&key, action) in &self.boards.key_mappings {
+                    let command_text = action.to_command_text(key);
+                    commands.push(CommandInfo::new(command_text));
+                }
+
+                self.help.set_cmds(commands);
+                self.help.show()?;
+            }
+            Select => match self.boards.focus {
+                BoardsFocus::Boards => self.update_sprints().await?,
+                BoardsFocus::Sprints => self.select_sprint().await?,
+            },
+        }
+        Ok(EventState::Consumed)
+    }
+
+    async fn epics_move_focus(&mut self, key: Key) -> anyhow::Result<EventState> {
+        let Some(action) = self.epics_key_mappings.get(&key) else {
+            return Ok(EventState::NotConsumed);
+        };
+        log::debug!("got epics focus event: {key:?}");
+        use EpicsAction::*;
+        match *action {
+            FocusTickets => {
+                self.focus = match self.tickets.tickets.is_empty() {
+                    true => Focus::Projects,
+                    false => Focus::Tickets,
+                };
+            }
+            OpenHelp => {
+                let mut commands = Vec::with_capacity(
+                    self.epics_key_mappings.len() + self.epics.key_mappings.len(),
+                );
+                for (&key, action) in &self.epics_key_mappings {
+                    let command_text = action.to_command_text(key);
+                    commands.push(CommandInfo::new(command_text));
+                }
+                for (&key, action) in &self.epics.key_mappings {
+                    let command_text = action.to_command_text(key);
+                    commands.push(CommandInfo::new(command_text));
+                }
+
+                self.help.set_cmds(commands);
+                self.help.show()?;
+            }
+            Reset => self.update_epics().await?,
+            Select => self.select_epic().await?,
+        }
+        Ok(EventState::Consumed)
+    }
+
+    async fn bulk_move_focus(&mut self, key: Key) -> anyhow::Result<EventState> {
+        let Some(action) = self.bulk_key_mappings.get(&key) else {
+            return Ok(EventState::NotConsumed);
+        };
+        log::debug!("got bulk focus event: {key:?}");
+        use BulkAction::*;
+        match *action {
+            FocusTickets => self.focus = Focus::Tickets,
+            OpenHelp => {
+                let mut commands =
+                    Vec::with_capacity(self.bulk_key_mappings.len() + self.bulk.key_mappings.len());
+                for (&key, action) in &self.bulk_key_mappings {
+                    let command_text = action.to_command_text(key);
+                    commands.push(CommandInfo::new(command_text));
+                }
+                for (&key, action) in &self.bulk.key_mappings {
+                    let command_text = action.to_command_text(key);
+                    commands.push(CommandInfo::new(command_text));
+                }
+
+                self.help.set_cmds(commands);
+                self.help.show()?;
+            }
+        }
+        Ok(EventState::Consumed)
+    }
+
+    async fn filters_move_focus(&mut self, key: Key) -> anyhow::Result<EventState> {
+        let Some(action) = self.filters_key_mappings.get(&key) else {
+            return Ok(EventState::NotConsumed);
+        };
+        log::debug!("got filters focus event: {key:?}");
+        use FiltersAction::*;
+        match *action {
+            FocusTickets => {
+                self.focus = match self.tickets.tickets.is_empty() {
+                    true => Focus::Projects,
+                    false => Focus::Tickets,
+                };
+            }
+            OpenHelp => {
+                let mut commands = Vec::with_capacity(
+                    self.filters_key_mappings.len() + self.filters.key_mappings.len(),
+                );
+                for (&key, action) in &self.filters_key_mappings {
+                    let command_text = action.to_command_text(key);
+                    commands.push(CommandInfo::new(command_text));
+                }
+                for (&key, action) in &self.filters.key_mappings {
+                    let command_text = action.to_command_text(key);
+                    commands.push(CommandInfo::new(command_text));
                 }
+
+                self.help.set_cmds(commands);
+                self.help.show()?;
             }
-            Focus::SearchProjects => {
-                if self.search_projects.event(key)?.is_consumed() {
-                    return Ok(EventState::Consumed);
-                }
+            Select => self.select_filter().await?,
+        }
+        Ok(EventState::Consumed)
+    }
+
+    async fn my_work_move_focus(&mut self, key: Key) -> anyhow::Result<EventState> {
+        let Some(action) = self.my_work_key_mappings.get(&key) else {
+            return Ok(EventState::NotConsumed);
+        };
+        log::debug!("got my work focus event: {key:?}");
+        use MyWorkAction::*;
+        match *action {
+            FocusTickets => {
+                self.focus = match self.tickets.tickets.is_empty() {
+                    true => Focus::Projects,
+                    false => Focus::Tickets,
+                };
             }
-            Focus::SearchTickets => {
-                if self.search_tickets.event(key)?.is_consumed() {
-                    return Ok(EventState::Consumed);
+            OpenHelp => {
+                let mut commands = Vec::with_capacity(
+                    self.my_work_key_mappings.len() + self.my_work.key_mappings.len(),
+                );
+                for (&key, action) in &self.my_work_key_mappings {
+                    let command_text = action.to_command_text(key);
+                    commands.push(CommandInfo::new(command_text));
                 }
-            }
-            Focus::TicketRelation => {
-                if self.relation.event(key)?.is_consumed() {
-                    return Ok(EventState::Consumed);
+                for (&key, action) in &self.my_work.key_mappings {
+                    let command_text = action.to_command_text(key);
+                    commands.push(CommandInfo::new(command_text));
                 }
+
+                self.help.set_cmds(commands);
+                self.help.show()?;
             }
-            Focus::Tickets => {
-                if key == self.config.key_config.reset {
-                    self.tickets.ticket_description = None;
-                    self.tickets.tickets.clear();
-                    self.jira.clear_tickets_table().await?;
-                    self.update_all_tickets().await?;
-                    return Ok(EventState::Consumed);
-                }
+            Reset => self.update_my_work().await?,
+            Select => self.select_my_work().await?,
+        }
+        Ok(EventState::Consumed)
+    }
 
-                if self.help.event(key)?.is_consumed() {
-                    return Ok(EventState::Consumed);
-                }
-                if self.tickets.event(key)?.is_consumed() {
-                    return Ok(EventState::Consumed);
-                }
+    async fn notifications_move_focus(&mut self, key: Key) -> anyhow::Result<EventState> {
+        let Some(action) = self.notifications_key_mappings.get(&key) else {
+            return Ok(EventState::NotConsumed);
+        };
+        log::debug!("got notifications focus event: {key:?}");
+        use NotificationsAction::*;
+        match *action {
+            FocusTickets => {
+                self.focus = match self.tickets.tickets.is_empty() {
+                    true => Focus::Projects,
+                    false => Focus::Tickets,
+                };
             }
-            Focus::TicketParent => {
-                if self.parent.event(key)?.is_consumed() {
-                    return Ok(EventState::Consumed);
-                }
-                if self.help.event(key)?.is_consumed() {
-                    return Ok(EventState::Consumed);
+            MarkAllRead => self.mark_notifications_read(None).await?,
+            OpenHelp => {
+                let mut commands = Vec::with_capacity(
+                    self.notifications_key_mappings.len() + self.notifications.key_mappings.len(),
+                );
+                for (&key, action) in &self.notifications_key_mappings {
+                    let command_text = action.to_command_text(key);
+                    commands.push(CommandInfo::new(command_text));
                 }
-            }
-            Focus::TicketTransition => {
-                if self.ticket_transition.event(key)?.is_consumed() {
-                    if self.ticket_transition.push_transition {
-                        debug!(
-                            "Transitioning {:?} to {:?}",
-                            self.tickets.selected(),
-                            self.ticket_transition.selected_transition()
-                        );
-                        if !self.ticket_transition.comment_float_screen.is_empty() {
-                            debug!(
-                                "Reason {:?}",
-                                self.ticket_transition.selected_transition_reason()
-                            );
-                            self.add_comment(
-                                vec![self.ticket_transition.comment_float_screen.clone()].as_ref(),
-                            )
-                            .await?;
-                            self.ticket_transition.comment_float_screen.clear();
-                        }
-                        self.move_ticket().await?;
-                        if let Some(t) = self.tickets.selected() {
-                            let ticket = t.clone();
-                            self.update_single_ticket(&ticket.key).await?;
-                        }
-                        self.ticket_transition.push_transition = false;
-                        self.focus = Focus::Tickets;
-                    }
-                    return Ok(EventState::Consumed);
+                for (&key, action) in &self.notifications.key_mappings {
+                    let command_text = action.to_command_text(key);
+                    commands.push(CommandInfo::new(command_text));
                 }
+
+                self.help.set_cmds(commands);
+                self.help.show()?;
             }
+            Select => self.select_notification().await?,
         }
-
-        Ok(EventState::NotConsumed)
+        Ok(EventState::Consumed)
     }
 
-    async fn project_move_focus(&mut self, key: Key) -> anyhow::Result<EventState> {
-        if let Some(action) = self.projects_key_mappings.get(&key) {
-            log::debug!("got projects focus event: {key:?}");
-            use ProjectsAction::*;
-            match *action {
-                OpenHelp => {
-                    let mut commands = Vec::with_capacity(
-                        self.projects_key_mappings.len() + self.projects.key_mappings.len(),
-                    );
-                    for (&key, action) in &self.projects_key_mappings {
-                        let command_text = action.to_command_text(key);
-                        commands.push(CommandInfo::new(command_text));
-                    }
-                    for (&key, action) in &self.projects.key_mappings {
-                        let command_text = action.to_command_text(key);
-                        commands.push(CommandInfo::new(command_text));
-                    }
+    async fn comments_move_focus(&mut self, key: Key) -> anyhow::Result<EventState> {
+        let Some(&action) = self.comments_key_mappings.get(&key) else {
+            return Ok(EventState::Consumed);
+        };
+        log::debug!("got comments focus event: {key:?}");
+        self.comments_action(action).await
+    }
 
-                    self.help.set_cmds(commands);
-                    self.help.show()?;
-                }
-                SelectProject => {
-                    self.update_all_tickets().await?;
-                    self.focus = Focus::Tickets;
-                }
-                SearchProjects => {
-                    self.focus = Focus::SearchProjects;
-                    self.search_projects.input_mode = InputMode::Editing;
+    async fn comments_action(&mut self, action: CommentsAction) -> anyhow::Result<EventState> {
+        use CommentsAction::*;
+        match action {
+            OpenHelp => {
+                let mut commands = Vec::with_capacity(
+                    self.comments_key_mappings.len() + self.comments_list.key_mappings.len(),
+                );
+                for (&key, action) in &self.comments_key_mappings {
+                    let command_text = action.to_command_text(key);
+                    commands.push(CommandInfo::new(command_text));
                 }
-                NextPage => {
-                    self.next_project_page().await?;
-                    self.update_projects().await?;
-                    self.focus = Focus::Projects;
+                for (&key, action) in &self.comments_list.key_mappings {
+                    let command_text = action.to_command_text(key);
+                    commands.push(CommandInfo::new(command_text));
                 }
-                PreviousPage => {
-                    self.previous_project_page().await?;
-                    self.update_projects().await?;
-                    self.focus = Focus::Projects;
+
+                self.help.set_cmds(commands);
+                self.help.show()?;
+            }
+            AddComment => {
+                self.focus = Focus::CommentsAdd;
+                return Ok(EventState::Consumed);
+            }
+            FocusTickets => {
+                self.focus = Focus::Tickets;
+                return Ok(EventState::Consumed);
+            }
+        }
+        Ok(EventState::Consumed)
+    }
+
+    async fn history_move_focus(&mut self, key: Key) -> anyhow::Result<EventState> {
+        let Some(action) = self.history_key_mappings.get(&key) else {
+            return Ok(EventState::NotConsumed);
+        };
+        log::debug!("got history focus event: {key:?}");
+        use HistoryAction::*;
+        match *action {
+            OpenHelp => {
+                let mut commands = Vec::with_capacity(
+                    self.history_key_mappings.len() + self.history.key_mappings.len(),
+                );
+                for (&key, action) in &self.history_key_mappings {
+                    let command_text = action.to_command_text(key);
+                    commands.push(CommandInfo::new(command_text));
                 }
-                Reset => {
-                    self.projects.projects.clear();
-                    self.tickets.tickets.clear();
-                    self.jira.clear_projects_table().await?;
-                    self.jira.clear_tickets_table().await?;
-                    let projects = &self.jira.get_jira_projects().await?;
-                    self.projects = ProjectsWidget::new(projects, self.config.key_config.clone());
+                for (&key, action) in &self.history.key_mappings {
+                    let command_text = action.to_command_text(key);
+                    commands.push(CommandInfo::new(command_text));
                 }
+
+                self.help.set_cmds(commands);
+                self.help.show()?;
+                Ok(EventState::Consumed)
+            }
+            FocusTickets => {
+                self.focus = Focus::Tickets;
+                Ok(EventState::Consumed)
             }
-            return Ok(EventState::Consumed);
         }
-        Ok(EventState::NotConsumed)
     }
-    async fn comments_move_focus(&mut self, key: Key) -> anyhow::Result<EventState> {
-        if let Some(action) = self.comments_key_mappings.get(&key) {
-            log::debug!("got comments focus event: {key:?}");
-            use CommentsAction::*;
-            match *action {
-                OpenHelp => {
-                    let mut commands = Vec::with_capacity(
-                        self.comments_key_mappings.len() + self.comments_list.key_mappings.len(),
-                    );
-                    for (&key, action) in &self.comments_key_mappings {
-                        let command_text = action.to_command_text(key);
-                        commands.push(CommandInfo::new(command_text));
-                    }
-                    for (&key, action) in &self.comments_list.key_mappings {
-                        let command_text = action.to_command_text(key);
-                        commands.push(CommandInfo::new(command_text));
-                    }
 
-                    self.help.set_cmds(commands);
-                    self.help.show()?;
+    async fn kanban_move_focus(&mut self, key: Key) -> anyhow::Result<EventState> {
+        let Some(action) = self.kanban_key_mappings.get(&key) else {
+            return Ok(EventState::NotConsumed);
+        };
+        log::debug!("got kanban focus event: {key:?}");
+        use KanbanAction::*;
+        match *action {
+            FocusTickets => {
+                self.focus = Focus::Tickets;
+            }
+            OpenHelp => {
+                let mut commands = Vec::with_capacity(
+                    self.kanban_key_mappings.len() + self.kanban.key_mappings.len(),
+                );
+                for (&key, action) in &self.kanban_key_mappings {
+                    let command_text = action.to_command_text(key);
+                    commands.push(CommandInfo::new(command_text));
                 }
-                AddComment => {
-                    self.focus = Focus::CommentsAdd;
-                    return Ok(EventState::Consumed);
+                for (&key, action) in &self.kanban.key_mappings {
+                    let command_text = action.to_command_text(key);
+                    commands.push(CommandInfo::new(command_text));
                 }
-                FocusTickets => {
-                    self.focus = Focus::Tickets;
-                    return Ok(EventState::Consumed);
+
+                self.help.set_cmds(commands);
+                self.help.show()?;
+            }
+            Select => {
+                if let Some(ticket) = self.kanban.selected() {
+                    let ticket_key = ticket.key.clone();
+                    match self.tickets.tickets.iter().any(|t| t.key == ticket_key) {
+                        true => self.tickets.select_ticket(&ticket_key)?,
+                        false => self.update_single_ticket(&ticket_key).await?,
+                    }
                 }
+                self.focus = Focus::Tickets;
             }
+            TransitionLeft => self.kanban_transition(false).await?,
+            TransitionRight => self.kanban_transition(true).await?,
         }
         Ok(EventState::Consumed)
     }
 
     async fn search_projects_move_focus(&mut self, key: Key) -> anyhow::Result<EventState> {
         if key == self.config.key_config.enter {
-            let project_input = &self.search_projects.input.clone();
+            let project_input = &self.search_projects.input();
             if self.search_projects.selected().is_some() {
                 let project = self.search_projects.selected().unwrap();
                 if self.projects.select_project(project).is_ok() {
+                    self.use_project_tickets();
                     self.update_all_tickets().await?;
                     self.focus = Focus::Tickets;
                     return Ok(EventState::Consumed);
@@ -815,6 +3126,7 @@ impl App {
                 Ok(p) => {
                     self.single_project_update(p).await?;
                     self.projects.select_project(project_input)?;
+                    self.use_project_tickets();
                     self.update_all_tickets().await?;
                     self.focus = Focus::Tickets;
                     return Ok(EventState::Consumed);
@@ -835,7 +3147,7 @@ impl App {
     }
 
     async fn search_tickets_move_focus(&mut self, key: Key) -> anyhow::Result<EventState> {
-        let ticket_input = &self.search_tickets.input;
+        let ticket_input = &self.search_tickets.input();
         if key == self.config.key_config.enter {
             if let Some(ticket_key) = self.search_tickets.selected() {
                 if self.tickets.select_ticket(ticket_key).is_ok() {
@@ -855,7 +3167,7 @@ impl App {
 
                     if self
                         .tickets
-                        .select_ticket(&self.search_tickets.input)
+                        .select_ticket(&self.search_tickets.input())
                         .is_ok()
                     {
                         self.focus = Focus::Tickets;
@@ -919,12 +3231,16 @@ impl App {
         }
     }
     async fn ticket_move_focus(&mut self, key: Key) -> anyhow::Result<EventState> {
-        let Some(action) = self.tickets_key_mappings.get(&key) else {
+        let Some(&action) = self.tickets_key_mappings.get(&key) else {
             return Ok(EventState::NotConsumed);
         };
         log::debug!("got tickets focus event: {key:?}");
+        self.tickets_action(action).await
+    }
+
+    async fn tickets_action(&mut self, action: TicketsAction) -> anyhow::Result<EventState> {
         use TicketsAction::*;
-        match *action {
+        match action {
             FocusRelation => {
                 self.focus = Focus::TicketRelation;
                 Ok(EventState::Consumed)
@@ -934,17 +3250,19 @@ impl App {
                 Ok(EventState::Consumed)
             }
             OpenCreateTicket => {
-                let project = self
-                    .projects
-                    .selected()
-                    .expect("Project should have been selected");
-                let ticket_type_response = self
+                self.open_create_ticket(None).await?;
+                Ok(EventState::Consumed)
+            }
+            CloneTicket => {
+                let Some(ticket_key) = self.tickets.selected().map(|t| t.key.clone()) else {
+                    return Ok(EventState::Consumed);
+                };
+                let ticket = self
                     .jira
                     .tickets_api
-                    .get_ticket_types(&self.jira.client, &project.project_id)
+                    .search_tickets_api(&ticket_key, &self.jira.client)
                     .await?;
-                self.create_ticket.contents.ticket_types = ticket_type_response;
-                self.focus = Focus::CreateTicket;
+                self.open_create_ticket(Some(ticket)).await?;
                 Ok(EventState::Consumed)
             }
             OpenHelp => {
@@ -969,6 +3287,58 @@ impl App {
                 self.focus = Focus::CommentsList;
                 Ok(EventState::Consumed)
             }
+            MoveToBacklog => {
+                self.move_ticket_to_backlog().await?;
+                Ok(EventState::Consumed)
+            }
+            MoveToSprint => {
+                let Some(ticket) = self.tickets.selected() else {
+                    return Ok(EventState::Consumed);
+                };
+                let ticket_key = ticket.key.clone();
+                self.bulk.sprint_move = false;
+                self.open_boards(vec![ticket_key]).await?;
+                Ok(EventState::Consumed)
+            }
+            OpenBoards => {
+                self.open_boards(vec![]).await?;
+                Ok(EventState::Consumed)
+            }
+            OpenBulk => {
+                self.bulk.open();
+                self.focus = Focus::Bulk;
+                Ok(EventState::Consumed)
+            }
+            OpenHistory => {
+                self.update_history_view().await?;
+                self.focus = Focus::History;
+                Ok(EventState::Consumed)
+            }
+            OpenEpics => {
+                self.open_epics().await?;
+                Ok(EventState::Consumed)
+            }
+            OpenFilters => {
+                self.open_filters().await?;
+                Ok(EventState::Consumed)
+            }
+            OpenJql => {
+                self.open_jql().await?;
+                Ok(EventState::Consumed)
+            }
+            OpenMyWork => {
+                self.open_my_work().await?;
+                Ok(EventState::Consumed)
+            }
+            OpenNotifications => {
+                self.focus = Focus::Notifications;
+                Ok(EventState::Consumed)
+            }
+            OpenKanban => {
+                self.update_kanban().await?;
+                self.focus = Focus::Kanban;
+                Ok(EventState::Consumed)
+            }
             OpenProjects => {
                 self.focus = Focus::Projects;
                 Ok(EventState::Consumed)
@@ -994,6 +3364,22 @@ impl App {
                 self.focus = Focus::Tickets;
                 Ok(EventState::Consumed)
             }
+            RankBottom => {
+                self.rank_ticket(false, true).await?;
+                Ok(EventState::Consumed)
+            }
+            RankDown => {
+                self.rank_ticket(false, false).await?;
+                Ok(EventState::Consumed)
+            }
+            RankTop => {
+                self.rank_ticket(true, true).await?;
+                Ok(EventState::Consumed)
+            }
+            RankUp => {
+                self.rank_ticket(true, false).await?;
+                Ok(EventState::Consumed)
+            }
             Reset => {
                 self.projects.projects.clear();
                 self.tickets.tickets.clear();
@@ -1008,13 +3394,16 @@ impl App {
 
     async fn move_focus(&mut self, key: Key) -> anyhow::Result<EventState> {
         match self.focus {
+            Focus::Boards => {
+                self.boards_move_focus(key).await?;
+            }
             Focus::CommentsList => {
                 self.comments_move_focus(key).await?;
             }
             Focus::CommentsAdd => {
                 if key == self.config.key_config.esc {
                     self.update_comments_view().await?;
-                    self.comment_add.messages.clear();
+                    self.comment_add.clear();
                     self.focus = Focus::CommentsList;
                     return Ok(EventState::Consumed);
                 }
@@ -1043,12 +3432,41 @@ impl App {
             }
             Focus::CreateTicket => {
                 if key == self.config.key_config.esc {
-                    self.create_ticket.contents.description.clear();
-                    self.create_ticket.contents.summary.clear();
+                    self.create_ticket.clear();
                     self.focus = Focus::Tickets;
                     return Ok(EventState::Consumed);
                 }
             }
+            Focus::Epics => {
+                self.epics_move_focus(key).await?;
+            }
+            Focus::Bulk => {
+                self.bulk_move_focus(key).await?;
+            }
+            Focus::Filters => {
+                self.filters_move_focus(key).await?;
+            }
+            Focus::History => {
+                self.history_move_focus(key).await?;
+            }
+            Focus::MyWork => {
+                self.my_work_move_focus(key).await?;
+            }
+            Focus::Notifications => {
+                self.notifications_move_focus(key).await?;
+            }
+            Focus::Jql => {
+                if key == self.config.key_config.esc {
+                    self.focus = match self.tickets.tickets.is_empty() {
+                        true => Focus::Projects,
+                        false => Focus::Tickets,
+                    };
+                    return Ok(EventState::Consumed);
+                }
+            }
+            Focus::Kanban => {
+                self.kanban_move_focus(key).await?;
+            }
             Focus::Labels => {
                 if key == self.config.key_config.previous || key == self.config.key_config.move_up {
                     // self.update_labels().await?;
@@ -1075,6 +3493,8 @@ impl App {
             Focus::SearchTickets => {
                 self.search_tickets_move_focus(key).await?;
             }
+            // The finder handles every key it uses in widget_event
+            Focus::TicketFinder => {}
             Focus::TicketParent => {
                 self.ticket_parent_move_focus(key).await?;
             }
@@ -1113,3 +3533,190 @@ impl App {
         Ok(EventState::NotConsumed)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+
+    fn keys<A>(mappings: &HashMap<Key, A>) -> Vec<Key> {
+        mappings.keys().copied().collect()
+    }
+
+    // key_contexts_cover_the_key_mappings binds every action to a key of its
+    // own, so each key of a screen tells which action the screen handles
+    #[test]
+    fn key_contexts_cover_the_key_mappings() {
+        let config: BTreeMap<String, String> = KeyConfig::default()
+            .bindings()
+            .into_iter()
+            .enumerate()
+            .map(|(i, (name, _))| {
+                let key = char::from_u32(0x4e00 + i as u32).unwrap();
+                (name.to_string(), key.to_string())
+            })
+            .collect();
+        let (key_config, problems) = KeyConfig::from_config(&config);
+        assert_eq!(problems, Vec::<String>::new());
+        let names: HashMap<Key, &str> = key_config
+            .bindings()
+            .into_iter()
+            .map(|(name, key)| (key, name))
+            .collect();
+
+        let kc = || key_config.clone();
+        let comment_add = CommentAdd::new(kc());
+        let jql = JqlWidget::new(kc());
+        let screens: Vec<(&str, Vec<Key>)> = vec![
+            (
+                "add comment",
+                [
+                    keys(&comment_add.normal_key_mappings),
+                    keys(&comment_add.edit_key_mappings),
+                ]
+                .concat(),
+            ),
+            (
+                "boards",
+                [
+                    keys(&BoardsAction::key_mappings(&kc())),
+                    keys(&BoardsWidget::new(kc()).key_mappings),
+                ]
+                .concat(),
+            ),
+            (
+                "bulk",
+                [
+                    keys(&BulkAction::key_mappings(&kc())),
+                    keys(&BulkWidget::new(kc()).key_mappings),
+                ]
+                .concat(),
+            ),
+            (
+                "command palette",
+                keys(&CommandPalette::<PaletteCommand>::new(kc()).key_mappings),
+            ),
+            (
+                "comments",
+                [
+                    keys(&CommentsAction::key_mappings(&kc())),
+                    keys(&CommentsList::new(kc()).key_mappings),
+                ]
+                .concat(),
+            ),
+            (
+                "create ticket",
+                keys(&CreateTicketWidget::new(kc()).key_mappings),
+            ),
+            (
+                "epics",
+                [
+                    keys(&EpicsAction::key_mappings(&kc())),
+                    keys(&EpicsWidget::new(kc()).key_mappings),
+                ]
+                .concat(),
+            ),
+            (
+                "filters",
+                [
+                    keys(&FiltersAction::key_mappings(&kc())),
+                    keys(&FiltersWidget::new(kc()).key_mappings),
+                ]
+                .concat(),
+            ),
+            ("help", vec![]),
+            (
+                "history",
+                [
+                    keys(&HistoryAction::key_mappings(&kc())),
+                    keys(&HistoryWidget::new(kc()).key_mappings),
+                ]
+                .concat(),
+            ),
+            (
+                "jql",
+                [
+                    keys(&jql.normal_key_mappings),
+                    keys(&jql.edit_key_mappings),
+                    keys(&jql.naming_key_mappings),
+                ]
+                .concat(),
+            ),
+            (
+                "kanban",
+                [
+                    keys(&KanbanAction::key_mappings(&kc())),
+                    keys(&KanbanWidget::new(kc()).key_mappings),
+                ]
+                .concat(),
+            ),
+            (
+                "my work",
+                [
+                    keys(&MyWorkAction::key_mappings(&kc())),
+                    keys(&MyWorkWidget::new(kc()).key_mappings),
+                ]
+                .concat(),
+            ),
+            (
+                "notifications",
+                [
+                    keys(&NotificationsAction::key_mappings(&kc())),
+                    keys(&NotificationsWidget::new(kc()).key_mappings),
+                ]
+                .concat(),
+            ),
+            (
+                "projects",
+                [
+                    keys(&ProjectsAction::key_mappings(&kc())),
+                    keys(&ProjectsWidget::new(&vec![], kc()).key_mappings),
+                ]
+                .concat(),
+            ),
+            (
+                "side panes",
+                [
+                    keys(&ParentAction::key_mappings(&kc())),
+                    keys(&TicketParentWidget::new(kc(), "").key_mappings),
+                ]
+                .concat(),
+            ),
+            (
+                "tickets",
+                [
+                    keys(&TicketsAction::key_mappings(&kc())),
+                    keys(&TicketWidget::new(kc(), String::new()).key_mappings),
+                ]
+                .concat(),
+            ),
+            (
+                "ticket finder",
+                keys(&TicketFinderWidget::new(kc()).key_mappings),
+            ),
+            ("ticket transition", vec![]),
+        ];
+
+        let contexts = config::key_contexts();
+        assert_eq!(
+            screens
+                .iter()
+                .map(|(screen, _)| *screen)
+                .collect::<Vec<_>>(),
+            contexts
+                .iter()
+                .map(|(context, _)| *context)
+                .collect::<Vec<_>>()
+        );
+        for ((screen, keys), (_, actions)) in screens.iter().zip(&contexts) {
+            // Keys that are not in the config, such as arrows, can not conflict
+            let unchecked: Vec<&str> = keys
+                .iter()
+                .filter_map(|key| names.get(key))
+                .filter(|name| !actions.contains(name))
+                .copied()
+                .collect();
+            assert_eq!(unchecked, Vec::<&str>::new(), "{screen}");
+        }
+    }
+}
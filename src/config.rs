@@ -1,4 +1,4 @@
-use std::{env, fs, process::exit};
+use std::{collections::BTreeMap, env, fs, process::exit};
 
 // use crate::{event::key::Key, log::LogLevel};
 use crate::events::key::Key;
@@ -12,10 +12,17 @@ use serde::Serialize;
 pub struct JiraConfigFile {
     pub api_key: Option<String>,
     pub api_version: Option<String>,
+    pub custom_fields: Option<BTreeMap<String, String>>,
     pub db_file: Option<bool>,
     pub domain: String,
     pub user_email: String,
+    pub key_config: Option<BTreeMap<String, String>>,
+    pub layout: Option<JiraConfigLayout>,
+    pub notifications: Option<JiraConfigNotifications>,
     pub projects: Option<JiraConfigProjects>,
+    pub queries: Option<BTreeMap<String, String>>,
+    pub theme: Option<JiraConfigTheme>,
+    pub ticket_table: Option<JiraConfigTicketTable>,
     pub tickets: Option<JiraConfigTickets>,
 }
 
@@ -24,11 +31,73 @@ pub struct JiraConfigTickets {
     // TODO: This functionality needs to be handled
     pub current_sprint_tickets_only: Option<bool>,
     pub current_user_tickets_only: Option<bool>,
-    // TODO: This functionality needs to be handled
     pub show_unassgined: Option<bool>,
     pub show_ticket_status: Option<Vec<String>>,
 }
 
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct JiraConfigNotifications {
+    pub enabled: Option<bool>,
+    pub poll_interval_secs: Option<u64>,
+}
+
+// ColumnWidth is either a number of characters or a percentage like "20%"
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+#[serde(untagged)]
+pub enum ColumnWidth {
+    Length(u16),
+    Percentage(String),
+}
+
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct JiraConfigColumn {
+    pub name: String,
+    pub width: Option<ColumnWidth>,
+}
+
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct JiraConfigTicketTable {
+    pub columns: Option<Vec<JiraConfigColumn>>,
+    pub sort_by: Option<String>,
+    pub sort_order: Option<String>,
+}
+
+// JiraConfigLayout places the panes of the tickets screen, see pane_layout.rs
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct JiraConfigLayout {
+    pub columns: Option<Vec<JiraConfigLayoutColumn>>,
+    pub bottom: Option<Vec<String>>,
+    pub bottom_height: Option<u16>,
+    pub stack_below: Option<u16>,
+}
+
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct JiraConfigLayoutColumn {
+    pub width: Option<u16>,
+    pub panes: Vec<JiraConfigLayoutPane>,
+}
+
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct JiraConfigLayoutPane {
+    pub name: String,
+    pub height: Option<u16>,
+}
+
+// JiraConfigTheme picks a preset and overrides its styles, see theme.rs
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct JiraConfigTheme {
+    pub preset: Option<String>,
+    pub focused_border: Option<String>,
+    pub highlight: Option<String>,
+    pub edit_mode: Option<String>,
+    pub error: Option<String>,
+    pub success: Option<String>,
+    pub muted: Option<String>,
+    pub updated: Option<String>,
+    pub status: Option<BTreeMap<String, String>>,
+    pub priority: Option<BTreeMap<String, String>>,
+}
+
 #[derive(Debug, Deserialize, Clone, Default)]
 pub struct JiraConfigProjects {
     pub default_projects: String,
@@ -39,6 +108,10 @@ pub struct Config {
     #[serde(default)]
     pub key_config: KeyConfig,
     pub jira_config: JiraConfigFile,
+    // key_config_problems are the bindings of [key_config] that could not be
+    // used or that conflict, reported once the app is up
+    #[serde(skip)]
+    pub key_config_problems: Vec<String>,
     // #[serde(default)]
     // pub log_level: LogLevel,
 }
@@ -55,7 +128,8 @@ pub struct JiraConfig {
 #[derive(Debug, Deserialize, Clone)]
 #[cfg_attr(test, derive(Serialize))]
 pub struct KeyConfig {
-    pub backspace: Key,
+    pub clear_selection: Key,
+    pub clone_ticket: Key,
     pub enter: Key,
     pub esc: Key,
     pub exit: Key,
@@ -65,15 +139,36 @@ pub struct KeyConfig {
     pub move_down: Key,
     pub move_left: Key,
     pub move_right: Key,
+    pub move_to_backlog: Key,
+    pub move_to_sprint: Key,
     pub open_create_ticket: Key,
+    pub open_editor: Key,
+    pub open_epics: Key,
+    pub open_filters: Key,
+    pub open_finder: Key,
     pub open_browser: Key,
+    pub open_bulk: Key,
     pub open_help: Key,
+    pub open_jql: Key,
+    pub open_my_work: Key,
+    pub open_notifications: Key,
+    pub open_palette: Key,
     pub next: Key,
     pub next_page: Key,
+    pub open_boards: Key,
+    pub open_kanban: Key,
     pub previous: Key,
     pub previous_page: Key,
     pub quit: Key,
+    pub rank_bottom: Key,
+    pub rank_down: Key,
+    pub rank_top: Key,
+    pub rank_up: Key,
+    pub sort_column: Key,
+    pub sort_order: Key,
+    pub mark_all_read: Key,
     pub reset: Key,
+    pub save_query: Key,
     pub scroll_up: Key,
     pub scroll_down: Key,
     pub page_up: Key,
@@ -85,15 +180,24 @@ pub struct KeyConfig {
     pub scroll_up_multiple_lines: Key,
     pub scroll_to_top: Key,
     pub scroll_to_bottom: Key,
+    pub select_all: Key,
     pub ticket_transition: Key,
     pub ticket_add_comments: Key,
     pub ticket_view_comments: Key,
+    pub ticket_view_history: Key,
+    pub toggle_clone_link: Key,
+    pub toggle_clone_subtasks: Key,
+    pub toggle_select: Key,
+    pub transition_left: Key,
+    pub transition_right: Key,
+    pub zoom: Key,
 }
 
 impl Default for KeyConfig {
     fn default() -> Self {
         Self {
-            backspace: Key::Backspace,
+            clear_selection: Key::Char('x'),
+            clone_ticket: Key::Char('y'),
             enter: Key::Enter,
             esc: Key::Esc,
             exit: Key::Ctrl('c'),
@@ -103,18 +207,39 @@ impl Default for KeyConfig {
             move_down: Key::Down,
             move_left: Key::Left,
             move_right: Key::Right,
+            move_to_backlog: Key::Char('B'),
+            move_to_sprint: Key::Char('M'),
             next: Key::Tab,
             next_page: Key::Char('n'),
+            open_boards: Key::Char('b'),
+            open_kanban: Key::Char('v'),
             open_browser: Key::Char('o'),
+            open_bulk: Key::Char('A'),
             open_create_ticket: Key::Char('m'),
+            open_editor: Key::Ctrl('e'),
+            open_epics: Key::Char('E'),
+            open_filters: Key::Char('F'),
+            open_finder: Key::Ctrl('f'),
             open_help: Key::Char('?'),
+            open_jql: Key::Char('Q'),
+            open_my_work: Key::Char('W'),
+            open_notifications: Key::Char('I'),
+            open_palette: Key::Char(':'),
             page_up: Key::Char('K'),
             page_down: Key::Char('J'),
             previous: Key::BackTab,
             previous_page: Key::Char('N'),
             push: Key::Char('P'),
             quit: Key::Char('q'),
+            rank_bottom: Key::Alt('G'),
+            rank_down: Key::Alt('j'),
+            rank_top: Key::Alt('g'),
+            rank_up: Key::Alt('k'),
+            sort_column: Key::Char('s'),
+            sort_order: Key::Char('S'),
+            mark_all_read: Key::Char('R'),
             reset: Key::Char('r'),
+            save_query: Key::Ctrl('s'),
             scroll_up: Key::Char('k'),
             scroll_down: Key::Char('j'),
             scroll_right: Key::Char('l'),
@@ -123,17 +248,381 @@ impl Default for KeyConfig {
             scroll_up_multiple_lines: Key::Ctrl('u'),
             scroll_to_top: Key::Char('g'),
             scroll_to_bottom: Key::Char('G'),
+            select_all: Key::Char('a'),
             ticket_transition: Key::Char('t'),
             ticket_add_comments: Key::Char('C'),
             ticket_view_comments: Key::Char('c'),
+            ticket_view_history: Key::Char('H'),
+            toggle_clone_link: Key::Char('L'),
+            toggle_clone_subtasks: Key::Char('T'),
+            toggle_select: Key::Char(' '),
+            transition_left: Key::Char('<'),
+            transition_right: Key::Char('>'),
+            zoom: Key::Char('z'),
         }
     }
 }
 
+// SCROLL_ACTIONS move through the lists of every screen
+const SCROLL_ACTIONS: [&str; 6] = [
+    "scroll_up",
+    "scroll_down",
+    "scroll_up_multiple_lines",
+    "scroll_down_multiple_lines",
+    "scroll_to_top",
+    "scroll_to_bottom",
+];
+
+// key_contexts are the actions handled together on each screen, two of them
+// can not share a key. quit, exit, open_palette and open_finder work on every
+// screen. A test in app.rs checks them against the key mappings of the screens.
+pub(crate) fn key_contexts() -> Vec<(&'static str, Vec<&'static str>)> {
+    let contexts: [(&str, &[&str]); 19] = [
+        ("add comment", &["edit", "esc", "open_editor", "push"]),
+        ("boards", &["enter", "esc", "next", "open_help", "previous"]),
+        ("bulk", &["enter", "esc", "open_help"]),
+        (
+            "command palette",
+            &["enter", "esc", "move_down", "move_up", "next", "previous"],
+        ),
+        (
+            "comments",
+            &[
+                "esc",
+                "open_help",
+                "page_down",
+                "page_up",
+                "ticket_add_comments",
+            ],
+        ),
+        (
+            "create ticket",
+            &[
+                "edit",
+                "enter",
+                "esc",
+                "next",
+                "open_editor",
+                "previous",
+                "push",
+                "toggle_clone_link",
+                "toggle_clone_subtasks",
+            ],
+        ),
+        ("epics", &["enter", "esc", "open_help", "reset"]),
+        ("filters", &["enter", "esc", "filter", "open_help"]),
+        ("help", &["esc", "open_help"]),
+        ("history", &["esc", "filter", "open_help", "reset"]),
+        (
+            "jql",
+            &[
+                "edit",
+                "enter",
+                "esc",
+                "move_down",
+                "move_up",
+                "next",
+                "save_query",
+            ],
+        ),
+        (
+            "kanban",
+            &[
+                "enter",
+                "esc",
+                "move_left",
+                "move_right",
+                "open_help",
+                "scroll_left",
+                "scroll_right",
+                "transition_left",
+                "transition_right",
+            ],
+        ),
+        (
+            "my work",
+            &["enter", "esc", "next", "open_help", "previous", "reset"],
+        ),
+        (
+            "notifications",
+            &["enter", "esc", "mark_all_read", "open_help"],
+        ),
+        (
+            "projects",
+            &[
+                "enter",
+                "filter",
+                "next_page",
+                "open_boards",
+                "open_epics",
+                "open_filters",
+                "open_help",
+                "open_jql",
+                "open_my_work",
+                "open_notifications",
+                "previous_page",
+                "reset",
+            ],
+        ),
+        (
+            "side panes",
+            &[
+                "esc",
+                "move_down",
+                "move_right",
+                "move_up",
+                "next",
+                "open_browser",
+                "open_help",
+                "previous",
+                "ticket_view_comments",
+                "zoom",
+            ],
+        ),
+        (
+            "tickets",
+            &[
+                "clear_selection",
+                "clone_ticket",
+                "esc",
+                "filter",
+                "move_down",
+                "move_to_backlog",
+                "move_to_sprint",
+                "move_up",
+                "next",
+                "next_page",
+                "open_boards",
+                "open_browser",
+                "open_bulk",
+                "open_create_ticket",
+                "open_epics",
+                "open_filters",
+                "open_help",
+                "open_jql",
+                "open_kanban",
+                "open_my_work",
+                "open_notifications",
+                "page_down",
+                "page_up",
+                "previous",
+                "previous_page",
+                "rank_bottom",
+                "rank_down",
+                "rank_top",
+                "rank_up",
+                "reset",
+                "select_all",
+                "sort_column",
+                "sort_order",
+                "ticket_transition",
+                "ticket_view_comments",
+                "ticket_view_history",
+                "toggle_select",
+                "zoom",
+            ],
+        ),
+        (
+            "ticket finder",
+            &["enter", "esc", "move_down", "move_up", "next", "previous"],
+        ),
+        ("ticket transition", &["enter", "esc", "next"]),
+    ];
+    contexts
+        .into_iter()
+        .map(|(context, actions)| {
+            let mut actions = actions.to_vec();
+            actions.extend(SCROLL_ACTIONS);
+            actions.extend(["quit", "exit", "open_palette", "open_finder"]);
+            (context, actions)
+        })
+        .collect()
+}
+
+impl KeyConfig {
+    fn bindings_mut(&mut self) -> Vec<(&'static str, &mut Key)> {
+        vec![
+            ("clear_selection", &mut self.clear_selection),
+            ("clone_ticket", &mut self.clone_ticket),
+            ("enter", &mut self.enter),
+            ("esc", &mut self.esc),
+            ("exit", &mut self.exit),
+            ("filter", &mut self.filter),
+            ("edit", &mut self.edit),
+            ("move_up", &mut self.move_up),
+            ("move_down", &mut self.move_down),
+            ("move_left", &mut self.move_left),
+            ("move_right", &mut self.move_right),
+            ("move_to_backlog", &mut self.move_to_backlog),
+            ("move_to_sprint", &mut self.move_to_sprint),
+            ("open_create_ticket", &mut self.open_create_ticket),
+            ("open_editor", &mut self.open_editor),
+            ("open_epics", &mut self.open_epics),
+            ("open_filters", &mut self.open_filters),
+            ("open_finder", &mut self.open_finder),
+            ("open_browser", &mut self.open_browser),
+            ("open_bulk", &mut self.open_bulk),
+            ("open_help", &mut self.open_help),
+            ("open_jql", &mut self.open_jql),
+            ("open_my_work", &mut self.open_my_work),
+            ("open_notifications", &mut self.open_notifications),
+            ("open_palette", &mut self.open_palette),
+            ("next", &mut self.next),
+            ("next_page", &mut self.next_page),
+            ("open_boards", &mut self.open_boards),
+            ("open_kanban", &mut self.open_kanban),
+            ("previous", &mut self.previous),
+            ("previous_page", &mut self.previous_page),
+            ("quit", &mut self.quit),
+            ("rank_bottom", &mut self.rank_bottom),
+            ("rank_down", &mut self.rank_down),
+            ("rank_top", &mut self.rank_top),
+            ("rank_up", &mut self.rank_up),
+            ("sort_column", &mut self.sort_column),
+            ("sort_order", &mut self.sort_order),
+            ("mark_all_read", &mut self.mark_all_read),
+            ("reset", &mut self.reset),
+            ("save_query", &mut self.save_query),
+            ("scroll_up", &mut self.scroll_up),
+            ("scroll_down", &mut self.scroll_down),
+            ("page_up", &mut self.page_up),
+            ("page_down", &mut self.page_down),
+            ("push", &mut self.push),
+            ("scroll_right", &mut self.scroll_right),
+            ("scroll_left", &mut self.scroll_left),
+            (
+                "scroll_down_multiple_lines",
+                &mut self.scroll_down_multiple_lines,
+            ),
+            (
+                "scroll_up_multiple_lines",
+                &mut self.scroll_up_multiple_lines,
+            ),
+            ("scroll_to_top", &mut self.scroll_to_top),
+            ("scroll_to_bottom", &mut self.scroll_to_bottom),
+            ("select_all", &mut self.select_all),
+            ("ticket_transition", &mut self.ticket_transition),
+            ("ticket_add_comments", &mut self.ticket_add_comments),
+            ("ticket_view_comments", &mut self.ticket_view_comments),
+            ("ticket_view_history", &mut self.ticket_view_history),
+            ("toggle_clone_link", &mut self.toggle_clone_link),
+            ("toggle_clone_subtasks", &mut self.toggle_clone_subtasks),
+            ("toggle_select", &mut self.toggle_select),
+            ("transition_left", &mut self.transition_left),
+            ("transition_right", &mut self.transition_right),
+            ("zoom", &mut self.zoom),
+        ]
+    }
+
+    // bindings are the keys of every action, by the action name used in
+    // the [key_config] section
+    pub fn bindings(&self) -> Vec<(&'static str, Key)> {
+        self.clone()
+            .bindings_mut()
+            .into_iter()
+            .map(|(name, key)| (name, *key))
+            .collect()
+    }
+
+    // starts_sequence tells if c is the first key of a sequence like "g g"
+    pub fn starts_sequence(&self, c: char) -> bool {
+        self.bindings()
+            .iter()
+            .any(|(_, key)| matches!(key, Key::Chord(first, _) if *first == c))
+    }
+
+    // conflicts lists the actions sharing a key on the same screen, and keys
+    // that also start a sequence there as the sequence could never be typed
+    pub fn conflicts(&self) -> Vec<String> {
+        let bindings = self.bindings();
+        let mut conflicts: BTreeMap<String, Vec<&str>> = BTreeMap::new();
+        for (context, actions) in key_contexts() {
+            let keys: Vec<(&str, Key)> = bindings
+                .iter()
+                .filter(|(name, _)| actions.contains(name))
+                .copied()
+                .collect();
+            for (i, (name, key)) in keys.iter().enumerate() {
+                for (other, other_key) in &keys[i + 1..] {
+                    let conflict = match (key, other_key) {
+                        _ if key == other_key => format!("{name} and {other} are both {key}"),
+                        (Key::Char(c), Key::Chord(first, _)) if c == first => {
+                            format!("{name} ({key}) starts {other} ({other_key})")
+                        }
+                        (Key::Chord(first, _), Key::Char(c)) if c == first => {
+                            format!("{other} ({other_key}) starts {name} ({key})")
+                        }
+                        _ => continue,
+                    };
+                    conflicts.entry(conflict).or_default().push(context);
+                }
+            }
+        }
+        conflicts
+            .into_iter()
+            .map(|(conflict, contexts)| format!("{conflict} in {}", contexts.join(", ")))
+            .collect()
+    }
+
+    // from_config overrides the default keys with the [key_config] section,
+    // keys that can not be read or bound are reported with the conflicts
+    pub fn from_config(config: &BTreeMap<String, String>) -> (KeyConfig, Vec<String>) {
+        let mut key_config = KeyConfig::default();
+        let mut problems = vec![];
+        for (name, text) in config {
+            let key = match text.parse::<Key>() {
+                Ok(key) => key,
+                Err(e) => {
+                    problems.push(format!("{name}: {e}"));
+                    continue;
+                }
+            };
+            match key_config
+                .bindings_mut()
+                .into_iter()
+                .find(|(action, _)| action == name)
+            {
+                Some((_, binding)) => *binding = key,
+                None => problems.push(format!("unknown action {name}")),
+            }
+        }
+        problems.extend(key_config.conflicts());
+        (key_config, problems)
+    }
+}
+
+// config_dir holds config.toml and the ticket templates, under the HOME of the user
+pub fn config_dir() -> String {
+    let home_directory = std::env::var("HOME").unwrap_or_default();
+    format!("{}/.config/jirust", home_directory)
+}
+
+fn config_file_path() -> String {
+    format!("{}/config.toml", config_dir())
+}
+
+// save_query stores a named JQL query under the [queries] table of config.toml
+pub fn save_query(name: &str, jql: &str) -> anyhow::Result<()> {
+    let filename = config_file_path();
+    let contents = fs::read_to_string(&filename)?;
+    fs::write(&filename, insert_query(&contents, name, jql)?)?;
+    Ok(())
+}
+
+// insert_query edits the config in place so comments and ordering survive
+fn insert_query(contents: &str, name: &str, jql: &str) -> anyhow::Result<String> {
+    let mut config: toml_edit::Document = contents.parse()?;
+    let queries = config.entry("queries").or_insert(toml_edit::table());
+    let Some(queries) = queries.as_table_like_mut() else {
+        anyhow::bail!("`queries` in config.toml is not a table");
+    };
+    queries.insert(name, toml_edit::value(jql));
+    Ok(config.to_string())
+}
+
 impl Default for JiraConfigFile {
     fn default() -> Self {
-        let home_directory = env!("HOME");
-        let filename = format!("{}/.config/jirust/config.toml", home_directory);
+        let filename = config_file_path();
         let contents = match fs::read_to_string(filename.clone()) {
             // If successful return the files text as `contents`.
             // `c` is a local variable.
@@ -182,9 +671,16 @@ impl Default for JiraConfigFile {
         Self {
             api_key: Some(jira_api_key),
             api_version: Some(jira_api_version),
+            custom_fields: data.custom_fields,
             db_file,
             domain,
+            key_config: data.key_config,
+            layout: data.layout,
+            notifications: data.notifications,
             projects: data.projects,
+            queries: data.queries,
+            theme: data.theme,
+            ticket_table: data.ticket_table,
             tickets: data.tickets,
             user_email: jira_user_email,
         }
@@ -193,6 +689,92 @@ impl Default for JiraConfigFile {
 
 impl Config {
     pub fn new() -> anyhow::Result<Self> {
-        Ok(Config::default())
+        let jira_config = JiraConfigFile::default();
+        let (key_config, key_config_problems) =
+            KeyConfig::from_config(&jira_config.key_config.clone().unwrap_or_default());
+        Ok(Config {
+            key_config,
+            jira_config,
+            key_config_problems,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_keys_do_not_conflict() {
+        assert_eq!(KeyConfig::default().conflicts(), Vec::<String>::new());
+    }
+
+    #[test]
+    fn keys_from_config() {
+        let config = BTreeMap::from([
+            ("scroll_to_top".to_string(), "g g".to_string()),
+            ("scroll_down".to_string(), "ctrl-n".to_string()),
+            ("edit".to_string(), "esc".to_string()),
+            ("teleport".to_string(), "t".to_string()),
+            ("quit".to_string(), "hyper-q".to_string()),
+        ]);
+        let (key_config, problems) = KeyConfig::from_config(&config);
+        assert_eq!(key_config.scroll_to_top, Key::Chord('g', 'g'));
+        assert_eq!(key_config.scroll_down, Key::Ctrl('n'));
+        assert_eq!(key_config.quit, Key::Char('q'));
+        assert!(key_config.starts_sequence('g'));
+        assert_eq!(
+            problems,
+            vec![
+                "quit: unknown key \"hyper-q\"".to_string(),
+                "unknown action teleport".to_string(),
+                "esc and edit are both <Esc> in add comment, create ticket, jql".to_string(),
+            ]
+        );
+
+        let (_, problems) = KeyConfig::from_config(&BTreeMap::from([(
+            "rank_top".to_string(),
+            "G g".to_string(),
+        )]));
+        assert_eq!(problems.len(), 1);
+        assert!(problems[0].starts_with("scroll_to_bottom (G) starts rank_top (G g) in "));
+    }
+
+    #[test]
+    fn saved_query_keeps_comments() {
+        let contents = r#"# These two properties are required
+domain = "https://example.atlassian.net"
+user_email = "me@example.com"
+api_key = "secret"
+api_version = "3"
+db_file = true # cached db in /tmp
+
+[tickets]
+# Show tickets in these status only
+show_ticket_status = ["Done", "In Progress"]
+
+[queries]
+# reviews waiting on me
+reviews = "status = Review"
+"#;
+        let saved = insert_query(contents, "my bugs", r#"type = "Bug""#).unwrap();
+        assert_eq!(
+            saved,
+            format!("{contents}\"my bugs\" = \"type = \\\"Bug\\\"\"\n")
+        );
+        let config: JiraConfigFile = toml::from_str(&saved).unwrap();
+        assert_eq!(config.domain, "https://example.atlassian.net");
+        assert_eq!(config.user_email, "me@example.com");
+        assert_eq!(
+            config.queries.unwrap().get("my bugs").map(String::as_str),
+            Some(r#"type = "Bug""#)
+        );
+
+        let contents = "domain = \"https://example.atlassian.net\" # no trailing slash\nuser_email = \"me@example.com\"\n";
+        let saved = insert_query(contents, "open", "resolution IS EMPTY").unwrap();
+        assert_eq!(
+            saved,
+            format!("{contents}\n[queries]\nopen = \"resolution IS EMPTY\"\n")
+        );
     }
 }
@@ -0,0 +1,45 @@
+use std::{env, fs, process::Command};
+
+// EditorTarget is what the text written in the external editor is for
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum EditorTarget {
+    Comment,
+    Ticket,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct EditorRequest {
+    pub target: EditorTarget,
+    pub text: String,
+}
+
+// editor_command is $VISUAL, then $EDITOR, then vi. It may have arguments,
+// like "code --wait".
+fn editor_command() -> String {
+    ["VISUAL", "EDITOR"]
+        .iter()
+        .filter_map(|name| env::var(name).ok())
+        .find(|editor| !editor.trim().is_empty())
+        .unwrap_or_else(|| String::from("vi"))
+}
+
+// edit opens the text in the external editor and returns it once the editor
+// exits. The terminal has to be handed over to the editor before.
+pub fn edit(text: &str) -> anyhow::Result<String> {
+    let path = env::temp_dir().join(format!("jirust-{}.md", std::process::id()));
+    fs::write(&path, text)?;
+    let editor = editor_command();
+    let mut parts = editor.split_whitespace();
+    let program = parts.next().unwrap_or("vi");
+    let status = Command::new(program).args(parts).arg(&path).status();
+    let edited = fs::read_to_string(&path);
+    // the text is only lost when the editor could not save it
+    if edited.is_ok() {
+        fs::remove_file(&path)?;
+    }
+    let status = status.map_err(|e| anyhow::anyhow!("Could not start {editor}: {e}"))?;
+    if !status.success() {
+        anyhow::bail!("{editor} exited with {status}");
+    }
+    Ok(edited?)
+}
@@ -1,8 +1,19 @@
 pub mod key;
+pub mod mouse;
 
-use crate::events::key::Key;
+use crate::events::{
+    key::Key,
+    mouse::{Clicks, Mouse},
+};
 use crossterm::event;
-use std::{sync::mpsc, thread, time::Duration};
+use std::{
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc, Arc,
+    },
+    thread,
+    time::{Duration, Instant},
+};
 
 #[derive(Debug, Clone, Copy)]
 pub struct EventConfig {
@@ -19,15 +30,21 @@ impl Default for EventConfig {
     }
 }
 
-#[derive(Copy, Clone)]
+#[derive(Clone)]
 pub enum Event<I> {
     Input(I),
+    // Paste is text pasted in the terminal, delivered at once instead of
+    // as key presses
+    Paste(String),
+    Mouse(Mouse),
     Tick,
 }
 
 pub struct Events {
     rx: mpsc::Receiver<Event<Key>>,
     _tx: mpsc::Sender<Event<Key>>,
+    // paused stops reading the terminal while another program uses it
+    paused: Arc<AtomicBool>,
 }
 
 impl Events {
@@ -40,21 +57,49 @@ impl Events {
 
     pub fn with_config(config: EventConfig) -> Events {
         let (tx, rx) = mpsc::channel();
+        let paused = Arc::new(AtomicBool::new(false));
 
         let event_tx = tx.clone();
+        let input_paused = paused.clone();
+        let mut clicks = Clicks::default();
         thread::spawn(move || loop {
-            if event::poll(config.tick_rate).unwrap() {
-                if let event::Event::Key(key) = event::read().unwrap() {
-                    let key = Key::from(key);
+            if input_paused.load(Ordering::Relaxed) {
+                thread::sleep(config.tick_rate);
+                continue;
+            }
+            if event::poll(config.tick_rate).unwrap() && !input_paused.load(Ordering::Relaxed) {
+                match event::read().unwrap() {
+                    event::Event::Key(key) => {
+                        let key = Key::from(key);
 
-                    event_tx.send(Event::Input(key)).unwrap();
+                        event_tx.send(Event::Input(key)).unwrap();
+                    }
+                    event::Event::Paste(text) => event_tx.send(Event::Paste(text)).unwrap(),
+                    event::Event::Mouse(mouse) => {
+                        if let Some(mouse) = clicks.mouse(mouse, Instant::now()) {
+                            event_tx.send(Event::Mouse(mouse)).unwrap();
+                        }
+                    }
+                    _ => {}
                 }
             }
 
             event_tx.send(Event::Tick).unwrap();
         });
 
-        Events { rx, _tx: tx }
+        Events {
+            rx,
+            _tx: tx,
+            paused,
+        }
+    }
+
+    pub fn pause(&self) {
+        self.paused.store(true, Ordering::Relaxed);
+    }
+
+    pub fn resume(&self) {
+        self.paused.store(false, Ordering::Relaxed);
     }
 
     pub fn next(&self) -> Result<Event<Key>, mpsc::RecvError> {
@@ -1,6 +1,6 @@
 use crossterm::event;
 use serde::Deserialize;
-use std::fmt;
+use std::{fmt, str::FromStr};
 
 #[cfg(test)]
 use serde::Serialize;
@@ -28,6 +28,22 @@ pub enum Key {
     Up,
     /// Down arrow
     Down,
+    /// Left arrow with control modifier
+    CtrlLeft,
+    /// Right arrow with control modifier
+    CtrlRight,
+    /// Left arrow with shift modifier
+    ShiftLeft,
+    /// Right arrow with shift modifier
+    ShiftRight,
+    /// Up arrow with shift modifier
+    ShiftUp,
+    /// Down arrow with shift modifier
+    ShiftDown,
+    /// Home key with shift modifier
+    ShiftHome,
+    /// End key with shift modifier
+    ShiftEnd,
 
     /// Insert key
     Ins,
@@ -71,6 +87,8 @@ pub enum Key {
     Char(char),
     Ctrl(char),
     Alt(char),
+    /// Two keys pressed one after the other, like `g g`
+    Chord(char, char),
     Unknown,
 }
 
@@ -111,10 +129,19 @@ impl fmt::Display for Key {
             Key::Alt(c) => write!(f, "<Alt+{}>", c),
             Key::Ctrl(c) => write!(f, "<Ctrl+{}>", c),
             Key::Char(c) => write!(f, "{}", c),
+            Key::Chord(first, second) => write!(f, "{} {}", Key::Char(first), Key::Char(second)),
             Key::Left => write!(f, "\u{2190}"),  //←
             Key::Right => write!(f, "\u{2192}"), //→
             Key::Up => write!(f, "\u{2191}"),    //↑
             Key::Down => write!(f, "\u{2193}"),  //↓
+            Key::CtrlLeft => write!(f, "<Ctrl+\u{2190}>"),
+            Key::CtrlRight => write!(f, "<Ctrl+\u{2192}>"),
+            Key::ShiftLeft => write!(f, "<Shift+\u{2190}>"),
+            Key::ShiftRight => write!(f, "<Shift+\u{2192}>"),
+            Key::ShiftUp => write!(f, "<Shift+\u{2191}>"),
+            Key::ShiftDown => write!(f, "<Shift+\u{2193}>"),
+            Key::ShiftHome => write!(f, "<Shift+Home>"),
+            Key::ShiftEnd => write!(f, "<Shift+End>"),
             Key::Enter
             | Key::Tab
             | Key::Backspace
@@ -130,6 +157,92 @@ impl fmt::Display for Key {
     }
 }
 
+// single_char is the only character of text, with "space" for ' '
+fn single_char(text: &str) -> Option<char> {
+    if text.eq_ignore_ascii_case("space") {
+        return Some(' ');
+    }
+    let mut chars = text.chars();
+    match (chars.next(), chars.next()) {
+        (Some(c), None) => Some(c),
+        _ => None,
+    }
+}
+
+/// Reads a key as written in config.toml: a character like `q`, a named key
+/// like `esc` or `pagedown`, a modifier like `ctrl-d` or `alt-g`, or two
+/// characters separated by a space like `g g`.
+impl FromStr for Key {
+    type Err = String;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let keys: Vec<&str> = text.split_whitespace().collect();
+        match keys.as_slice() {
+            [] if text == " " => return Ok(Key::Char(' ')),
+            [] if text.is_empty() => return Err(String::from("empty key")),
+            [] => return Err(format!("unknown key {text:?}")),
+            [_] => {}
+            [first, second] => {
+                return match (single_char(first), single_char(second)) {
+                    (Some(first), Some(second)) => Ok(Key::Chord(first, second)),
+                    _ => Err(format!("\"{text}\" is not a sequence of two characters")),
+                }
+            }
+            _ => return Err(format!("\"{text}\" has more than two keys")),
+        }
+        let key = keys[0];
+        if let Some(c) = single_char(key) {
+            return Ok(Key::Char(c));
+        }
+        let lower = key.to_lowercase();
+        let (modifier, name) = match lower.split_once(['-', '+']) {
+            Some((modifier @ ("ctrl" | "alt" | "shift"), _)) => {
+                (modifier, &key[modifier.len() + 1..])
+            }
+            _ => ("", key),
+        };
+        let named = match (modifier, name.to_lowercase().as_str()) {
+            ("", "enter" | "return") => Some(Key::Enter),
+            ("", "tab") => Some(Key::Tab),
+            ("", "backtab") | ("shift", "tab") => Some(Key::BackTab),
+            ("", "backspace") => Some(Key::Backspace),
+            ("", "esc" | "escape") => Some(Key::Esc),
+            ("", "left") => Some(Key::Left),
+            ("", "right") => Some(Key::Right),
+            ("", "up") => Some(Key::Up),
+            ("", "down") => Some(Key::Down),
+            ("ctrl", "left") => Some(Key::CtrlLeft),
+            ("ctrl", "right") => Some(Key::CtrlRight),
+            ("shift", "left") => Some(Key::ShiftLeft),
+            ("shift", "right") => Some(Key::ShiftRight),
+            ("shift", "up") => Some(Key::ShiftUp),
+            ("shift", "down") => Some(Key::ShiftDown),
+            ("shift", "home") => Some(Key::ShiftHome),
+            ("shift", "end") => Some(Key::ShiftEnd),
+            ("", "ins" | "insert") => Some(Key::Ins),
+            ("", "del" | "delete") => Some(Key::Delete),
+            ("", "home") => Some(Key::Home),
+            ("", "end") => Some(Key::End),
+            ("", "pageup") => Some(Key::PageUp),
+            ("", "pagedown") => Some(Key::PageDown),
+            ("", f) if f.starts_with('f') => match f[1..].parse::<u8>() {
+                Ok(n) if n <= 12 => Some(Key::from_f(n)),
+                _ => None,
+            },
+            _ => None,
+        };
+        if let Some(named) = named {
+            return Ok(named);
+        }
+        // Terminals report control characters in lowercase
+        match (modifier, single_char(name)) {
+            ("ctrl", Some(c)) => Ok(Key::Ctrl(c.to_ascii_lowercase())),
+            ("alt", Some(c)) => Ok(Key::Alt(c)),
+            _ => Err(format!("unknown key \"{text}\"")),
+        }
+    }
+}
+
 impl From<event::KeyEvent> for Key {
     fn from(key_event: event::KeyEvent) -> Self {
         match key_event {
@@ -141,6 +254,46 @@ impl From<event::KeyEvent> for Key {
                 code: event::KeyCode::Backspace,
                 ..
             } => Key::Backspace,
+            event::KeyEvent {
+                code: event::KeyCode::Left,
+                modifiers: event::KeyModifiers::CONTROL,
+                ..
+            } => Key::CtrlLeft,
+            event::KeyEvent {
+                code: event::KeyCode::Right,
+                modifiers: event::KeyModifiers::CONTROL,
+                ..
+            } => Key::CtrlRight,
+            event::KeyEvent {
+                code: event::KeyCode::Left,
+                modifiers: event::KeyModifiers::SHIFT,
+                ..
+            } => Key::ShiftLeft,
+            event::KeyEvent {
+                code: event::KeyCode::Right,
+                modifiers: event::KeyModifiers::SHIFT,
+                ..
+            } => Key::ShiftRight,
+            event::KeyEvent {
+                code: event::KeyCode::Up,
+                modifiers: event::KeyModifiers::SHIFT,
+                ..
+            } => Key::ShiftUp,
+            event::KeyEvent {
+                code: event::KeyCode::Down,
+                modifiers: event::KeyModifiers::SHIFT,
+                ..
+            } => Key::ShiftDown,
+            event::KeyEvent {
+                code: event::KeyCode::Home,
+                modifiers: event::KeyModifiers::SHIFT,
+                ..
+            } => Key::ShiftHome,
+            event::KeyEvent {
+                code: event::KeyCode::End,
+                modifiers: event::KeyModifiers::SHIFT,
+                ..
+            } => Key::ShiftEnd,
             event::KeyEvent {
                 code: event::KeyCode::Left,
                 ..
@@ -224,3 +377,33 @@ impl From<event::KeyEvent> for Key {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keys_from_config_text() {
+        let key = |text: &str| text.parse::<Key>();
+        assert_eq!(key("q"), Ok(Key::Char('q')));
+        assert_eq!(key("G"), Ok(Key::Char('G')));
+        assert_eq!(key("-"), Ok(Key::Char('-')));
+        assert_eq!(key(" "), Ok(Key::Char(' ')));
+        assert_eq!(key("Esc"), Ok(Key::Esc));
+        assert_eq!(key("shift-tab"), Ok(Key::BackTab));
+        assert_eq!(key("ctrl-D"), Ok(Key::Ctrl('d')));
+        assert_eq!(key("ctrl+left"), Ok(Key::CtrlLeft));
+        assert_eq!(key("alt-G"), Ok(Key::Alt('G')));
+        assert_eq!(key("f5"), Ok(Key::F5));
+        assert_eq!(key("g g"), Ok(Key::Chord('g', 'g')));
+        assert_eq!(key("space x"), Ok(Key::Chord(' ', 'x')));
+        assert_eq!(Key::Chord('g', 'g').to_string(), "g g");
+        assert!(key("ctrl-g g").is_err());
+        assert!(key("g g g").is_err());
+        assert!(key("hyper-x").is_err());
+        assert!(key("f13").is_err());
+        assert!(key("\t").is_err());
+        assert!(key("  ").is_err());
+        assert!(key("").is_err());
+    }
+}
@@ -0,0 +1,98 @@
+use crossterm::event::{MouseButton, MouseEvent, MouseEventKind};
+use std::time::{Duration, Instant};
+
+// A second click on the same cell within DOUBLE_CLICK is a double click
+const DOUBLE_CLICK: Duration = Duration::from_millis(400);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MouseAction {
+    Click,
+    DoubleClick,
+    ScrollUp,
+    ScrollDown,
+}
+
+/// Represents a mouse action at a terminal cell.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Mouse {
+    pub action: MouseAction,
+    pub column: u16,
+    pub row: u16,
+}
+
+// Clicks turns the terminal mouse events into Mouse, remembering the last
+// click to tell double clicks apart
+#[derive(Debug, Default)]
+pub struct Clicks {
+    last: Option<(Instant, u16, u16)>,
+}
+
+impl Clicks {
+    pub fn mouse(&mut self, event: MouseEvent, now: Instant) -> Option<Mouse> {
+        let action = match event.kind {
+            MouseEventKind::Down(MouseButton::Left) => match self.last.take() {
+                Some((at, column, row))
+                    if (column, row) == (event.column, event.row)
+                        && now.duration_since(at) <= DOUBLE_CLICK =>
+                {
+                    MouseAction::DoubleClick
+                }
+                _ => {
+                    self.last = Some((now, event.column, event.row));
+                    MouseAction::Click
+                }
+            },
+            MouseEventKind::ScrollUp => MouseAction::ScrollUp,
+            MouseEventKind::ScrollDown => MouseAction::ScrollDown,
+            _ => return None,
+        };
+        Some(Mouse {
+            action,
+            column: event.column,
+            row: event.row,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::KeyModifiers;
+
+    fn click(column: u16, row: u16) -> MouseEvent {
+        MouseEvent {
+            kind: MouseEventKind::Down(MouseButton::Left),
+            column,
+            row,
+            modifiers: KeyModifiers::NONE,
+        }
+    }
+
+    #[test]
+    fn double_click_on_the_same_cell() {
+        let mut clicks = Clicks::default();
+        let start = Instant::now();
+        let action = |mouse: Option<Mouse>| mouse.map(|m| m.action);
+        assert_eq!(
+            action(clicks.mouse(click(3, 4), start)),
+            Some(MouseAction::Click)
+        );
+        assert_eq!(
+            action(clicks.mouse(click(3, 4), start + Duration::from_millis(200))),
+            Some(MouseAction::DoubleClick)
+        );
+        // A third click starts over, as does a click elsewhere or too late
+        assert_eq!(
+            action(clicks.mouse(click(3, 4), start + Duration::from_millis(300))),
+            Some(MouseAction::Click)
+        );
+        assert_eq!(
+            action(clicks.mouse(click(5, 4), start + Duration::from_millis(400))),
+            Some(MouseAction::Click)
+        );
+        assert_eq!(
+            action(clicks.mouse(click(5, 4), start + Duration::from_secs(1))),
+            Some(MouseAction::Click)
+        );
+    }
+}
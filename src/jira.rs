@@ -1,4 +1,4 @@
-use anyhow::anyhow;
+use futures::stream::{self, StreamExt};
 use log::debug;
 use serde::Deserialize;
 use serde::Serialize;
@@ -11,7 +11,17 @@ pub type SurrealAny = Surreal<Any>;
 use crate::config::JiraConfigProjects;
 use crate::config::JiraConfigTickets;
 use crate::jira::projects::ProjectRecord;
+use std::collections::BTreeMap;
 
+use self::agile::{Board, JiraAgileAPI, Sprint};
+use self::bulk::BULK_CONCURRENCY;
+use self::createmeta::JiraCreateMetaAPI;
+use self::epics::JiraEpicsAPI;
+use self::fields::{CustomField, JiraFieldsAPI};
+use self::filters::{Filter, FilterTicketsPage, JiraFiltersAPI};
+use self::jql::{Jql, JqlHistory, JqlQuery, SortOrder};
+use self::my_work::JiraMyWorkAPI;
+use self::notifications::Notification;
 use self::projects::Project;
 use self::tickets::TicketData;
 use self::{
@@ -20,10 +30,85 @@ use self::{
     tickets::JiraTicketsAPI,
 };
 
+pub mod agile;
 pub mod auth;
+pub mod bulk;
+pub mod changelog;
+pub mod clone;
+pub mod createmeta;
+pub mod epics;
+pub mod fields;
+pub mod filters;
+pub mod jql;
+pub mod my_work;
+pub mod notifications;
 pub mod projects;
 pub mod tickets;
 
+const TICKET_FIELDS: &str = "assignee,comments,components,created,creator,description,issuelinks,issuetype,labels,parent,priority,project,reporter,status,summary,updated";
+
+// keep_custom_fields drops the fields that are not configured under
+// [custom_fields], since the flattened custom_fields map catches every field
+// Jira sends back
+fn keep_custom_fields(custom_fields: &[CustomField], tickets: &mut [TicketData]) {
+    for ticket in tickets {
+        ticket.fields.custom_fields.retain(|field_id, _| {
+            custom_fields
+                .iter()
+                .any(|custom_field| custom_field.field_id == *field_id)
+        });
+    }
+}
+
+// TicketSource is where the ticket list currently shown comes from
+#[derive(Debug, Clone, PartialEq)]
+pub enum TicketSource {
+    Project,
+    Sprint(Sprint),
+    Backlog(Board),
+    Epic(String),
+    Filter(Filter),
+    Jql(String),
+}
+
+impl TicketSource {
+    fn agile_path(&self) -> Option<String> {
+        match self {
+            Self::Project => None,
+            Self::Sprint(sprint) => Some(format!("sprint/{}/issue", sprint.id)),
+            Self::Backlog(board) => Some(format!("board/{}/backlog", board.id)),
+            Self::Epic(_) | Self::Filter(_) | Self::Jql(_) => None,
+        }
+    }
+
+    // rank_order sorts sprint and backlog lists by the board rank
+    fn rank_order() -> String {
+        JqlQuery::default()
+            .order_by("Rank", SortOrder::Asc)
+            .to_string()
+    }
+
+    // jql returns the search behind the list for sources that are not agile endpoints
+    fn jql(&self) -> Option<String> {
+        match self {
+            Self::Epic(epic_key) => Some(
+                JqlQuery::new(Jql::equals("parent", epic_key))
+                    .order_by("status", SortOrder::Asc)
+                    .order_by("key", SortOrder::Asc)
+                    .to_string(),
+            ),
+            Self::Filter(filter) => Some(filter.jql.clone()),
+            Self::Jql(jql) => Some(jql.clone()),
+            _ => None,
+        }
+    }
+
+    // is_ranked reports whether the list follows the board rank
+    pub fn is_ranked(&self) -> bool {
+        matches!(self, Self::Sprint(_) | Self::Backlog(_))
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct DBTicketData {
     pub id: String,
@@ -32,14 +117,23 @@ pub struct DBTicketData {
 }
 
 pub struct Jira {
+    pub agile_api: JiraAgileAPI,
     pub client: JiraClient,
+    pub createmeta_api: JiraCreateMetaAPI,
+    pub custom_fields: Vec<CustomField>,
     pub db: SurrealAny,
+    pub epics_api: JiraEpicsAPI,
+    pub fields_api: JiraFieldsAPI,
+    pub filters_api: JiraFiltersAPI,
+    pub my_work_api: JiraMyWorkAPI,
     pub projects_api: JiraProjectsAPI,
     pub project_start_at: u32,
     pub project_max_results: u32,
     pub tickets_start_at: u32,
     pub tickets_max_results: u32,
     pub tickets_api: JiraTicketsAPI,
+    // ticket_fields are the fields requested for ticket lists, custom fields included
+    pub ticket_fields: String,
     pub user_config_projects: Option<JiraConfigProjects>,
     pub user_config_tickets: Option<JiraConfigTickets>,
 }
@@ -64,19 +158,48 @@ impl Jira {
         db.use_ns("noc").use_db("database").await?;
 
         Ok(Self {
+            agile_api: JiraAgileAPI::new(),
             client: auth.await,
+            createmeta_api: JiraCreateMetaAPI::new(),
+            custom_fields: vec![],
             db,
+            epics_api: JiraEpicsAPI::new(),
+            fields_api: JiraFieldsAPI::new(),
+            filters_api: JiraFiltersAPI::new(),
+            my_work_api: JiraMyWorkAPI::new(),
             projects_api: projects,
             project_start_at: 0,
             project_max_results: 50,
             tickets_start_at: 0,
             tickets_max_results: 50,
             tickets_api: tickets,
+            ticket_fields: TICKET_FIELDS.to_string(),
             user_config_projects: user_config_project.clone(),
             user_config_tickets: user_config_tickets.clone(),
         })
     }
 
+    // load_custom_fields resolves the [custom_fields] config against the field
+    // metadata and adds them to the requested ticket fields. The names of the
+    // fields that do not exist are returned.
+    pub async fn load_custom_fields(
+        &mut self,
+        configured: &BTreeMap<String, String>,
+    ) -> anyhow::Result<Vec<String>> {
+        if configured.is_empty() {
+            return Ok(vec![]);
+        }
+        self.fields_api.get_fields(&self.client, &self.db).await?;
+        let (custom_fields, unknown) = self.fields_api.resolve(configured);
+        self.ticket_fields = custom_fields
+            .iter()
+            .fold(TICKET_FIELDS.to_string(), |fields, custom_field| {
+                format!("{fields},{}", custom_field.field_id)
+            });
+        self.custom_fields = custom_fields;
+        Ok(unknown)
+    }
+
     pub async fn clear_projects_table(&mut self) -> anyhow::Result<()> {
         let _delete_projects: Vec<Project> = self.db.delete("projects").await?;
         Ok(())
@@ -181,6 +304,7 @@ impl Jira {
 
     pub async fn clear_tickets_table(&mut self) -> anyhow::Result<()> {
         let _delete_projects: Vec<TicketData> = self.db.delete("tickets").await?;
+        let _delete_filters: Vec<FilterTicketsPage> = self.db.delete("filter_tickets").await?;
         Ok(())
     }
 
@@ -193,44 +317,15 @@ impl Jira {
         let url = format!("{}/search", jira_url);
         let max_results = self.tickets_max_results.to_string();
         let start_at = self.tickets_start_at.to_string();
-        let mut jql = format!("project = {}", project_key);
-        if self.user_config_tickets.is_some() {
-            let config_tickets = self.user_config_tickets.clone().unwrap();
-            if let Some(current_user_tickets) = config_tickets.current_user_tickets_only {
-                if current_user_tickets {
-                    jql = format!("{jql} AND assignee = currentUser()")
-                }
-                debug!("{jql}");
-            }
-            if config_tickets.current_sprint_tickets_only == Some(true) {
-                jql = format!("{jql} AND sprint IN openSprints()")
-            }
-            debug!("{jql}");
-
-            let mut ticket_status: Vec<String> = vec![];
-            if let Some(specified_ticket_status) = config_tickets.show_ticket_status {
-                ticket_status = specified_ticket_status
-            }
-            if !ticket_status.is_empty() {
-                for (index, status) in ticket_status.iter().enumerate() {
-                    if index == 0 {
-                        jql = format!("{jql} AND (Status = \"{status}\"");
-                        debug!("{jql}");
-                    } else {
-                        jql = format!("{jql} OR Status = \"{status}\"");
-                        debug!("{jql}");
-                    }
-                }
-                jql += ")";
-                debug!("{jql}");
-            }
-        }
+        let config_tickets = self.user_config_tickets.clone().unwrap_or_default();
+        let jql = JqlQuery::for_project_tickets(project_key, &config_tickets).to_string();
+        debug!("{jql}");
         let params = vec![
             ("maxResults", max_results.as_ref()),
             ("jql", jql.as_ref()),
             ("expand", "renderedFields"),
             ("startAt", start_at.as_ref()),
-            ("fields", "assignee,comments,components,creator,description,issuelinks,issuetype,labels,parent,priority,project,reporter,status,summary")
+            ("fields", self.ticket_fields.as_str()),
         ];
         debug!("JQL {:?}", params);
         let resp = self
@@ -239,6 +334,7 @@ impl Jira {
             .await?;
         debug!("{resp}");
         self.tickets_api = serde_json::from_str(resp.as_str()).expect("tickets deserialized");
+        keep_custom_fields(&self.custom_fields, &mut self.tickets_api.issues);
         for ticket in self.tickets_api.issues.clone() {
             let tickets_insert: TicketData = self
                 .db
@@ -252,6 +348,149 @@ impl Jira {
         Ok(self.tickets_api.issues.clone())
     }
 
+    // get_source_tickets retrieves the current page of a sprint, board backlog,
+    // epic or JQL search and records the tickets in the cache so they can be searched later
+    pub async fn get_source_tickets(
+        &mut self,
+        source: &TicketSource,
+    ) -> anyhow::Result<Vec<TicketData>, anyhow::Error> {
+        if let TicketSource::Filter(filter) = source {
+            return self.get_filter_tickets(filter).await;
+        }
+        let resp = match (source.agile_path(), source.jql()) {
+            (Some(path), _) => {
+                let start_at = self.tickets_start_at.to_string();
+                let max_results = self.tickets_max_results.to_string();
+                let jql = TicketSource::rank_order();
+                let params = [
+                    ("jql", jql.as_str()),
+                    ("expand", "renderedFields"),
+                    ("fields", self.ticket_fields.as_str()),
+                    ("startAt", start_at.as_str()),
+                    ("maxResults", max_results.as_str()),
+                ];
+                debug!("Retrieve agile tickets {path}");
+                self.client
+                    .get_from_agile_api_with_query(&path, &params)
+                    .await?
+            }
+            (None, Some(jql)) => self.search_tickets_page(&jql).await?,
+            (None, None) => return Ok(self.tickets_api.issues.clone()),
+        };
+        self.tickets_api = serde_json::from_str(resp.as_str())?;
+        keep_custom_fields(&self.custom_fields, &mut self.tickets_api.issues);
+        for ticket in self.tickets_api.issues.clone() {
+            let _tickets_insert: Option<TicketData> = self
+                .db
+                .update(("tickets", &ticket.key))
+                .content(ticket)
+                .await?;
+        }
+
+        Ok(self.tickets_api.issues.clone())
+    }
+
+    async fn search_tickets_page(&self, jql: &str) -> anyhow::Result<String> {
+        let start_at = self.tickets_start_at.to_string();
+        let max_results = self.tickets_max_results.to_string();
+        let params = [
+            ("jql", jql),
+            ("expand", "renderedFields"),
+            ("fields", self.ticket_fields.as_str()),
+            ("startAt", start_at.as_str()),
+            ("maxResults", max_results.as_str()),
+        ];
+        debug!("Retrieve tickets {jql}");
+        self.client.search_jira_api(&params).await
+    }
+
+    // get_filter_tickets pages through the results of a saved filter. Pages are
+    // always fetched and then cached in their own table, keyed by filter and
+    // page, so filter results never mix with the per-project tickets cache.
+    // The cached page is only used when the fetch fails.
+    pub async fn get_filter_tickets(
+        &mut self,
+        filter: &Filter,
+    ) -> anyhow::Result<Vec<TicketData>, anyhow::Error> {
+        let page_key = format!("{}_{}", filter.id, self.tickets_start_at);
+        let resp = match self.search_tickets_page(&filter.jql).await {
+            Ok(resp) => resp,
+            Err(e) => {
+                let cached: Option<FilterTicketsPage> =
+                    self.db.select(("filter_tickets", &page_key)).await?;
+                let Some(page) = cached else {
+                    return Err(e);
+                };
+                debug!("Filter {} page {page_key} from cache: {e}", filter.name);
+                self.tickets_api.issues = page.tickets;
+                self.tickets_api.total = page.total;
+                return Ok(self.tickets_api.issues.clone());
+            }
+        };
+        self.tickets_api = serde_json::from_str(resp.as_str())?;
+        keep_custom_fields(&self.custom_fields, &mut self.tickets_api.issues);
+        let page = FilterTicketsPage {
+            filter_id: filter.id.clone(),
+            start_at: self.tickets_start_at,
+            tickets: self.tickets_api.issues.clone(),
+            total: self.tickets_api.total,
+        };
+        let _page_insert: Option<FilterTicketsPage> = self
+            .db
+            .update(("filter_tickets", &page_key))
+            .content(page)
+            .await?;
+        Ok(self.tickets_api.issues.clone())
+    }
+
+    // get_agile_ticket_at returns the key of the ticket at the given rank of a
+    // sprint or board backlog, which may not be on the current page
+    pub async fn get_agile_ticket_at(
+        &self,
+        source: &TicketSource,
+        rank: u32,
+    ) -> anyhow::Result<Option<String>> {
+        let Some(path) = source.agile_path() else {
+            return Ok(None);
+        };
+        let start_at = rank.to_string();
+        let jql = TicketSource::rank_order();
+        let params = [
+            ("jql", jql.as_str()),
+            ("fields", "summary"),
+            ("startAt", start_at.as_str()),
+            ("maxResults", "1"),
+        ];
+        let resp = self
+            .client
+            .get_from_agile_api_with_query(&path, &params)
+            .await?;
+        let page: serde_json::Value = serde_json::from_str(&resp)?;
+        let key = page["issues"][0]["key"].as_str().map(String::from);
+        Ok(key)
+    }
+
+    pub async fn get_next_source_page(
+        &mut self,
+        source: &TicketSource,
+    ) -> anyhow::Result<Vec<TicketData>, anyhow::Error> {
+        if self.tickets_start_at + self.tickets_max_results >= self.tickets_api.total {
+            return Ok(self.tickets_api.issues.clone());
+        }
+        self.tickets_start_at += self.tickets_max_results;
+        self.get_source_tickets(source).await
+    }
+
+    pub async fn get_previous_source_page(
+        &mut self,
+        source: &TicketSource,
+    ) -> anyhow::Result<Vec<TicketData>, anyhow::Error> {
+        self.tickets_start_at = self
+            .tickets_start_at
+            .saturating_sub(self.tickets_max_results);
+        self.get_source_tickets(source).await
+    }
+
     pub async fn get_next_ticket_page(
         &mut self,
         project_key: &str,
@@ -306,6 +545,79 @@ impl Jira {
         Ok(self.tickets_api.issues.clone())
     }
 
+    // get_cached_project_tickets returns every ticket of the project found in the cache
+    pub async fn get_cached_project_tickets(
+        &mut self,
+        project_key: &str,
+    ) -> anyhow::Result<Vec<TicketData>, anyhow::Error> {
+        let mut query = self
+            .db
+            .query("SELECT * FROM tickets WHERE fields.project.key = type::string($project_key)")
+            .bind(("project_key", project_key))
+            .await?;
+        let tickets: Vec<TicketData> = query.take(0)?;
+        Ok(tickets)
+    }
+
+    // get_cached_tickets returns every ticket found in the cache, whatever its project
+    pub async fn get_cached_tickets(&self) -> anyhow::Result<Vec<TicketData>> {
+        let mut query = self.db.query("SELECT * FROM tickets").await?;
+        let tickets: Vec<TicketData> = query.take(0)?;
+        Ok(tickets)
+    }
+
+    pub async fn record_jql_history(&self, jql: &str) -> anyhow::Result<()> {
+        let history = JqlHistory {
+            jql: jql.to_string(),
+            last_run: chrono::Utc::now().timestamp(),
+        };
+        let _history: Option<JqlHistory> = self
+            .db
+            .update(("jql_history", jql))
+            .content(history)
+            .await?;
+        Ok(())
+    }
+
+    // get_jql_history returns the most recently run queries first
+    pub async fn get_jql_history(&self) -> anyhow::Result<Vec<String>> {
+        let mut query = self
+            .db
+            .query("SELECT * FROM jql_history ORDER BY last_run DESC LIMIT 50")
+            .await?;
+        let history: Vec<JqlHistory> = query.take(0)?;
+        Ok(history.into_iter().map(|h| h.jql).collect())
+    }
+
+    // get_notifications returns the most recent notifications first
+    pub async fn get_notifications(&self) -> anyhow::Result<Vec<Notification>> {
+        let mut query = self
+            .db
+            .query("SELECT * FROM notifications ORDER BY created DESC LIMIT 200")
+            .await?;
+        let notifications: Vec<Notification> = query.take(0)?;
+        Ok(notifications)
+    }
+
+    // mark_notifications_read marks the notifications of a ticket as read, or
+    // every notification when no ticket is given
+    pub async fn mark_notifications_read(&self, ticket_key: Option<&str>) -> anyhow::Result<()> {
+        match ticket_key {
+            Some(ticket_key) => {
+                self.db
+                    .query("UPDATE notifications SET read = true WHERE ticket_key = type::string($ticket_key)")
+                    .bind(("ticket_key", ticket_key))
+                    .await?;
+            }
+            None => {
+                self.db
+                    .query("UPDATE notifications SET read = true")
+                    .await?;
+            }
+        }
+        Ok(())
+    }
+
     pub async fn search_cache_ticket(
         &mut self,
         ticket_key: &str,
@@ -324,14 +636,15 @@ impl Jira {
     }
 
     pub async fn jira_ticket_api(
-        &mut self,
+        &self,
         ticket_key: &str,
     ) -> anyhow::Result<TicketData, anyhow::Error> {
         debug!("Retrieve {ticket_key}");
-        let ticket = self
+        let mut ticket = self
             .tickets_api
             .search_tickets_api(ticket_key, &self.client)
             .await?;
+        keep_custom_fields(&self.custom_fields, std::slice::from_mut(&mut ticket));
         let update_ticket_record: TicketData = self
             .db
             .update(("tickets", ticket_key))
@@ -344,6 +657,19 @@ impl Jira {
         Ok(update_ticket_record)
     }
 
+    // refresh_tickets reloads tickets changed by a bulk operation, as many at
+    // a time as the operation itself
+    pub async fn refresh_tickets(&self, ticket_keys: &[String]) -> anyhow::Result<()> {
+        let refreshed: Vec<anyhow::Result<TicketData>> = stream::iter(ticket_keys)
+            .map(|ticket_key| self.jira_ticket_api(ticket_key))
+            .buffer_unordered(BULK_CONCURRENCY)
+            .collect()
+            .await;
+        refreshed
+            .into_iter()
+            .try_for_each(|ticket| ticket.map(|_| ()))
+    }
+
     pub async fn search_cache_projects(
         &mut self,
         project_key: &str,
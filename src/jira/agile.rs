@@ -0,0 +1,217 @@
+use super::auth::JiraClient;
+use chrono::DateTime;
+use log::debug;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+// MOVE_MAX_TICKETS is the most tickets Jira moves to a sprint or the backlog
+// in one request
+pub const MOVE_MAX_TICKETS: usize = 50;
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Board {
+    pub id: u64,
+    pub name: String,
+    #[serde(alias = "type")]
+    pub board_type: String,
+}
+
+impl Board {
+    pub fn supports_sprints(&self) -> bool {
+        self.board_type == "scrum"
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Sprint {
+    pub id: u64,
+    pub name: String,
+    pub state: String,
+    pub goal: Option<String>,
+    pub start_date: Option<String>,
+    pub end_date: Option<String>,
+    pub complete_date: Option<String>,
+    pub origin_board_id: Option<u64>,
+}
+
+impl Sprint {
+    pub fn is_closed(&self) -> bool {
+        self.state == "closed"
+    }
+
+    // date_range returns the sprint dates formatted for display
+    pub fn date_range(&self) -> String {
+        let format_date = |date: &Option<String>| match date {
+            Some(d) => match DateTime::parse_from_rfc3339(d) {
+                Ok(d) => d.format("%Y-%m-%d").to_string(),
+                Err(_) => d.clone(),
+            },
+            None => String::from("?"),
+        };
+        format!(
+            "{} -> {}",
+            format_date(&self.start_date),
+            format_date(&self.end_date)
+        )
+    }
+}
+
+// Rank places tickets right before or right after another ticket
+#[derive(Debug, Clone, PartialEq)]
+pub enum Rank {
+    Before(String),
+    After(String),
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct BoardColumnStatus {
+    pub id: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct BoardColumn {
+    pub name: String,
+    pub statuses: Vec<BoardColumnStatus>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+struct BoardColumnConfig {
+    columns: Vec<BoardColumn>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+struct BoardConfiguration {
+    column_config: BoardColumnConfig,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+struct AgilePage<T> {
+    is_last: bool,
+    max_results: u32,
+    start_at: u32,
+    values: Vec<T>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Default, Clone)]
+pub struct JiraAgileAPI {
+    pub boards: Vec<Board>,
+    pub sprints: Vec<Sprint>,
+}
+
+impl JiraAgileAPI {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // get_boards pulls every board that contains the project
+    pub async fn get_boards(
+        &mut self,
+        jira_client: &JiraClient,
+        project_key: &str,
+    ) -> anyhow::Result<&Vec<Board>> {
+        self.boards.clear();
+        let mut start_at = 0;
+        loop {
+            let url = format!("board?projectKeyOrId={project_key}&startAt={start_at}");
+            let response = jira_client.get_from_agile_api(&url).await?;
+            let mut page: AgilePage<Board> = serde_json::from_str(&response)?;
+            debug!("Boards page {} for {project_key}", page.start_at);
+            start_at = page.start_at + page.values.len() as u32;
+            let empty_page = page.values.is_empty();
+            self.boards.append(&mut page.values);
+            if page.is_last || empty_page {
+                break;
+            }
+        }
+        Ok(&self.boards)
+    }
+
+    // get_sprints pulls active, future and closed sprints of a board, newest first
+    pub async fn get_sprints(
+        &mut self,
+        jira_client: &JiraClient,
+        board: &Board,
+    ) -> anyhow::Result<&Vec<Sprint>> {
+        self.sprints.clear();
+        if !board.supports_sprints() {
+            return Ok(&self.sprints);
+        }
+        let mut start_at = 0;
+        loop {
+            let url = format!(
+                "board/{}/sprint?state=active,future,closed&startAt={start_at}",
+                board.id
+            );
+            let response = jira_client.get_from_agile_api(&url).await?;
+            let mut page: AgilePage<Sprint> = serde_json::from_str(&response)?;
+            debug!("Sprints page {} for board {}", page.start_at, board.id);
+            start_at = page.start_at + page.values.len() as u32;
+            let empty_page = page.values.is_empty();
+            self.sprints.append(&mut page.values);
+            if page.is_last || empty_page {
+                break;
+            }
+        }
+        self.sprints.reverse();
+        Ok(&self.sprints)
+    }
+
+    // get_board_columns returns the board columns in the order they are shown in Jira
+    pub async fn get_board_columns(
+        &self,
+        jira_client: &JiraClient,
+        board: &Board,
+    ) -> anyhow::Result<Vec<BoardColumn>> {
+        let url = format!("board/{}/configuration", board.id);
+        let response = jira_client.get_from_agile_api(&url).await?;
+        let configuration: BoardConfiguration = serde_json::from_str(&response)?;
+        debug!(
+            "Board {} columns {:?}",
+            board.id, configuration.column_config.columns
+        );
+        Ok(configuration.column_config.columns)
+    }
+
+    pub async fn move_to_sprint(
+        &self,
+        jira_client: &JiraClient,
+        sprint_id: u64,
+        ticket_keys: &[String],
+    ) -> anyhow::Result<()> {
+        let url = format!("sprint/{sprint_id}/issue");
+        let data = json!({ "issues": ticket_keys }).to_string();
+        jira_client.post_to_agile_api(&url, data).await?;
+        Ok(())
+    }
+
+    pub async fn rank_tickets(
+        &self,
+        jira_client: &JiraClient,
+        ticket_keys: &[String],
+        rank: &Rank,
+    ) -> anyhow::Result<()> {
+        let data = match rank {
+            Rank::Before(key) => json!({ "issues": ticket_keys, "rankBeforeIssue": key }),
+            Rank::After(key) => json!({ "issues": ticket_keys, "rankAfterIssue": key }),
+        };
+        debug!("Rank {ticket_keys:?} {rank:?}");
+        jira_client
+            .put_to_agile_api("issue/rank", data.to_string())
+            .await?;
+        Ok(())
+    }
+
+    pub async fn move_to_backlog(
+        &self,
+        jira_client: &JiraClient,
+        ticket_keys: &[String],
+    ) -> anyhow::Result<()> {
+        let data = json!({ "issues": ticket_keys }).to_string();
+        jira_client.post_to_agile_api("backlog/issue", data).await?;
+        Ok(())
+    }
+}
@@ -4,7 +4,7 @@ use log::debug;
 use reqwest::header::{HeaderMap, HeaderValue, ACCEPT, AUTHORIZATION, CONTENT_TYPE};
 use serde::{Deserialize, Serialize};
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub struct UserData {
     pub account_id: String,
@@ -13,6 +13,7 @@ pub struct UserData {
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct JiraClient {
+    pub agile_url: String,
     pub api_key: String,
     pub api_version: String,
     pub email: String,
@@ -34,22 +35,28 @@ impl JiraClient {
         headers
     }
 
+    // client sends every request with the basic auth headers, over https only
+    fn client(&self) -> anyhow::Result<reqwest::Client> {
+        let client = reqwest::Client::builder()
+            .default_headers(self.get_basic_auth())
+            .https_only(true)
+            .build()?;
+        Ok(client)
+    }
+
     pub fn get_domain(&self) -> &String {
         &self.url
     }
 
     pub async fn post_to_jira_api(&self, api_url: &str, data: String) -> anyhow::Result<String> {
-        let headers = self.get_basic_auth();
         let api_url = format!("{}/{}", self.get_domain(), api_url);
-        let client = reqwest::Client::builder()
-            .default_headers(headers)
-            .https_only(true)
-            .build()?;
+        let client = self.client()?;
         let response = client
             .post(api_url.clone())
             .body(data.clone())
             .send()
             .await?
+            .error_for_status()?
             .text()
             .await?;
         debug!("client {:#?}", client);
@@ -58,25 +65,141 @@ impl JiraClient {
         Ok(response)
     }
 
-    pub async fn get_from_jira_api(&self, api_url: &str) -> anyhow::Result<String> {
-        let headers = self.get_basic_auth();
+    // post_to_jira_api_with_status keeps the body of rejected requests, Jira
+    // explains there what was wrong with the data
+    pub async fn post_to_jira_api_with_status(
+        &self,
+        api_url: &str,
+        data: String,
+    ) -> anyhow::Result<(reqwest::StatusCode, String)> {
         let api_url = format!("{}/{}", self.get_domain(), api_url);
+        let client = self.client()?;
+        let response = client.post(api_url.clone()).body(data).send().await?;
+        let status = response.status();
+        let body = response.text().await?;
+        debug!("api url {}", api_url);
+        debug!("api response {} {} ", status, body);
+        Ok((status, body))
+    }
 
-        let client = reqwest::Client::builder()
-            .default_headers(headers)
-            .https_only(true)
-            .build()?;
+    pub async fn put_to_jira_api(&self, api_url: &str, data: String) -> anyhow::Result<String> {
+        let api_url = format!("{}/{}", self.get_domain(), api_url);
+        let client = self.client()?;
+        let response = client
+            .put(api_url.clone())
+            .body(data)
+            .send()
+            .await?
+            .error_for_status()?
+            .text()
+            .await?;
+        debug!("api url {}", api_url);
+        debug!("api response {} ", response);
+        Ok(response)
+    }
+
+    pub async fn get_from_jira_api(&self, api_url: &str) -> anyhow::Result<String> {
+        let api_url = format!("{}/{}", self.get_domain(), api_url);
+        let client = self.client()?;
         let response = client.get(api_url).send().await?.text().await?;
         Ok(response)
     }
 
+    // get_from_jira_api_with_query sends the parameters as an encoded query string
+    pub async fn get_from_jira_api_with_query(
+        &self,
+        api_url: &str,
+        params: &[(&str, &str)],
+    ) -> anyhow::Result<String> {
+        let api_url = format!("{}/{}", self.get_domain(), api_url);
+        let client = self.client()?;
+        let response = client
+            .get(api_url)
+            .query(params)
+            .send()
+            .await?
+            .error_for_status()?
+            .text()
+            .await?;
+        Ok(response)
+    }
+
+    pub async fn search_jira_api(&self, params: &[(&str, &str)]) -> anyhow::Result<String> {
+        self.get_from_jira_api_with_query("search", params).await
+    }
+
+    pub async fn get_from_agile_api(&self, api_url: &str) -> anyhow::Result<String> {
+        let api_url = format!("{}/{}", self.agile_url, api_url);
+        let client = self.client()?;
+        let response = client
+            .get(api_url)
+            .send()
+            .await?
+            .error_for_status()?
+            .text()
+            .await?;
+        Ok(response)
+    }
+
+    pub async fn get_from_agile_api_with_query(
+        &self,
+        api_url: &str,
+        params: &[(&str, &str)],
+    ) -> anyhow::Result<String> {
+        let api_url = format!("{}/{}", self.agile_url, api_url);
+        let client = self.client()?;
+        let response = client
+            .get(api_url)
+            .query(params)
+            .send()
+            .await?
+            .error_for_status()?
+            .text()
+            .await?;
+        Ok(response)
+    }
+
+    pub async fn post_to_agile_api(&self, api_url: &str, data: String) -> anyhow::Result<String> {
+        let api_url = format!("{}/{}", self.agile_url, api_url);
+        let client = self.client()?;
+        let response = client
+            .post(api_url.clone())
+            .body(data)
+            .send()
+            .await?
+            .error_for_status()?
+            .text()
+            .await?;
+        debug!("api url {}", api_url);
+        debug!("api response {} ", response);
+        Ok(response)
+    }
+
+    pub async fn put_to_agile_api(&self, api_url: &str, data: String) -> anyhow::Result<String> {
+        let api_url = format!("{}/{}", self.agile_url, api_url);
+        let client = self.client()?;
+        let response = client
+            .put(api_url.clone())
+            .body(data)
+            .send()
+            .await?
+            .error_for_status()?
+            .text()
+            .await?;
+        debug!("api url {}", api_url);
+        debug!("api response {} ", response);
+        Ok(response)
+    }
+
     pub async fn new(
         jira_api_version: String,
         jira_api_key: String,
         jira_email: String,
         jira_url: String,
+        jira_agile_url: String,
     ) -> Self {
         let mut client = JiraClient {
+            agile_url: jira_agile_url,
             api_key: jira_api_key,
             api_version: jira_api_version,
             email: jira_email,
@@ -117,11 +240,13 @@ pub async fn jira_authentication(
     let jira_encoded_auth: String =
         general_purpose::URL_SAFE.encode(format!("{jira_user_email}:{jira_api_key}"));
     let jira_rest_domain = jira_domain.to_string() + "/rest/api/" + jira_api_version;
+    let jira_agile_domain = jira_domain.to_string() + "/rest/agile/1.0";
     JiraClient::new(
         jira_api_version.to_string(),
         jira_encoded_auth,
         jira_user_email.to_string(),
         jira_rest_domain,
+        jira_agile_domain,
     )
     .await
 }
@@ -0,0 +1,228 @@
+use super::auth::{JiraClient, UserData};
+use super::tickets::{comment_body, TicketTransitions};
+use futures::stream::{self, StreamExt};
+use log::debug;
+use serde_json::json;
+use std::collections::BTreeMap;
+use std::fmt;
+use tokio::sync::mpsc::UnboundedSender;
+
+// BULK_CONCURRENCY is the number of requests sent to Jira at the same time
+pub const BULK_CONCURRENCY: usize = 5;
+
+// CommonTransition is a status every selected ticket can be moved to, each
+// ticket has its own transition id for it
+#[derive(Debug, Clone, PartialEq)]
+pub struct CommonTransition {
+    pub status: String,
+    pub transition_ids: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum BulkOperation {
+    Transition(CommonTransition),
+    AddLabel(String),
+    RemoveLabel(String),
+    Assign(Option<UserData>),
+    Comment(String),
+}
+
+impl fmt::Display for BulkOperation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Transition(transition) => write!(f, "Transition to {}", transition.status),
+            Self::AddLabel(label) => write!(f, "Add label {label}"),
+            Self::RemoveLabel(label) => write!(f, "Remove label {label}"),
+            Self::Assign(Some(user)) => write!(f, "Assign to {}", user.display_name),
+            Self::Assign(None) => write!(f, "Unassign"),
+            Self::Comment(_) => write!(f, "Add comment"),
+        }
+    }
+}
+
+impl BulkOperation {
+    async fn apply(&self, jira_client: &JiraClient, ticket_key: &str) -> anyhow::Result<()> {
+        match self {
+            Self::Transition(transition) => {
+                let Some(id) = transition.transition_ids.get(ticket_key) else {
+                    anyhow::bail!("No transition to {}", transition.status);
+                };
+                let url = format!("/issue/{ticket_key}/transitions");
+                let data = json!({ "transition": { "id": id } }).to_string();
+                jira_client.post_to_jira_api(&url, data).await?;
+            }
+            Self::AddLabel(label) | Self::RemoveLabel(label) => {
+                let change = match self {
+                    Self::AddLabel(_) => json!({ "add": label }),
+                    _ => json!({ "remove": label }),
+                };
+                let url = format!("/issue/{ticket_key}");
+                let data = json!({ "update": { "labels": [change] } }).to_string();
+                jira_client.put_to_jira_api(&url, data).await?;
+            }
+            Self::Assign(user) => {
+                let url = format!("/issue/{ticket_key}/assignee");
+                let account_id = user.as_ref().map(|u| u.account_id.clone());
+                let data = json!({ "accountId": account_id }).to_string();
+                jira_client.put_to_jira_api(&url, data).await?;
+            }
+            Self::Comment(comment) => {
+                let url = format!("/issue/{ticket_key}/comment");
+                jira_client
+                    .post_to_jira_api(&url, comment_body(comment))
+                    .await?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct BulkResult {
+    pub ticket_key: String,
+    pub outcome: Result<(), String>,
+}
+
+// common_transitions keeps the target statuses offered for every ticket, in
+// the order of the first ticket
+fn common_transitions(tickets: &[(String, TicketTransitions)]) -> Vec<CommonTransition> {
+    let mut common: Vec<CommonTransition> = vec![];
+    for (ticket_key, transitions) in tickets {
+        for transition in &transitions.transitions {
+            let status = match &transition.to {
+                Some(to) => to.name.clone(),
+                None => transition.name.clone().unwrap_or_default(),
+            };
+            match common.iter_mut().find(|c| c.status == status) {
+                Some(c) => {
+                    c.transition_ids
+                        .entry(ticket_key.clone())
+                        .or_insert(transition.id.clone());
+                }
+                None => common.push(CommonTransition {
+                    status,
+                    transition_ids: BTreeMap::from([(ticket_key.clone(), transition.id.clone())]),
+                }),
+            }
+        }
+    }
+    common.retain(|c| c.transition_ids.len() == tickets.len());
+    common
+}
+
+// get_common_transitions asks Jira for the transitions of every ticket
+pub async fn get_common_transitions(
+    jira_client: &JiraClient,
+    ticket_keys: &[String],
+) -> anyhow::Result<Vec<CommonTransition>> {
+    let tickets: Vec<anyhow::Result<(String, TicketTransitions)>> = stream::iter(ticket_keys)
+        .map(|ticket_key| async move {
+            let url = format!("/issue/{ticket_key}/transitions");
+            let response = jira_client.get_from_jira_api(&url).await?;
+            let transitions: TicketTransitions = serde_json::from_str(&response)?;
+            Ok((ticket_key.clone(), transitions))
+        })
+        .buffered(BULK_CONCURRENCY)
+        .collect()
+        .await;
+    let tickets = tickets.into_iter().collect::<anyhow::Result<Vec<_>>>()?;
+    Ok(common_transitions(&tickets))
+}
+
+// find_user looks up the user to assign tickets to, "me" is the current user
+pub async fn find_user(jira_client: &JiraClient, query: &str) -> anyhow::Result<UserData> {
+    if query.eq_ignore_ascii_case("me") {
+        if let Some(user) = &jira_client.user {
+            return Ok(user.clone());
+        }
+    }
+    let response = jira_client
+        .get_from_jira_api_with_query("user/search", &[("query", query)])
+        .await?;
+    let users: Vec<UserData> = serde_json::from_str(&response)?;
+    users
+        .into_iter()
+        .next()
+        .ok_or_else(|| anyhow::anyhow!("No user found for \"{query}\""))
+}
+
+// spawn_bulk applies the operation to every ticket in the background, at most
+// BULK_CONCURRENCY at a time, and sends each outcome as soon as it is known
+pub fn spawn_bulk(
+    jira_client: JiraClient,
+    operation: BulkOperation,
+    ticket_keys: Vec<String>,
+    tx: UnboundedSender<BulkResult>,
+) {
+    tokio::spawn(async move {
+        let jira_client = &jira_client;
+        let operation = &operation;
+        let mut results = stream::iter(ticket_keys)
+            .map(|ticket_key| async move {
+                let outcome = operation
+                    .apply(jira_client, &ticket_key)
+                    .await
+                    .map_err(|e| e.to_string());
+                debug!("Bulk {operation} on {ticket_key}: {outcome:?}");
+                BulkResult {
+                    ticket_key,
+                    outcome,
+                }
+            })
+            .buffer_unordered(BULK_CONCURRENCY);
+        while let Some(result) = results.next().await {
+            if tx.send(result).is_err() {
+                return;
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::jira::tickets::{Status, TicketTransition};
+
+    fn transitions(targets: &[(&str, &str)]) -> TicketTransitions {
+        TicketTransitions {
+            transitions: targets
+                .iter()
+                .map(|(id, status)| TicketTransition {
+                    id: id.to_string(),
+                    name: None,
+                    has_screen: None,
+                    fields: None,
+                    to: Some(Status {
+                        id: None,
+                        name: status.to_string(),
+                        status_category: None,
+                    }),
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn keeps_statuses_every_ticket_can_reach() {
+        let tickets = vec![
+            (
+                "ABC-1".to_string(),
+                transitions(&[("11", "In Progress"), ("31", "Done")]),
+            ),
+            (
+                "ABC-2".to_string(),
+                transitions(&[("41", "Done"), ("51", "Blocked")]),
+            ),
+        ];
+        assert_eq!(
+            common_transitions(&tickets),
+            vec![CommonTransition {
+                status: "Done".to_string(),
+                transition_ids: BTreeMap::from([
+                    ("ABC-1".to_string(), "31".to_string()),
+                    ("ABC-2".to_string(), "41".to_string()),
+                ]),
+            }]
+        );
+    }
+}
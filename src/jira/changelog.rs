@@ -0,0 +1,200 @@
+use super::auth::JiraClient;
+use super::tickets::FieldAuthor;
+use log::debug;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ChangelogItem {
+    pub field: String,
+    // from and to are raw ids for fields such as status or assignee, the
+    // display values are in from_string and to_string
+    pub from: Option<String>,
+    pub from_string: Option<String>,
+    pub to: Option<String>,
+    pub to_string: Option<String>,
+}
+
+impl ChangelogItem {
+    pub fn old_value(&self) -> String {
+        self.from_string
+            .clone()
+            .or(self.from.clone())
+            .unwrap_or_default()
+    }
+
+    pub fn new_value(&self) -> String {
+        self.to_string
+            .clone()
+            .or(self.to.clone())
+            .unwrap_or_default()
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ChangelogHistory {
+    pub id: String,
+    pub author: Option<FieldAuthor>,
+    pub created: String,
+    pub items: Vec<ChangelogItem>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct Changelog {
+    pub histories: Vec<ChangelogHistory>,
+    pub max_results: u32,
+    pub start_at: u32,
+    pub total: u32,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+struct ChangelogPage {
+    is_last: Option<bool>,
+    max_results: u32,
+    start_at: u32,
+    total: u32,
+    values: Vec<ChangelogHistory>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+struct TicketChangelog {
+    changelog: Changelog,
+}
+
+impl Changelog {
+    // from_jira_api reads the first page through expand=changelog and follows
+    // the changelog endpoint until every history entry has been retrieved
+    pub async fn from_jira_api(ticket_key: &str, jira_client: &JiraClient) -> anyhow::Result<Self> {
+        let url = format!("/issue/{}?expand=changelog&fields=summary", ticket_key);
+        let response = jira_client.get_from_jira_api(&url).await?;
+        let TicketChangelog { mut changelog } = serde_json::from_str(&response)?;
+        debug!(
+            "Changelog for {ticket_key}: {} of {} histories",
+            changelog.histories.len(),
+            changelog.total
+        );
+
+        while (changelog.histories.len() as u32) < changelog.total {
+            let url = format!(
+                "/issue/{}/changelog?startAt={}&maxResults={}",
+                ticket_key,
+                changelog.histories.len(),
+                changelog.max_results.max(1)
+            );
+            let response = jira_client.get_from_jira_api(&url).await?;
+            let mut page: ChangelogPage = serde_json::from_str(&response)?;
+            debug!("Changelog page {} for {ticket_key}", page.start_at);
+            if page.values.is_empty() {
+                break;
+            }
+            changelog.histories.append(&mut page.values);
+            changelog.total = page.total;
+            if page.is_last == Some(true) {
+                break;
+            }
+        }
+        changelog.start_at = 0;
+        changelog.max_results = changelog.histories.len() as u32;
+
+        Ok(changelog)
+    }
+
+    // fields returns every distinct field name that appears in the changelog
+    pub fn fields(&self) -> Vec<String> {
+        let mut fields: Vec<String> = self
+            .histories
+            .iter()
+            .flat_map(|history| history.items.iter().map(|item| item.field.clone()))
+            .collect();
+        fields.sort();
+        fields.dedup();
+        fields
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn changelog_from_jira_payload() {
+        let ticket: TicketChangelog = serde_json::from_str(
+            r#"{
+                "changelog": {
+                    "startAt": 0,
+                    "maxResults": 2,
+                    "total": 2,
+                    "histories": [
+                        {
+                            "id": "10400",
+                            "author": { "accountId": "5b10a2844c20165700ede21g", "displayName": "Mia Krystof", "active": true },
+                            "created": "2023-03-02T10:41:29.543+0100",
+                            "items": [
+                                {
+                                    "field": "status",
+                                    "fieldtype": "jira",
+                                    "fieldId": "status",
+                                    "from": "10000",
+                                    "fromString": "To Do",
+                                    "to": "3",
+                                    "toString": "In Progress"
+                                },
+                                {
+                                    "field": "Sprint",
+                                    "fieldtype": "custom",
+                                    "from": "",
+                                    "fromString": "",
+                                    "to": "12",
+                                    "toString": "Sprint 4"
+                                }
+                            ]
+                        },
+                        {
+                            "id": "10401",
+                            "created": "2023-03-03T09:00:00.000+0100",
+                            "items": [
+                                {
+                                    "field": "summary",
+                                    "fieldtype": "jira",
+                                    "from": null,
+                                    "fromString": "Payment fails",
+                                    "to": null,
+                                    "toString": "Payment fails on retry"
+                                },
+                                {
+                                    "field": "resolution",
+                                    "fieldtype": "jira",
+                                    "from": "10001",
+                                    "to": null
+                                }
+                            ]
+                        }
+                    ]
+                }
+            }"#,
+        )
+        .unwrap();
+        let changelog = ticket.changelog;
+        assert_eq!(
+            changelog.fields(),
+            vec!["Sprint", "resolution", "status", "summary"]
+        );
+        let status = &changelog.histories[0].items[0];
+        assert_eq!(status.from.as_deref(), Some("10000"));
+        assert_eq!(
+            (status.old_value(), status.new_value()),
+            ("To Do".into(), "In Progress".into())
+        );
+        let summary = &changelog.histories[1].items[0];
+        assert_eq!(summary.new_value(), "Payment fails on retry");
+        let resolution = &changelog.histories[1].items[1];
+        assert_eq!(
+            (resolution.old_value(), resolution.new_value()),
+            ("10001".into(), "".into())
+        );
+        assert!(changelog.histories[1].author.is_none());
+    }
+}
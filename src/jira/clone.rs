@@ -0,0 +1,193 @@
+use super::auth::JiraClient;
+use super::bulk::{BulkResult, BULK_CONCURRENCY};
+use super::tickets::{markdown_to_adf, TicketData};
+use futures::stream::{self, StreamExt};
+use html2md::parse_html;
+use log::debug;
+use serde_json::{json, Value};
+use std::collections::BTreeMap;
+
+const CLONE_PREFIX: &str = "CLONE - ";
+const CLONE_LINK_TYPE: &str = "Cloners";
+
+// CloneSource is the ticket the create form was filled from and what to do
+// with it once the clone exists
+#[derive(Debug, Clone, PartialEq)]
+pub struct CloneSource {
+    pub ticket_key: String,
+    pub subtask_keys: Vec<String>,
+    pub link: bool,
+    pub copy_subtasks: bool,
+}
+
+impl CloneSource {
+    pub fn from_ticket(ticket: &TicketData) -> Self {
+        Self {
+            ticket_key: ticket.key.clone(),
+            subtask_keys: ticket
+                .fields
+                .subtasks
+                .iter()
+                .map(|subtask| subtask.key.clone())
+                .collect(),
+            link: true,
+            copy_subtasks: false,
+        }
+    }
+}
+
+fn description(ticket: &TicketData) -> String {
+    parse_html(&ticket.rendered_fields.description)
+        .trim()
+        .to_string()
+}
+
+// clone_values are the create form values of a clone of the ticket, by field id
+pub fn clone_values(ticket: &TicketData) -> BTreeMap<String, String> {
+    let fields = &ticket.fields;
+    let mut values = BTreeMap::from([(
+        String::from("summary"),
+        format!("{CLONE_PREFIX}{}", fields.summary),
+    )]);
+    let description = description(ticket);
+    if !description.is_empty() {
+        values.insert(String::from("description"), description);
+    }
+    if !fields.labels.is_empty() {
+        values.insert(String::from("labels"), fields.labels.join(" "));
+    }
+    if !fields.components.is_empty() {
+        let components: Vec<&str> = fields.components.iter().map(|c| c.name.as_str()).collect();
+        values.insert(String::from("components"), components.join(", "));
+    }
+    if let Some(parent) = &fields.parent {
+        values.insert(String::from("parent"), parent.key.clone());
+    }
+    values
+}
+
+// link_clone records that clone_key clones original_key
+pub async fn link_clone(
+    jira_client: &JiraClient,
+    clone_key: &str,
+    original_key: &str,
+) -> anyhow::Result<()> {
+    let data = json!({
+        "type": { "name": CLONE_LINK_TYPE },
+        "inwardIssue": { "key": clone_key },
+        "outwardIssue": { "key": original_key },
+    })
+    .to_string();
+    jira_client.post_to_jira_api("issueLink", data).await?;
+    Ok(())
+}
+
+async fn copy_subtask(
+    jira_client: &JiraClient,
+    project_id: &str,
+    parent_key: &str,
+    subtask_key: &str,
+) -> anyhow::Result<()> {
+    let url = format!("/issue/{subtask_key}?expand=renderedFields");
+    let response = jira_client.get_from_jira_api(&url).await?;
+    let subtask: TicketData = serde_json::from_str(&response)?;
+    let mut fields = json!({
+        "project": { "id": project_id },
+        "parent": { "key": parent_key },
+        "issuetype": { "id": subtask.fields.issuetype.id },
+        "summary": subtask.fields.summary,
+        "labels": subtask.fields.labels,
+        "components": subtask
+            .fields
+            .components
+            .iter()
+            .map(|c| json!({ "name": c.name }))
+            .collect::<Vec<Value>>(),
+    });
+    let description = description(&subtask);
+    if !description.is_empty() {
+        fields["description"] = serde_json::from_str(&markdown_to_adf(&description))?;
+    }
+    let data = json!({ "fields": fields }).to_string();
+    jira_client.post_to_jira_api("issue", data).await?;
+    Ok(())
+}
+
+// copy_subtasks creates a copy of every sub-task under the clone, the
+// outcome of each is keyed by the original sub-task
+pub async fn copy_subtasks(
+    jira_client: &JiraClient,
+    project_id: &str,
+    parent_key: &str,
+    subtask_keys: &[String],
+) -> Vec<BulkResult> {
+    stream::iter(subtask_keys)
+        .map(|subtask_key| async move {
+            let outcome = copy_subtask(jira_client, project_id, parent_key, subtask_key)
+                .await
+                .map_err(|e| e.to_string());
+            debug!("Copy sub-task {subtask_key} to {parent_key}: {outcome:?}");
+            BulkResult {
+                ticket_key: subtask_key.clone(),
+                outcome,
+            }
+        })
+        .buffered(BULK_CONCURRENCY)
+        .collect()
+        .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn clone_values_from_ticket() {
+        let ticket: TicketData = serde_json::from_value(json!({
+            "key": "ABC-7",
+            "renderedFields": { "description": "<p>Steps to <strong>reproduce</strong></p>" },
+            "fields": {
+                "components": [{ "name": "API" }, { "name": "Web" }],
+                "issuelinks": [],
+                "issuetype": { "id": "10004", "name": "Bug", "subtask": false },
+                "labels": ["triage"],
+                "parent": {
+                    "key": "ABC-1",
+                    "fields": {
+                        "issuetype": { "id": "10000", "name": "Epic", "subtask": false },
+                        "status": { "name": "To Do" },
+                        "summary": "Checkout"
+                    }
+                },
+                "project": { "key": "ABC", "name": "Alphabet" },
+                "status": { "name": "To Do" },
+                "subtasks": [{
+                    "key": "ABC-8",
+                    "fields": {
+                        "issuetype": { "id": "10005", "name": "Sub-task", "subtask": true },
+                        "status": { "name": "To Do" },
+                        "summary": "Write a test"
+                    }
+                }],
+                "summary": "Payment fails"
+            }
+        }))
+        .unwrap();
+        assert_eq!(
+            clone_values(&ticket),
+            BTreeMap::from([
+                ("components".to_string(), "API, Web".to_string()),
+                (
+                    "description".to_string(),
+                    "Steps to **reproduce**".to_string()
+                ),
+                ("labels".to_string(), "triage".to_string()),
+                ("parent".to_string(), "ABC-1".to_string()),
+                ("summary".to_string(), "CLONE - Payment fails".to_string()),
+            ])
+        );
+        let source = CloneSource::from_ticket(&ticket);
+        assert_eq!(source.subtask_keys, vec!["ABC-8".to_string()]);
+        assert!(source.link && !source.copy_subtasks);
+    }
+}
@@ -0,0 +1,384 @@
+use super::auth::JiraClient;
+use super::bulk::find_user;
+use super::fields::{format_value, FieldSchema};
+use super::tickets::markdown_to_adf;
+use chrono::{Local, NaiveDate, NaiveDateTime, TimeZone};
+use log::debug;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+use std::collections::{BTreeMap, HashMap};
+
+const CREATEMETA_MAX_RESULTS: &str = "200";
+// project and issuetype are picked on the create screen itself
+const FORM_SKIPPED_FIELDS: [&str; 2] = ["project", "issuetype"];
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateMetaField {
+    pub field_id: String,
+    pub name: String,
+    #[serde(default)]
+    pub required: bool,
+    #[serde(default)]
+    pub has_default_value: bool,
+    pub schema: Option<FieldSchema>,
+    pub allowed_values: Option<Vec<Value>>,
+}
+
+#[derive(Deserialize, Debug)]
+struct CreateMetaPage {
+    #[serde(alias = "values")]
+    fields: Vec<CreateMetaField>,
+}
+
+#[derive(Deserialize, Debug)]
+struct CreatedTicket {
+    key: String,
+}
+
+#[derive(Deserialize, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+struct JiraErrors {
+    #[serde(default)]
+    error_messages: Vec<String>,
+    #[serde(default)]
+    errors: BTreeMap<String, String>,
+}
+
+// FieldKind is how the text typed for a field is turned into what Jira expects
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FieldKind {
+    RichText,
+    Text,
+    Number,
+    Date,
+    DateTime,
+    Choice,
+    MultiChoice,
+    Labels,
+    User,
+    Parent,
+    Unsupported,
+}
+
+impl CreateMetaField {
+    pub fn kind(&self) -> FieldKind {
+        let Some(schema) = &self.schema else {
+            return FieldKind::Unsupported;
+        };
+        let textarea = matches!(schema.custom.as_deref(), Some(c) if c.ends_with(":textarea"));
+        let rich_text = ["description", "environment"].contains(&self.field_id.as_str());
+        match (schema.field_type.as_str(), schema.items.as_deref()) {
+            ("string", _) if rich_text || textarea => FieldKind::RichText,
+            ("array", _) if self.allowed_values.is_some() => FieldKind::MultiChoice,
+            _ if self.allowed_values.is_some() => FieldKind::Choice,
+            ("string", _) => FieldKind::Text,
+            ("number", _) => FieldKind::Number,
+            ("date", _) => FieldKind::Date,
+            ("datetime", _) => FieldKind::DateTime,
+            ("user", _) => FieldKind::User,
+            ("array", Some("string")) => FieldKind::Labels,
+            ("issuelink", _) if self.field_id == "parent" => FieldKind::Parent,
+            _ => FieldKind::Unsupported,
+        }
+    }
+
+    pub fn choices(&self) -> Vec<String> {
+        self.allowed_values
+            .iter()
+            .flatten()
+            .map(|value| format_value(value, None))
+            .collect()
+    }
+
+    // hint explains what can be typed for the field
+    pub fn hint(&self) -> String {
+        match self.kind() {
+            FieldKind::RichText => String::from("Markdown text, Enter adds a new line"),
+            FieldKind::Text => String::from("Text"),
+            FieldKind::Number => String::from("Number"),
+            FieldKind::Date => String::from("Date, YYYY-MM-DD"),
+            FieldKind::DateTime => String::from("Date and time, YYYY-MM-DD HH:MM"),
+            FieldKind::Choice => format!("One of: {}", self.choices().join(", ")),
+            FieldKind::MultiChoice => {
+                format!("Comma separated, from: {}", self.choices().join(", "))
+            }
+            FieldKind::Labels => String::from("Labels separated by spaces or commas"),
+            FieldKind::User => String::from("User name or email, \"me\" for yourself"),
+            FieldKind::Parent => String::from("Key of the parent ticket"),
+            FieldKind::Unsupported => String::from("Can not be set from here"),
+        }
+    }
+
+    fn choice_id(&self, value: &str) -> Result<Value, String> {
+        self.allowed_values
+            .iter()
+            .flatten()
+            .find(|allowed| {
+                format_value(allowed, None).eq_ignore_ascii_case(value)
+                    || allowed.get("id").and_then(Value::as_str) == Some(value)
+            })
+            .and_then(|allowed| allowed.get("id"))
+            .map(|id| json!({ "id": id }))
+            .ok_or_else(|| format!("{value} is not one of {}", self.choices().join(", ")))
+    }
+
+    // to_json converts the typed value, users are looked up separately
+    pub fn to_json(&self, value: &str) -> Result<Option<Value>, String> {
+        let value = value.trim();
+        if value.is_empty() {
+            return Ok(None);
+        }
+        let json = match self.kind() {
+            FieldKind::RichText => {
+                serde_json::from_str(&markdown_to_adf(value)).map_err(|e| e.to_string())?
+            }
+            FieldKind::Text => json!(value),
+            FieldKind::Number => json!(value
+                .parse::<f64>()
+                .map_err(|_| String::from("must be a number"))?),
+            FieldKind::Date => {
+                NaiveDate::parse_from_str(value, "%Y-%m-%d")
+                    .map_err(|_| String::from("must be a date like 2024-05-31"))?;
+                json!(value)
+            }
+            FieldKind::DateTime => {
+                let datetime = NaiveDateTime::parse_from_str(value, "%Y-%m-%d %H:%M")
+                    .ok()
+                    .and_then(|datetime| Local.from_local_datetime(&datetime).single())
+                    .ok_or_else(|| String::from("must be a date and time like 2024-05-31 14:30"))?;
+                json!(datetime.format("%Y-%m-%dT%H:%M:%S%.3f%z").to_string())
+            }
+            FieldKind::Choice => self.choice_id(value)?,
+            FieldKind::MultiChoice => Value::Array(
+                value
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|choice| !choice.is_empty())
+                    .map(|choice| self.choice_id(choice))
+                    .collect::<Result<Vec<Value>, String>>()?,
+            ),
+            FieldKind::Labels => json!(value
+                .split(|c: char| c == ',' || c.is_whitespace())
+                .filter(|label| !label.is_empty())
+                .collect::<Vec<&str>>()),
+            FieldKind::Parent => json!({ "key": value.to_uppercase() }),
+            FieldKind::User => return Ok(None),
+            FieldKind::Unsupported => return Err(String::from("can not be set from here")),
+        };
+        Ok(Some(json))
+    }
+}
+
+// validate checks the typed values before anything is sent to Jira and
+// returns the problems by field id
+pub fn validate(
+    fields: &[CreateMetaField],
+    values: &BTreeMap<String, String>,
+) -> BTreeMap<String, String> {
+    let mut errors = BTreeMap::new();
+    for field in fields {
+        let value = values.get(&field.field_id).map(|v| v.trim()).unwrap_or("");
+        if value.is_empty() {
+            if field.required && !field.has_default_value {
+                errors.insert(field.field_id.clone(), String::from("is required"));
+            }
+            continue;
+        }
+        if let Err(e) = field.to_json(value) {
+            errors.insert(field.field_id.clone(), e);
+        }
+    }
+    errors
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum CreateOutcome {
+    Created(String),
+    Rejected {
+        field_errors: BTreeMap<String, String>,
+        messages: Vec<String>,
+    },
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct JiraCreateMetaAPI {
+    fields: HashMap<(String, String), Vec<CreateMetaField>>,
+}
+
+impl JiraCreateMetaAPI {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // get_fields returns the create screen fields of an issue type, required
+    // ones first. They are pulled once per project and issue type.
+    pub async fn get_fields(
+        &mut self,
+        jira_client: &JiraClient,
+        project_id: &str,
+        issue_type_id: &str,
+    ) -> anyhow::Result<Vec<CreateMetaField>> {
+        let cache_key = (project_id.to_string(), issue_type_id.to_string());
+        if let Some(fields) = self.fields.get(&cache_key) {
+            return Ok(fields.clone());
+        }
+        let url = format!("issue/createmeta/{project_id}/issuetypes/{issue_type_id}");
+        let response = jira_client
+            .get_from_jira_api_with_query(&url, &[("maxResults", CREATEMETA_MAX_RESULTS)])
+            .await?;
+        let page: CreateMetaPage = serde_json::from_str(&response)?;
+        let mut fields: Vec<CreateMetaField> = page
+            .fields
+            .into_iter()
+            .filter(|field| !FORM_SKIPPED_FIELDS.contains(&field.field_id.as_str()))
+            .collect();
+        fields.sort_by_key(|field| !field.required);
+        debug!("Create fields of {issue_type_id}: {}", fields.len());
+        self.fields.insert(cache_key, fields.clone());
+        Ok(fields)
+    }
+
+    // create_ticket sends the form to Jira. Problems found before sending and
+    // the field errors Jira answers with are both returned by field id.
+    pub async fn create_ticket(
+        &self,
+        jira_client: &JiraClient,
+        project_id: &str,
+        issue_type_id: &str,
+        fields: &[CreateMetaField],
+        values: &BTreeMap<String, String>,
+    ) -> anyhow::Result<CreateOutcome> {
+        let mut field_errors = validate(fields, values);
+        let mut data = Map::new();
+        data.insert("project".to_string(), json!({ "id": project_id }));
+        data.insert("issuetype".to_string(), json!({ "id": issue_type_id }));
+        for field in fields {
+            let value = values.get(&field.field_id).map(|v| v.trim()).unwrap_or("");
+            if value.is_empty() || field_errors.contains_key(&field.field_id) {
+                continue;
+            }
+            let json = match field.kind() {
+                FieldKind::User => find_user(jira_client, value)
+                    .await
+                    .map(|user| Some(json!({ "accountId": user.account_id })))
+                    .map_err(|e| e.to_string()),
+                _ => field.to_json(value),
+            };
+            match json {
+                Ok(Some(json)) => {
+                    data.insert(field.field_id.clone(), json);
+                }
+                Ok(None) => {}
+                Err(e) => {
+                    field_errors.insert(field.field_id.clone(), e);
+                }
+            }
+        }
+        if !field_errors.is_empty() {
+            return Ok(CreateOutcome::Rejected {
+                field_errors,
+                messages: vec![],
+            });
+        }
+
+        let body = json!({ "fields": data }).to_string();
+        let (status, response) = jira_client
+            .post_to_jira_api_with_status("/issue", body)
+            .await?;
+        if status.is_success() {
+            let created: CreatedTicket = serde_json::from_str(&response)?;
+            return Ok(CreateOutcome::Created(created.key));
+        }
+        let errors: JiraErrors = serde_json::from_str(&response).unwrap_or_default();
+        let mut messages = errors.error_messages;
+        if messages.is_empty() && errors.errors.is_empty() {
+            messages.push(format!("Jira answered {status}"));
+        }
+        Ok(CreateOutcome::Rejected {
+            field_errors: errors.errors,
+            messages,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn field(field_id: &str, field_type: &str, items: Option<&str>) -> CreateMetaField {
+        CreateMetaField {
+            field_id: field_id.to_string(),
+            name: field_id.to_string(),
+            required: false,
+            has_default_value: false,
+            schema: Some(FieldSchema {
+                field_type: field_type.to_string(),
+                items: items.map(String::from),
+                custom: None,
+            }),
+            allowed_values: None,
+        }
+    }
+
+    #[test]
+    fn converts_values_by_kind() {
+        let mut priority = field("priority", "priority", None);
+        priority.allowed_values = Some(vec![
+            json!({"id": "1", "name": "High"}),
+            json!({"id": "2", "name": "Low"}),
+        ]);
+        assert_eq!(priority.to_json("low"), Ok(Some(json!({"id": "2"}))));
+        assert!(priority.to_json("Urgent").is_err());
+
+        let mut components = field("components", "array", Some("component"));
+        components.allowed_values = Some(vec![
+            json!({"id": "10", "name": "API"}),
+            json!({"id": "11", "name": "UI"}),
+        ]);
+        assert_eq!(
+            components.to_json("API, UI"),
+            Ok(Some(json!([{"id": "10"}, {"id": "11"}])))
+        );
+
+        let labels = field("labels", "array", Some("string"));
+        assert_eq!(
+            labels.to_json("bug, backend  ui"),
+            Ok(Some(json!(["bug", "backend", "ui"])))
+        );
+
+        let due = field("duedate", "date", None);
+        assert_eq!(due.to_json("2024-05-31"), Ok(Some(json!("2024-05-31"))));
+        assert!(due.to_json("31/05/2024").is_err());
+
+        let points = field("customfield_10016", "number", None);
+        assert_eq!(points.to_json("3"), Ok(Some(json!(3.0))));
+        assert_eq!(field("assignee", "user", None).kind(), FieldKind::User);
+        assert_eq!(
+            field("description", "string", None).kind(),
+            FieldKind::RichText
+        );
+    }
+
+    #[test]
+    fn validates_required_and_typed_fields() {
+        let mut summary = field("summary", "string", None);
+        summary.required = true;
+        let mut reporter = field("reporter", "user", None);
+        reporter.required = true;
+        reporter.has_default_value = true;
+        let fields = vec![summary, reporter, field("duedate", "date", None)];
+        let values = BTreeMap::from([("duedate".to_string(), "tomorrow".to_string())]);
+        let errors = validate(&fields, &values);
+        assert_eq!(
+            errors,
+            BTreeMap::from([
+                (
+                    "duedate".to_string(),
+                    "must be a date like 2024-05-31".to_string()
+                ),
+                ("summary".to_string(), "is required".to_string()),
+            ])
+        );
+    }
+}
@@ -0,0 +1,154 @@
+use super::auth::JiraClient;
+use super::jql::{Jql, JqlQuery, JqlValue, SortOrder};
+use super::tickets::Status;
+use log::debug;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+const EPIC_PAGE_SIZE: u32 = 100;
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+struct EpicParent {
+    key: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+struct EpicFields {
+    parent: Option<EpicParent>,
+    status: Status,
+    summary: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+struct EpicIssue {
+    key: String,
+    fields: EpicFields,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+struct EpicSearch {
+    issues: Vec<EpicIssue>,
+    start_at: u32,
+    total: u32,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct EpicProgress {
+    pub key: String,
+    pub summary: String,
+    pub status: String,
+    pub total: u32,
+    pub in_progress: u32,
+    pub done: u32,
+}
+
+impl EpicProgress {
+    // ratio is the share of children in the done status category
+    pub fn ratio(&self) -> f64 {
+        match self.total {
+            0 => 0.0,
+            total => f64::from(self.done) / f64::from(total),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Default, Clone)]
+pub struct JiraEpicsAPI {
+    pub epics: Vec<EpicProgress>,
+    pub project_key: Option<String>,
+}
+
+impl JiraEpicsAPI {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // search pulls every issue matching the JQL, following the pagination
+    async fn search(
+        jira_client: &JiraClient,
+        jql: &str,
+        fields: &str,
+    ) -> anyhow::Result<Vec<EpicIssue>> {
+        let mut issues = vec![];
+        loop {
+            let start_at = issues.len().to_string();
+            let max_results = EPIC_PAGE_SIZE.to_string();
+            let params = [
+                ("jql", jql),
+                ("fields", fields),
+                ("startAt", start_at.as_str()),
+                ("maxResults", max_results.as_str()),
+            ];
+            let response = jira_client.search_jira_api(&params).await?;
+            let mut page: EpicSearch = serde_json::from_str(&response)?;
+            debug!(
+                "Epic search page {} of {} for {jql}",
+                page.start_at, page.total
+            );
+            let empty_page = page.issues.is_empty();
+            issues.append(&mut page.issues);
+            if empty_page || issues.len() as u32 >= page.total {
+                break;
+            }
+        }
+        Ok(issues)
+    }
+
+    // get_epics lists the epics of a project and counts their children by
+    // status category
+    pub async fn get_epics(
+        &mut self,
+        jira_client: &JiraClient,
+        project_key: &str,
+    ) -> anyhow::Result<&Vec<EpicProgress>> {
+        let jql = JqlQuery::new(
+            Jql::equals("project", project_key).and(Jql::equals("issuetype", "Epic")),
+        )
+        .order_by("created", SortOrder::Desc)
+        .to_string();
+        let epics = Self::search(jira_client, &jql, "summary,status").await?;
+        self.epics = epics
+            .into_iter()
+            .map(|epic| EpicProgress {
+                summary: epic.fields.summary.unwrap_or_default(),
+                status: epic.fields.status.name,
+                key: epic.key,
+                ..Default::default()
+            })
+            .collect();
+        self.project_key = Some(project_key.to_string());
+        if self.epics.is_empty() {
+            return Ok(&self.epics);
+        }
+
+        // Children are searched in batches of epics to keep the JQL short
+        let epic_keys: Vec<&str> = self.epics.iter().map(|epic| epic.key.as_str()).collect();
+        let mut children = vec![];
+        for batch in epic_keys.chunks(EPIC_PAGE_SIZE as usize) {
+            let jql = Jql::is_in("parent", JqlValue::list(batch.iter().copied())).to_string();
+            children.append(&mut Self::search(jira_client, &jql, "status,parent").await?);
+        }
+        let mut progress: HashMap<String, (u32, u32, u32)> = HashMap::new();
+        for child in children {
+            let Some(parent) = child.fields.parent else {
+                continue;
+            };
+            let counts = progress.entry(parent.key).or_default();
+            counts.0 += 1;
+            match child.fields.status.category_key() {
+                "indeterminate" => counts.1 += 1,
+                "done" => counts.2 += 1,
+                _ => {}
+            }
+        }
+        for epic in self.epics.iter_mut() {
+            if let Some((total, in_progress, done)) = progress.get(&epic.key) {
+                epic.total = *total;
+                epic.in_progress = *in_progress;
+                epic.done = *done;
+            }
+        }
+        Ok(&self.epics)
+    }
+}
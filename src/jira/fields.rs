@@ -0,0 +1,242 @@
+use super::auth::JiraClient;
+use super::SurrealAny;
+use chrono::DateTime;
+use log::debug;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct FieldSchema {
+    #[serde(rename = "type")]
+    pub field_type: String,
+    pub items: Option<String>,
+    pub custom: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct FieldMetadata {
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub custom: bool,
+    pub schema: Option<FieldSchema>,
+}
+
+// FieldMetadataCache keeps the field list of one Jira instance in the db cache
+#[derive(Serialize, Deserialize, Debug, Clone)]
+struct FieldMetadataCache {
+    domain: String,
+    fields: Vec<FieldMetadata>,
+}
+
+// CustomField is a field from the [custom_fields] config shown next to the
+// built in ticket fields
+#[derive(Debug, Clone, PartialEq)]
+pub struct CustomField {
+    pub name: String,
+    pub field_id: String,
+    pub schema: Option<FieldSchema>,
+}
+
+impl CustomField {
+    pub fn format(&self, value: Option<&Value>) -> String {
+        match value {
+            Some(value) => format_value(value, self.schema.as_ref()),
+            None => String::new(),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Default, Clone)]
+pub struct JiraFieldsAPI {
+    pub fields: Vec<FieldMetadata>,
+}
+
+impl JiraFieldsAPI {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // get_fields pulls the field metadata once per Jira instance, later runs
+    // read it back from the db cache
+    pub async fn get_fields(
+        &mut self,
+        jira_client: &JiraClient,
+        db: &SurrealAny,
+    ) -> anyhow::Result<&Vec<FieldMetadata>> {
+        let domain = jira_client.get_domain().clone();
+        let cached: Option<FieldMetadataCache> = db.select(("field_metadata", &domain)).await?;
+        if let Some(cache) = cached {
+            debug!("Field metadata of {domain} from cache");
+            self.fields = cache.fields;
+            return Ok(&self.fields);
+        }
+
+        let response = jira_client.get_from_jira_api("/field").await?;
+        self.fields = serde_json::from_str(&response)?;
+        let cache = FieldMetadataCache {
+            domain: domain.clone(),
+            fields: self.fields.clone(),
+        };
+        let _cache: Option<FieldMetadataCache> = db
+            .update(("field_metadata", &domain))
+            .content(cache)
+            .await?;
+        Ok(&self.fields)
+    }
+
+    // resolve matches the configured fields by id or by their Jira name and
+    // returns the names of the ones that were not found
+    pub fn resolve(
+        &self,
+        configured: &BTreeMap<String, String>,
+    ) -> (Vec<CustomField>, Vec<String>) {
+        let mut custom_fields = vec![];
+        let mut unknown = vec![];
+        for (name, field) in configured {
+            let metadata = self.fields.iter().find(|m| m.id == *field).or_else(|| {
+                self.fields
+                    .iter()
+                    .find(|m| m.name.eq_ignore_ascii_case(field))
+            });
+            match metadata {
+                Some(metadata) => custom_fields.push(CustomField {
+                    name: name.clone(),
+                    field_id: metadata.id.clone(),
+                    schema: metadata.schema.clone(),
+                }),
+                None => unknown.push(name.clone()),
+            }
+        }
+        (custom_fields, unknown)
+    }
+}
+
+// format_datetime shortens a Jira timestamp to the minute
+pub fn format_datetime(datetime: &str) -> String {
+    DateTime::parse_from_str(datetime, "%Y-%m-%dT%H:%M:%S%.f%z")
+        .map(|date| date.format("%Y-%m-%d %H:%M").to_string())
+        .unwrap_or_else(|_| datetime.to_string())
+}
+
+// format_value renders a field value for a table cell, following the field
+// type when Jira gives one
+pub fn format_value(value: &Value, schema: Option<&FieldSchema>) -> String {
+    let field_type = schema.map(|s| s.field_type.as_str());
+    match value {
+        Value::Null => String::new(),
+        Value::Bool(b) => match b {
+            true => String::from("Yes"),
+            false => String::from("No"),
+        },
+        Value::Number(n) => match n.as_f64() {
+            Some(f) if f.fract() == 0.0 => format!("{}", f as i64),
+            Some(f) => format!("{f}"),
+            None => n.to_string(),
+        },
+        Value::String(s) => match field_type {
+            Some("datetime") => format_datetime(s),
+            _ => s.clone(),
+        },
+        Value::Array(values) => {
+            let item_schema = schema
+                .and_then(|s| s.items.as_ref())
+                .map(|items| FieldSchema {
+                    field_type: items.clone(),
+                    items: None,
+                    custom: None,
+                });
+            values
+                .iter()
+                .map(|v| format_value(v, item_schema.as_ref()))
+                .filter(|v| !v.is_empty())
+                .collect::<Vec<String>>()
+                .join(", ")
+        }
+        Value::Object(object) => {
+            // Users, options, versions, teams and sprints all name themselves differently
+            let label = ["displayName", "value", "name", "title", "key"]
+                .iter()
+                .find_map(|key| object.get(*key))
+                .map(|v| format_value(v, None))
+                .unwrap_or_default();
+            match object.get("child") {
+                Some(child) => format!("{label} / {}", format_value(child, None)),
+                None => label,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn schema(field_type: &str, items: Option<&str>) -> FieldSchema {
+        FieldSchema {
+            field_type: field_type.to_string(),
+            items: items.map(String::from),
+            custom: None,
+        }
+    }
+
+    #[test]
+    fn formats_by_type() {
+        assert_eq!(format_value(&json!(null), None), "");
+        assert_eq!(
+            format_value(&json!(5.0), Some(&schema("number", None))),
+            "5"
+        );
+        assert_eq!(
+            format_value(&json!(2.5), Some(&schema("number", None))),
+            "2.5"
+        );
+        assert_eq!(
+            format_value(
+                &json!("2024-05-01T10:30:00.000+0000"),
+                Some(&schema("datetime", None))
+            ),
+            "2024-05-01 10:30"
+        );
+        assert_eq!(
+            format_value(&json!({"displayName": "Ann", "accountId": "1"}), None),
+            "Ann"
+        );
+        assert_eq!(
+            format_value(&json!({"value": "EU", "child": {"value": "Paris"}}), None),
+            "EU / Paris"
+        );
+        assert_eq!(
+            format_value(
+                &json!([{"name": "Sprint 1"}, {"name": "Sprint 2"}]),
+                Some(&schema("array", Some("json")))
+            ),
+            "Sprint 1, Sprint 2"
+        );
+    }
+
+    #[test]
+    fn resolves_fields_by_id_or_name() {
+        let api = JiraFieldsAPI {
+            fields: vec![FieldMetadata {
+                id: "customfield_10016".to_string(),
+                name: "Story point estimate".to_string(),
+                custom: true,
+                schema: Some(schema("number", None)),
+            }],
+        };
+        let configured = BTreeMap::from([
+            ("Points".to_string(), "customfield_10016".to_string()),
+            ("Estimate".to_string(), "story point estimate".to_string()),
+            ("Team".to_string(), "customfield_99999".to_string()),
+        ]);
+        let (custom_fields, unknown) = api.resolve(&configured);
+        assert_eq!(custom_fields.len(), 2);
+        assert!(custom_fields
+            .iter()
+            .all(|f| f.field_id == "customfield_10016"));
+        assert_eq!(unknown, vec!["Team".to_string()]);
+    }
+}
@@ -0,0 +1,96 @@
+use super::auth::JiraClient;
+use super::tickets::TicketData;
+use log::debug;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct FilterOwner {
+    pub display_name: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Filter {
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub jql: String,
+    pub description: Option<String>,
+    pub favourite: Option<bool>,
+    pub owner: Option<FilterOwner>,
+}
+
+// FilterTicketsPage is one cached page of a filter's results
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct FilterTicketsPage {
+    pub filter_id: String,
+    pub start_at: u32,
+    pub tickets: Vec<TicketData>,
+    pub total: u32,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+struct FilterPage {
+    is_last: Option<bool>,
+    start_at: u32,
+    total: Option<u32>,
+    values: Vec<Filter>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Default, Clone)]
+pub struct JiraFiltersAPI {
+    pub favourites: Vec<Filter>,
+    pub search_results: Vec<Filter>,
+}
+
+impl JiraFiltersAPI {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub async fn get_favourites(
+        &mut self,
+        jira_client: &JiraClient,
+    ) -> anyhow::Result<&Vec<Filter>> {
+        let response = jira_client
+            .get_from_jira_api_with_query("filter/favourite", &[("expand", "jql")])
+            .await?;
+        self.favourites = serde_json::from_str(&response)?;
+        debug!("{} favourite filters", self.favourites.len());
+        Ok(&self.favourites)
+    }
+
+    // search_filters pulls every filter visible to the user whose name contains filter_name
+    pub async fn search_filters(
+        &mut self,
+        jira_client: &JiraClient,
+        filter_name: &str,
+    ) -> anyhow::Result<&Vec<Filter>> {
+        self.search_results.clear();
+        loop {
+            let start_at = self.search_results.len().to_string();
+            let params = [
+                ("filterName", filter_name),
+                ("expand", "jql,description,favourite,owner"),
+                ("orderBy", "name"),
+                ("startAt", start_at.as_str()),
+            ];
+            let response = jira_client
+                .get_from_jira_api_with_query("filter/search", &params)
+                .await?;
+            let mut page: FilterPage = serde_json::from_str(&response)?;
+            debug!(
+                "Filter search page {} of {:?} for {filter_name}",
+                page.start_at, page.total
+            );
+            let empty_page = page.values.is_empty();
+            self.search_results.append(&mut page.values);
+            if page.is_last != Some(false) || empty_page {
+                break;
+            }
+        }
+        Ok(&self.search_results)
+    }
+}
@@ -0,0 +1,547 @@
+use super::auth::JiraClient;
+use crate::config::JiraConfigTickets;
+use log::debug;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+const MAX_SUGGESTIONS: usize = 10;
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct JqlFieldReference {
+    pub value: String,
+    pub display_name: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct JqlFunctionReference {
+    pub value: String,
+    pub display_name: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct JqlAutocomplete {
+    #[serde(default)]
+    pub visible_field_names: Vec<JqlFieldReference>,
+    #[serde(default)]
+    pub visible_function_names: Vec<JqlFunctionReference>,
+    #[serde(default)]
+    pub jql_reserved_words: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct JqlHistory {
+    pub jql: String,
+    pub last_run: i64,
+}
+
+impl JqlAutocomplete {
+    pub async fn from_jira_api(jira_client: &JiraClient) -> anyhow::Result<Self> {
+        let response = jira_client
+            .get_from_jira_api("/jql/autocompletedata")
+            .await?;
+        let autocomplete: Self = serde_json::from_str(&response)?;
+        debug!(
+            "JQL autocomplete {} fields {} functions",
+            autocomplete.visible_field_names.len(),
+            autocomplete.visible_function_names.len()
+        );
+        Ok(autocomplete)
+    }
+
+    // suggestions returns the field names, functions and keywords starting
+    // with the word currently being typed
+    pub fn suggestions(&self, word: &str) -> Vec<String> {
+        if word.is_empty() {
+            return vec![];
+        }
+        let word = word.to_lowercase();
+        let fields = self.visible_field_names.iter().map(|f| &f.value);
+        let functions = self.visible_function_names.iter().map(|f| &f.value);
+        let mut suggestions: Vec<String> = fields
+            .chain(functions)
+            .chain(self.jql_reserved_words.iter())
+            .filter(|value| {
+                let value = value.trim_start_matches('"').to_lowercase();
+                value.starts_with(&word) && value != word
+            })
+            .cloned()
+            .collect();
+        suggestions.sort();
+        suggestions.dedup();
+        suggestions.truncate(MAX_SUGGESTIONS);
+        suggestions
+    }
+}
+
+// quote wraps a value in double quotes, escaping the characters JQL treats specially
+fn quote(value: &str) -> String {
+    let escaped = value.replace('\\', "\\\\").replace('"', "\\\"");
+    format!("\"{escaped}\"")
+}
+
+// quote_field leaves plain field names such as `status` or `cf[10020]` untouched
+fn quote_field(field: &str) -> String {
+    let plain = field.starts_with(|c: char| c.is_ascii_alphabetic())
+        && field
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '.' | '[' | ']'));
+    match plain {
+        true => field.to_string(),
+        false => quote(field),
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum JqlValue {
+    Text(String),
+    Function(String),
+    Empty,
+    List(Vec<JqlValue>),
+}
+
+impl JqlValue {
+    // function is a JQL function call such as `currentUser()`, rendered as is
+    pub fn function(name: &str) -> Self {
+        Self::Function(name.to_string())
+    }
+
+    pub fn list<T: Into<JqlValue>>(values: impl IntoIterator<Item = T>) -> Self {
+        Self::List(values.into_iter().map(Into::into).collect())
+    }
+}
+
+impl From<&str> for JqlValue {
+    fn from(value: &str) -> Self {
+        Self::Text(value.to_string())
+    }
+}
+
+impl From<String> for JqlValue {
+    fn from(value: String) -> Self {
+        Self::Text(value)
+    }
+}
+
+impl From<&String> for JqlValue {
+    fn from(value: &String) -> Self {
+        Self::Text(value.clone())
+    }
+}
+
+impl fmt::Display for JqlValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Text(value) => write!(f, "{}", quote(value)),
+            Self::Function(name) => write!(f, "{name}"),
+            Self::Empty => write!(f, "EMPTY"),
+            Self::List(values) => {
+                let values: Vec<String> = values.iter().map(ToString::to_string).collect();
+                write!(f, "({})", values.join(", "))
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum JqlOperator {
+    Equals,
+    NotEquals,
+    In,
+    Is,
+    IsNot,
+    GreaterThanOrEquals,
+    Contains,
+}
+
+impl fmt::Display for JqlOperator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let operator = match self {
+            Self::Equals => "=",
+            Self::NotEquals => "!=",
+            Self::In => "IN",
+            Self::Is => "IS",
+            Self::IsNot => "IS NOT",
+            Self::GreaterThanOrEquals => ">=",
+            Self::Contains => "~",
+        };
+        write!(f, "{operator}")
+    }
+}
+
+// Jql is a search condition, clauses are combined into AND/OR groups
+#[derive(Debug, Clone, PartialEq)]
+pub enum Jql {
+    Clause {
+        field: String,
+        operator: JqlOperator,
+        value: JqlValue,
+    },
+    And(Vec<Jql>),
+    Or(Vec<Jql>),
+}
+
+impl Jql {
+    pub fn clause(field: &str, operator: JqlOperator, value: impl Into<JqlValue>) -> Self {
+        Self::Clause {
+            field: field.to_string(),
+            operator,
+            value: value.into(),
+        }
+    }
+
+    pub fn equals(field: &str, value: impl Into<JqlValue>) -> Self {
+        Self::clause(field, JqlOperator::Equals, value)
+    }
+
+    pub fn is_in(field: &str, values: JqlValue) -> Self {
+        Self::clause(field, JqlOperator::In, values)
+    }
+
+    pub fn is_empty(field: &str) -> Self {
+        Self::clause(field, JqlOperator::Is, JqlValue::Empty)
+    }
+
+    pub fn is_not_empty(field: &str) -> Self {
+        Self::clause(field, JqlOperator::IsNot, JqlValue::Empty)
+    }
+
+    pub fn and(self, other: Jql) -> Self {
+        match self {
+            Self::And(mut conditions) => {
+                conditions.push(other);
+                Self::And(conditions)
+            }
+            condition => Self::And(vec![condition, other]),
+        }
+    }
+
+    pub fn or(self, other: Jql) -> Self {
+        match self {
+            Self::Or(mut conditions) => {
+                conditions.push(other);
+                Self::Or(conditions)
+            }
+            condition => Self::Or(vec![condition, other]),
+        }
+    }
+
+    fn is_group(&self) -> bool {
+        matches!(self, Self::And(c) | Self::Or(c) if c.len() > 1)
+    }
+}
+
+impl fmt::Display for Jql {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Nested groups are always wrapped so the precedence never depends on Jira
+        let group = |conditions: &[Jql], separator: &str| {
+            conditions
+                .iter()
+                .map(|condition| match condition.is_group() {
+                    true => format!("({condition})"),
+                    false => condition.to_string(),
+                })
+                .collect::<Vec<String>>()
+                .join(separator)
+        };
+        match self {
+            Self::Clause {
+                field,
+                operator,
+                value,
+            } => write!(f, "{} {operator} {value}", quote_field(field)),
+            Self::And(conditions) => write!(f, "{}", group(conditions, " AND ")),
+            Self::Or(conditions) => write!(f, "{}", group(conditions, " OR ")),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SortOrder {
+    Asc,
+    Desc,
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct JqlQuery {
+    pub condition: Option<Jql>,
+    pub order_by: Vec<(String, SortOrder)>,
+}
+
+impl JqlQuery {
+    pub fn new(condition: Jql) -> Self {
+        Self {
+            condition: Some(condition),
+            order_by: vec![],
+        }
+    }
+
+    pub fn order_by(mut self, field: &str, order: SortOrder) -> Self {
+        self.order_by.push((field.to_string(), order));
+        self
+    }
+
+    // for_project_tickets builds the ticket list query of a project from the
+    // [tickets] configuration. `show_unassgined` adds unassigned tickets to the
+    // current user's tickets when true and hides unassigned tickets when false.
+    pub fn for_project_tickets(project_key: &str, config: &JiraConfigTickets) -> Self {
+        let mut condition = Jql::equals("project", project_key);
+
+        let current_user = Jql::equals("assignee", JqlValue::function("currentUser()"));
+        match (config.current_user_tickets_only, config.show_unassgined) {
+            (Some(true), Some(true)) => {
+                condition = condition.and(current_user.or(Jql::is_empty("assignee")))
+            }
+            (Some(true), _) => condition = condition.and(current_user),
+            (_, Some(false)) => condition = condition.and(Jql::is_not_empty("assignee")),
+            _ => {}
+        }
+
+        if config.current_sprint_tickets_only == Some(true) {
+            condition = condition.and(Jql::is_in("sprint", JqlValue::function("openSprints()")));
+        }
+
+        if let Some(statuses) = &config.show_ticket_status {
+            if !statuses.is_empty() {
+                condition = condition.and(Jql::is_in("status", JqlValue::list(statuses)));
+            }
+        }
+
+        Self::new(condition)
+    }
+}
+
+impl fmt::Display for JqlQuery {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut parts = vec![];
+        if let Some(condition) = &self.condition {
+            parts.push(condition.to_string());
+        }
+        if !self.order_by.is_empty() {
+            let order_by: Vec<String> = self
+                .order_by
+                .iter()
+                .map(|(field, order)| match order {
+                    SortOrder::Asc => format!("{} ASC", quote_field(field)),
+                    SortOrder::Desc => format!("{} DESC", quote_field(field)),
+                })
+                .collect();
+            parts.push(format!("ORDER BY {}", order_by.join(", ")));
+        }
+        write!(f, "{}", parts.join(" "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NONE: Option<&[&str]> = None;
+    const EMPTY: Option<&[&str]> = Some(&[]);
+    const TODO: Option<&[&str]> = Some(&["To Do"]);
+    const REVIEW: Option<&[&str]> = Some(&["In \"Review\"", "Done"]);
+
+    // ProjectTickets is current_sprint_tickets_only, current_user_tickets_only,
+    // show_unassgined, show_ticket_status and the expected query
+    type ProjectTickets = (
+        Option<bool>,
+        Option<bool>,
+        Option<bool>,
+        Option<&'static [&'static str]>,
+        &'static str,
+    );
+
+    // PROJECT_TICKETS is every combination of the [tickets] configuration
+    #[rustfmt::skip]
+    const PROJECT_TICKETS: &[ProjectTickets] = &[
+        (None,        None,        None,        NONE,   r#"project = "ABC""#),
+        (None,        None,        None,        EMPTY,  r#"project = "ABC""#),
+        (None,        None,        None,        TODO,   r#"project = "ABC" AND status IN ("To Do")"#),
+        (None,        None,        None,        REVIEW, r#"project = "ABC" AND status IN ("In \"Review\"", "Done")"#),
+        (None,        None,        Some(false), NONE,   r#"project = "ABC" AND assignee IS NOT EMPTY"#),
+        (None,        None,        Some(false), EMPTY,  r#"project = "ABC" AND assignee IS NOT EMPTY"#),
+        (None,        None,        Some(false), TODO,   r#"project = "ABC" AND assignee IS NOT EMPTY AND status IN ("To Do")"#),
+        (None,        None,        Some(false), REVIEW, r#"project = "ABC" AND assignee IS NOT EMPTY AND status IN ("In \"Review\"", "Done")"#),
+        (None,        None,        Some(true),  NONE,   r#"project = "ABC""#),
+        (None,        None,        Some(true),  EMPTY,  r#"project = "ABC""#),
+        (None,        None,        Some(true),  TODO,   r#"project = "ABC" AND status IN ("To Do")"#),
+        (None,        None,        Some(true),  REVIEW, r#"project = "ABC" AND status IN ("In \"Review\"", "Done")"#),
+        (None,        Some(false), None,        NONE,   r#"project = "ABC""#),
+        (None,        Some(false), None,        EMPTY,  r#"project = "ABC""#),
+        (None,        Some(false), None,        TODO,   r#"project = "ABC" AND status IN ("To Do")"#),
+        (None,        Some(false), None,        REVIEW, r#"project = "ABC" AND status IN ("In \"Review\"", "Done")"#),
+        (None,        Some(false), Some(false), NONE,   r#"project = "ABC" AND assignee IS NOT EMPTY"#),
+        (None,        Some(false), Some(false), EMPTY,  r#"project = "ABC" AND assignee IS NOT EMPTY"#),
+        (None,        Some(false), Some(false), TODO,   r#"project = "ABC" AND assignee IS NOT EMPTY AND status IN ("To Do")"#),
+        (None,        Some(false), Some(false), REVIEW, r#"project = "ABC" AND assignee IS NOT EMPTY AND status IN ("In \"Review\"", "Done")"#),
+        (None,        Some(false), Some(true),  NONE,   r#"project = "ABC""#),
+        (None,        Some(false), Some(true),  EMPTY,  r#"project = "ABC""#),
+        (None,        Some(false), Some(true),  TODO,   r#"project = "ABC" AND status IN ("To Do")"#),
+        (None,        Some(false), Some(true),  REVIEW, r#"project = "ABC" AND status IN ("In \"Review\"", "Done")"#),
+        (None,        Some(true),  None,        NONE,   r#"project = "ABC" AND assignee = currentUser()"#),
+        (None,        Some(true),  None,        EMPTY,  r#"project = "ABC" AND assignee = currentUser()"#),
+        (None,        Some(true),  None,        TODO,   r#"project = "ABC" AND assignee = currentUser() AND status IN ("To Do")"#),
+        (None,        Some(true),  None,        REVIEW, r#"project = "ABC" AND assignee = currentUser() AND status IN ("In \"Review\"", "Done")"#),
+        (None,        Some(true),  Some(false), NONE,   r#"project = "ABC" AND assignee = currentUser()"#),
+        (None,        Some(true),  Some(false), EMPTY,  r#"project = "ABC" AND assignee = currentUser()"#),
+        (None,        Some(true),  Some(false), TODO,   r#"project = "ABC" AND assignee = currentUser() AND status IN ("To Do")"#),
+        (None,        Some(true),  Some(false), REVIEW, r#"project = "ABC" AND assignee = currentUser() AND status IN ("In \"Review\"", "Done")"#),
+        (None,        Some(true),  Some(true),  NONE,   r#"project = "ABC" AND (assignee = currentUser() OR assignee IS EMPTY)"#),
+        (None,        Some(true),  Some(true),  EMPTY,  r#"project = "ABC" AND (assignee = currentUser() OR assignee IS EMPTY)"#),
+        (None,        Some(true),  Some(true),  TODO,   r#"project = "ABC" AND (assignee = currentUser() OR assignee IS EMPTY) AND status IN ("To Do")"#),
+        (None,        Some(true),  Some(true),  REVIEW, r#"project = "ABC" AND (assignee = currentUser() OR assignee IS EMPTY) AND status IN ("In \"Review\"", "Done")"#),
+        (Some(false), None,        None,        NONE,   r#"project = "ABC""#),
+        (Some(false), None,        None,        EMPTY,  r#"project = "ABC""#),
+        (Some(false), None,        None,        TODO,   r#"project = "ABC" AND status IN ("To Do")"#),
+        (Some(false), None,        None,        REVIEW, r#"project = "ABC" AND status IN ("In \"Review\"", "Done")"#),
+        (Some(false), None,        Some(false), NONE,   r#"project = "ABC" AND assignee IS NOT EMPTY"#),
+        (Some(false), None,        Some(false), EMPTY,  r#"project = "ABC" AND assignee IS NOT EMPTY"#),
+        (Some(false), None,        Some(false), TODO,   r#"project = "ABC" AND assignee IS NOT EMPTY AND status IN ("To Do")"#),
+        (Some(false), None,        Some(false), REVIEW, r#"project = "ABC" AND assignee IS NOT EMPTY AND status IN ("In \"Review\"", "Done")"#),
+        (Some(false), None,        Some(true),  NONE,   r#"project = "ABC""#),
+        (Some(false), None,        Some(true),  EMPTY,  r#"project = "ABC""#),
+        (Some(false), None,        Some(true),  TODO,   r#"project = "ABC" AND status IN ("To Do")"#),
+        (Some(false), None,        Some(true),  REVIEW, r#"project = "ABC" AND status IN ("In \"Review\"", "Done")"#),
+        (Some(false), Some(false), None,        NONE,   r#"project = "ABC""#),
+        (Some(false), Some(false), None,        EMPTY,  r#"project = "ABC""#),
+        (Some(false), Some(false), None,        TODO,   r#"project = "ABC" AND status IN ("To Do")"#),
+        (Some(false), Some(false), None,        REVIEW, r#"project = "ABC" AND status IN ("In \"Review\"", "Done")"#),
+        (Some(false), Some(false), Some(false), NONE,   r#"project = "ABC" AND assignee IS NOT EMPTY"#),
+        (Some(false), Some(false), Some(false), EMPTY,  r#"project = "ABC" AND assignee IS NOT EMPTY"#),
+        (Some(false), Some(false), Some(false), TODO,   r#"project = "ABC" AND assignee IS NOT EMPTY AND status IN ("To Do")"#),
+        (Some(false), Some(false), Some(false), REVIEW, r#"project = "ABC" AND assignee IS NOT EMPTY AND status IN ("In \"Review\"", "Done")"#),
+        (Some(false), Some(false), Some(true),  NONE,   r#"project = "ABC""#),
+        (Some(false), Some(false), Some(true),  EMPTY,  r#"project = "ABC""#),
+        (Some(false), Some(false), Some(true),  TODO,   r#"project = "ABC" AND status IN ("To Do")"#),
+        (Some(false), Some(false), Some(true),  REVIEW, r#"project = "ABC" AND status IN ("In \"Review\"", "Done")"#),
+        (Some(false), Some(true),  None,        NONE,   r#"project = "ABC" AND assignee = currentUser()"#),
+        (Some(false), Some(true),  None,        EMPTY,  r#"project = "ABC" AND assignee = currentUser()"#),
+        (Some(false), Some(true),  None,        TODO,   r#"project = "ABC" AND assignee = currentUser() AND status IN ("To Do")"#),
+        (Some(false), Some(true),  None,        REVIEW, r#"project = "ABC" AND assignee = currentUser() AND status IN ("In \"Review\"", "Done")"#),
+        (Some(false), Some(true),  Some(false), NONE,   r#"project = "ABC" AND assignee = currentUser()"#),
+        (Some(false), Some(true),  Some(false), EMPTY,  r#"project = "ABC" AND assignee = currentUser()"#),
+        (Some(false), Some(true),  Some(false), TODO,   r#"project = "ABC" AND assignee = currentUser() AND status IN ("To Do")"#),
+        (Some(false), Some(true),  Some(false), REVIEW, r#"project = "ABC" AND assignee = currentUser() AND status IN ("In \"Review\"", "Done")"#),
+        (Some(false), Some(true),  Some(true),  NONE,   r#"project = "ABC" AND (assignee = currentUser() OR assignee IS EMPTY)"#),
+        (Some(false), Some(true),  Some(true),  EMPTY,  r#"project = "ABC" AND (assignee = currentUser() OR assignee IS EMPTY)"#),
+        (Some(false), Some(true),  Some(true),  TODO,   r#"project = "ABC" AND (assignee = currentUser() OR assignee IS EMPTY) AND status IN ("To Do")"#),
+        (Some(false), Some(true),  Some(true),  REVIEW, r#"project = "ABC" AND (assignee = currentUser() OR assignee IS EMPTY) AND status IN ("In \"Review\"", "Done")"#),
+        (Some(true),  None,        None,        NONE,   r#"project = "ABC" AND sprint IN openSprints()"#),
+        (Some(true),  None,        None,        EMPTY,  r#"project = "ABC" AND sprint IN openSprints()"#),
+        (Some(true),  None,        None,        TODO,   r#"project = "ABC" AND sprint IN openSprints() AND status IN ("To Do")"#),
+        (Some(true),  None,        None,        REVIEW, r#"project = "ABC" AND sprint IN openSprints() AND status IN ("In \"Review\"", "Done")"#),
+        (Some(true),  None,        Some(false), NONE,   r#"project = "ABC" AND assignee IS NOT EMPTY AND sprint IN openSprints()"#),
+        (Some(true),  None,        Some(false), EMPTY,  r#"project = "ABC" AND assignee IS NOT EMPTY AND sprint IN openSprints()"#),
+        (Some(true),  None,        Some(false), TODO,   r#"project = "ABC" AND assignee IS NOT EMPTY AND sprint IN openSprints() AND status IN ("To Do")"#),
+        (Some(true),  None,        Some(false), REVIEW, r#"project = "ABC" AND assignee IS NOT EMPTY AND sprint IN openSprints() AND status IN ("In \"Review\"", "Done")"#),
+        (Some(true),  None,        Some(true),  NONE,   r#"project = "ABC" AND sprint IN openSprints()"#),
+        (Some(true),  None,        Some(true),  EMPTY,  r#"project = "ABC" AND sprint IN openSprints()"#),
+        (Some(true),  None,        Some(true),  TODO,   r#"project = "ABC" AND sprint IN openSprints() AND status IN ("To Do")"#),
+        (Some(true),  None,        Some(true),  REVIEW, r#"project = "ABC" AND sprint IN openSprints() AND status IN ("In \"Review\"", "Done")"#),
+        (Some(true),  Some(false), None,        NONE,   r#"project = "ABC" AND sprint IN openSprints()"#),
+        (Some(true),  Some(false), None,        EMPTY,  r#"project = "ABC" AND sprint IN openSprints()"#),
+        (Some(true),  Some(false), None,        TODO,   r#"project = "ABC" AND sprint IN openSprints() AND status IN ("To Do")"#),
+        (Some(true),  Some(false), None,        REVIEW, r#"project = "ABC" AND sprint IN openSprints() AND status IN ("In \"Review\"", "Done")"#),
+        (Some(true),  Some(false), Some(false), NONE,   r#"project = "ABC" AND assignee IS NOT EMPTY AND sprint IN openSprints()"#),
+        (Some(true),  Some(false), Some(false), EMPTY,  r#"project = "ABC" AND assignee IS NOT EMPTY AND sprint IN openSprints()"#),
+        (Some(true),  Some(false), Some(false), TODO,   r#"project = "ABC" AND assignee IS NOT EMPTY AND sprint IN openSprints() AND status IN ("To Do")"#),
+        (Some(true),  Some(false), Some(false), REVIEW, r#"project = "ABC" AND assignee IS NOT EMPTY AND sprint IN openSprints() AND status IN ("In \"Review\"", "Done")"#),
+        (Some(true),  Some(false), Some(true),  NONE,   r#"project = "ABC" AND sprint IN openSprints()"#),
+        (Some(true),  Some(false), Some(true),  EMPTY,  r#"project = "ABC" AND sprint IN openSprints()"#),
+        (Some(true),  Some(false), Some(true),  TODO,   r#"project = "ABC" AND sprint IN openSprints() AND status IN ("To Do")"#),
+        (Some(true),  Some(false), Some(true),  REVIEW, r#"project = "ABC" AND sprint IN openSprints() AND status IN ("In \"Review\"", "Done")"#),
+        (Some(true),  Some(true),  None,        NONE,   r#"project = "ABC" AND assignee = currentUser() AND sprint IN openSprints()"#),
+        (Some(true),  Some(true),  None,        EMPTY,  r#"project = "ABC" AND assignee = currentUser() AND sprint IN openSprints()"#),
+        (Some(true),  Some(true),  None,        TODO,   r#"project = "ABC" AND assignee = currentUser() AND sprint IN openSprints() AND status IN ("To Do")"#),
+        (Some(true),  Some(true),  None,        REVIEW, r#"project = "ABC" AND assignee = currentUser() AND sprint IN openSprints() AND status IN ("In \"Review\"", "Done")"#),
+        (Some(true),  Some(true),  Some(false), NONE,   r#"project = "ABC" AND assignee = currentUser() AND sprint IN openSprints()"#),
+        (Some(true),  Some(true),  Some(false), EMPTY,  r#"project = "ABC" AND assignee = currentUser() AND sprint IN openSprints()"#),
+        (Some(true),  Some(true),  Some(false), TODO,   r#"project = "ABC" AND assignee = currentUser() AND sprint IN openSprints() AND status IN ("To Do")"#),
+        (Some(true),  Some(true),  Some(false), REVIEW, r#"project = "ABC" AND assignee = currentUser() AND sprint IN openSprints() AND status IN ("In \"Review\"", "Done")"#),
+        (Some(true),  Some(true),  Some(true),  NONE,   r#"project = "ABC" AND (assignee = currentUser() OR assignee IS EMPTY) AND sprint IN openSprints()"#),
+        (Some(true),  Some(true),  Some(true),  EMPTY,  r#"project = "ABC" AND (assignee = currentUser() OR assignee IS EMPTY) AND sprint IN openSprints()"#),
+        (Some(true),  Some(true),  Some(true),  TODO,   r#"project = "ABC" AND (assignee = currentUser() OR assignee IS EMPTY) AND sprint IN openSprints() AND status IN ("To Do")"#),
+        (Some(true),  Some(true),  Some(true),  REVIEW, r#"project = "ABC" AND (assignee = currentUser() OR assignee IS EMPTY) AND sprint IN openSprints() AND status IN ("In \"Review\"", "Done")"#),
+    ];
+
+    #[test]
+    fn quotes_values_and_fields() {
+        let jql = Jql::equals("summary", r#"say "hi" \ bye"#);
+        assert_eq!(jql.to_string(), r#"summary = "say \"hi\" \\ bye""#);
+
+        let jql = Jql::equals("Story Points", "3");
+        assert_eq!(jql.to_string(), r#""Story Points" = "3""#);
+
+        let jql = Jql::equals("cf[10020]", "x");
+        assert_eq!(jql.to_string(), r#"cf[10020] = "x""#);
+    }
+
+    #[test]
+    fn groups() {
+        let jql = Jql::equals("project", "ABC")
+            .and(Jql::equals("status", "Done").or(Jql::is_empty("resolution")))
+            .and(Jql::clause("labels", JqlOperator::NotEquals, "ops"));
+        assert_eq!(
+            jql.to_string(),
+            r#"project = "ABC" AND (status = "Done" OR resolution IS EMPTY) AND labels != "ops""#
+        );
+
+        let jql = Jql::equals("a", "1")
+            .or(Jql::equals("b", "2").and(Jql::equals("c", "3")))
+            .and(Jql::equals("d", "4"));
+        assert_eq!(
+            jql.to_string(),
+            r#"(a = "1" OR (b = "2" AND c = "3")) AND d = "4""#
+        );
+    }
+
+    #[test]
+    fn comparison_and_text_operators() {
+        let jql =
+            Jql::clause("updated", JqlOperator::GreaterThanOrEquals, "-14d").and(Jql::clause(
+                "comment",
+                JqlOperator::Contains,
+                JqlValue::function("currentUser()"),
+            ));
+        assert_eq!(
+            jql.to_string(),
+            r#"updated >= "-14d" AND comment ~ currentUser()"#
+        );
+    }
+
+    #[test]
+    fn order_by() {
+        let query = JqlQuery::new(Jql::equals("parent", "ABC-1"))
+            .order_by("status", SortOrder::Asc)
+            .order_by("created", SortOrder::Desc);
+        assert_eq!(
+            query.to_string(),
+            r#"parent = "ABC-1" ORDER BY status ASC, created DESC"#
+        );
+        let query = JqlQuery::default().order_by("Rank", SortOrder::Asc);
+        assert_eq!(query.to_string(), "ORDER BY Rank ASC");
+    }
+
+    #[test]
+    fn project_tickets_for_every_config_combination() {
+        for &(sprint, user, unassigned, statuses, expected) in PROJECT_TICKETS {
+            let config = JiraConfigTickets {
+                current_sprint_tickets_only: sprint,
+                current_user_tickets_only: user,
+                show_unassgined: unassigned,
+                show_ticket_status: statuses.map(|s| s.iter().map(|s| s.to_string()).collect()),
+            };
+            let query = JqlQuery::for_project_tickets("ABC", &config);
+            assert_eq!(query.to_string(), expected, "{config:?}");
+        }
+        assert_eq!(PROJECT_TICKETS.len(), 3 * 3 * 3 * 4);
+    }
+
+    #[test]
+    fn project_tickets_without_status_config() {
+        let query = JqlQuery::for_project_tickets("ABC", &JiraConfigTickets::default());
+        assert_eq!(query.to_string(), r#"project = "ABC""#);
+    }
+}
@@ -0,0 +1,104 @@
+use super::auth::JiraClient;
+use super::jql::{Jql, JqlOperator, JqlQuery, JqlValue, SortOrder};
+use super::tickets::{JiraTicketsAPI, TicketData};
+use super::TICKET_FIELDS;
+use futures::future::try_join_all;
+use log::debug;
+
+const MY_WORK_MAX_RESULTS: &str = "50";
+
+// MyWorkSection is one of the cross-project lists of the dashboard
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MyWorkSection {
+    Assigned,
+    Reported,
+    Watched,
+    Mentioned,
+}
+
+impl MyWorkSection {
+    pub const ALL: [MyWorkSection; 4] = [
+        Self::Assigned,
+        Self::Reported,
+        Self::Watched,
+        Self::Mentioned,
+    ];
+
+    pub fn title(self) -> &'static str {
+        match self {
+            Self::Assigned => "Assigned to me",
+            Self::Reported => "Reported by me",
+            Self::Watched => "Watched, updated in the last 14 days",
+            Self::Mentioned => "Mentioned me",
+        }
+    }
+
+    pub fn query(self) -> JqlQuery {
+        let current_user = || JqlValue::function("currentUser()");
+        let not_done = Jql::clause("statusCategory", JqlOperator::NotEquals, "Done");
+        let condition = match self {
+            Self::Assigned => Jql::equals("assignee", current_user()).and(not_done),
+            Self::Reported => Jql::equals("reporter", current_user()).and(not_done),
+            Self::Watched => Jql::equals("watcher", current_user()).and(Jql::clause(
+                "updated",
+                JqlOperator::GreaterThanOrEquals,
+                "-14d",
+            )),
+            Self::Mentioned => Jql::clause("comment", JqlOperator::Contains, current_user()),
+        };
+        JqlQuery::new(condition).order_by("updated", SortOrder::Desc)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct MyWorkList {
+    pub section: MyWorkSection,
+    pub jql: String,
+    pub total: u32,
+    pub tickets: Vec<TicketData>,
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct JiraMyWorkAPI {
+    pub lists: Vec<MyWorkList>,
+}
+
+impl JiraMyWorkAPI {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    async fn get_list(
+        jira_client: &JiraClient,
+        section: MyWorkSection,
+    ) -> anyhow::Result<MyWorkList> {
+        let jql = section.query().to_string();
+        let params = [
+            ("jql", jql.as_str()),
+            ("expand", "renderedFields"),
+            ("fields", TICKET_FIELDS),
+            ("maxResults", MY_WORK_MAX_RESULTS),
+        ];
+        let response = jira_client.search_jira_api(&params).await?;
+        let page: JiraTicketsAPI = serde_json::from_str(&response)?;
+        debug!("My work {:?}: {} tickets", section, page.total);
+        Ok(MyWorkList {
+            section,
+            jql,
+            total: page.total,
+            tickets: page.issues,
+        })
+    }
+
+    // get_my_work runs the searches of every section at the same time
+    pub async fn get_my_work(
+        &mut self,
+        jira_client: &JiraClient,
+    ) -> anyhow::Result<&Vec<MyWorkList>> {
+        let lists = MyWorkSection::ALL
+            .into_iter()
+            .map(|section| Self::get_list(jira_client, section));
+        self.lists = try_join_all(lists).await?;
+        Ok(&self.lists)
+    }
+}
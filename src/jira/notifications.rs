@@ -0,0 +1,432 @@
+use super::auth::JiraClient;
+use super::jql::{Jql, JqlOperator, JqlQuery, JqlValue, SortOrder};
+use super::tickets::{Assignee, CreatorReporter, Status, TicketData};
+use super::SurrealAny;
+use chrono::{DateTime, TimeZone, Utc};
+use log::debug;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::time::Duration;
+use tokio::sync::mpsc::UnboundedSender;
+
+pub const DEFAULT_POLL_INTERVAL_SECS: u64 = 120;
+const POLL_FIELDS: &str = "summary,status,assignee,comment";
+const POLL_MAX_RESULTS: &str = "100";
+const JIRA_DATE_FORMAT: &str = "%Y-%m-%dT%H:%M:%S%.f%z";
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+struct PollComment {
+    author: Option<CreatorReporter>,
+    created: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+struct PollComments {
+    comments: Vec<PollComment>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+struct PollFields {
+    assignee: Option<Assignee>,
+    comment: Option<PollComments>,
+    status: Status,
+    summary: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+struct PollIssue {
+    key: String,
+    fields: PollFields,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+struct PollSearch {
+    issues: Vec<PollIssue>,
+}
+
+// TicketSnapshot is what the poller last saw of a ticket that is not in the tickets cache
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct TicketSnapshot {
+    pub ticket_key: String,
+    pub assignee: Option<String>,
+    pub status: String,
+}
+
+impl TicketSnapshot {
+    fn from_cached(ticket: &TicketData) -> Self {
+        Self {
+            ticket_key: ticket.key.clone(),
+            assignee: ticket
+                .fields
+                .assignee
+                .as_ref()
+                .map(|a| a.display_name.clone()),
+            status: ticket.fields.status.name.clone(),
+        }
+    }
+
+    fn from_issue(issue: &PollIssue) -> Self {
+        Self {
+            ticket_key: issue.key.clone(),
+            assignee: issue
+                .fields
+                .assignee
+                .as_ref()
+                .map(|a| a.display_name.clone()),
+            status: issue.fields.status.name.clone(),
+        }
+    }
+}
+
+// NotificationSync is when a Jira instance was last polled, in milliseconds
+#[derive(Serialize, Deserialize, Debug, Clone)]
+struct NotificationSync {
+    last_sync: i64,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub enum NotificationKind {
+    NewComment {
+        author: String,
+    },
+    StatusChanged {
+        from: String,
+        to: String,
+    },
+    Reassigned {
+        from: Option<String>,
+        to: Option<String>,
+    },
+    Updated,
+}
+
+impl fmt::Display for NotificationKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let unassigned = |user: &Option<String>| user.clone().unwrap_or("Unassigned".to_string());
+        match self {
+            Self::NewComment { author } => write!(f, "New comment by {author}"),
+            Self::StatusChanged { from, to } => write!(f, "Status {from} -> {to}"),
+            Self::Reassigned { from, to } => {
+                write!(f, "Assignee {} -> {}", unassigned(from), unassigned(to))
+            }
+            Self::Updated => write!(f, "Updated"),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Notification {
+    pub notification_id: String,
+    pub ticket_key: String,
+    pub summary: String,
+    pub kind: NotificationKind,
+    pub created: i64,
+    pub read: bool,
+}
+
+// changes compares a polled ticket with what was known about it before. New
+// comments are the ones written by someone else since the last sync.
+fn changes(
+    issue: &PollIssue,
+    previous: Option<&TicketSnapshot>,
+    last_sync: DateTime<Utc>,
+    current_user: Option<&str>,
+) -> Vec<NotificationKind> {
+    let mut kinds = vec![];
+    let comments = issue.fields.comment.iter().flat_map(|c| c.comments.iter());
+    for comment in comments {
+        let Ok(created) = DateTime::parse_from_str(&comment.created, JIRA_DATE_FORMAT) else {
+            continue;
+        };
+        let author = comment.author.as_ref().map(|a| a.display_name.as_str());
+        if created.with_timezone(&Utc) >= last_sync && author != current_user {
+            kinds.push(NotificationKind::NewComment {
+                author: author.unwrap_or("Anonymous").to_string(),
+            });
+        }
+    }
+
+    let current = TicketSnapshot::from_issue(issue);
+    if let Some(previous) = previous {
+        if previous.status != current.status {
+            kinds.push(NotificationKind::StatusChanged {
+                from: previous.status.clone(),
+                to: current.status.clone(),
+            });
+        }
+        if previous.assignee != current.assignee {
+            kinds.push(NotificationKind::Reassigned {
+                from: previous.assignee.clone(),
+                to: current.assignee,
+            });
+        }
+    }
+
+    if kinds.is_empty() && previous.is_none() {
+        kinds.push(NotificationKind::Updated);
+    }
+    kinds
+}
+
+// NotificationPoller runs in the background and looks for changes on tickets
+// the user watches or is assigned to
+pub struct NotificationPoller {
+    client: JiraClient,
+    db: SurrealAny,
+    last_sync: DateTime<Utc>,
+    // restored is set once last_sync has been read back from the db
+    restored: bool,
+}
+
+impl NotificationPoller {
+    pub fn new(client: JiraClient, db: SurrealAny) -> Self {
+        Self {
+            client,
+            db,
+            last_sync: Utc::now(),
+            restored: false,
+        }
+    }
+
+    // restore_last_sync starts from the last poll of a previous run, so tickets
+    // changed while the app was closed are reported as well
+    async fn restore_last_sync(&mut self) -> anyhow::Result<()> {
+        if self.restored {
+            return Ok(());
+        }
+        let stored: Option<NotificationSync> = self
+            .db
+            .select(("notification_sync", self.client.get_domain()))
+            .await?;
+        if let Some(last_sync) = stored.and_then(|s| Utc.timestamp_millis_opt(s.last_sync).single())
+        {
+            debug!("Notifications since {last_sync}");
+            self.last_sync = last_sync;
+        }
+        self.restored = true;
+        Ok(())
+    }
+
+    async fn save_last_sync(&mut self, last_sync: DateTime<Utc>) -> anyhow::Result<()> {
+        let _sync: Option<NotificationSync> = self
+            .db
+            .update(("notification_sync", self.client.get_domain()))
+            .content(NotificationSync {
+                last_sync: last_sync.timestamp_millis(),
+            })
+            .await?;
+        self.last_sync = last_sync;
+        Ok(())
+    }
+
+    // query uses a relative date, an absolute one would be read in the
+    // timezone of the Jira profile instead of UTC
+    fn query(&self) -> String {
+        let minutes = (Utc::now() - self.last_sync).num_minutes() + 1;
+        let current_user = || JqlValue::function("currentUser()");
+        let condition = Jql::equals("watcher", current_user())
+            .or(Jql::equals("assignee", current_user()))
+            .and(Jql::clause(
+                "updated",
+                JqlOperator::GreaterThanOrEquals,
+                format!("-{minutes}m"),
+            ));
+        JqlQuery::new(condition)
+            .order_by("updated", SortOrder::Desc)
+            .to_string()
+    }
+
+    // previous is what was known of the ticket before this poll. A tickets cache
+    // entry already matching Jira means the change was made or seen in the app,
+    // otherwise the last poller snapshot wins over an older cache entry.
+    async fn previous(&self, issue: &PollIssue) -> anyhow::Result<Option<TicketSnapshot>> {
+        let cached: Option<TicketData> = self.db.select(("tickets", &issue.key)).await?;
+        let cached = cached.map(|ticket| TicketSnapshot::from_cached(&ticket));
+        if cached.as_ref() == Some(&TicketSnapshot::from_issue(issue)) {
+            return Ok(cached);
+        }
+        let snapshot: Option<TicketSnapshot> =
+            self.db.select(("ticket_snapshots", &issue.key)).await?;
+        Ok(snapshot.or(cached))
+    }
+
+    pub async fn poll(&mut self) -> anyhow::Result<Vec<Notification>> {
+        self.restore_last_sync().await?;
+        let sync_started = Utc::now();
+        let jql = self.query();
+        let params = [
+            ("jql", jql.as_str()),
+            ("fields", POLL_FIELDS),
+            ("maxResults", POLL_MAX_RESULTS),
+        ];
+        let response = self.client.search_jira_api(&params).await?;
+        let search: PollSearch = serde_json::from_str(&response)?;
+        debug!("Notification poll {jql}: {} tickets", search.issues.len());
+
+        let current_user = self.client.user.as_ref().map(|u| u.display_name.clone());
+        let mut notifications = vec![];
+        for issue in search.issues {
+            let previous = self.previous(&issue).await?;
+            let kinds = changes(
+                &issue,
+                previous.as_ref(),
+                self.last_sync,
+                current_user.as_deref(),
+            );
+            for (index, kind) in kinds.into_iter().enumerate() {
+                let notification = Notification {
+                    notification_id: format!(
+                        "{}_{}_{index}",
+                        issue.key,
+                        sync_started.timestamp_millis()
+                    ),
+                    ticket_key: issue.key.clone(),
+                    summary: issue.fields.summary.clone(),
+                    kind,
+                    created: sync_started.timestamp(),
+                    read: false,
+                };
+                let _notification: Option<Notification> = self
+                    .db
+                    .update(("notifications", &notification.notification_id))
+                    .content(notification.clone())
+                    .await?;
+                notifications.push(notification);
+            }
+            let _snapshot: Option<TicketSnapshot> = self
+                .db
+                .update(("ticket_snapshots", &issue.key))
+                .content(TicketSnapshot::from_issue(&issue))
+                .await?;
+        }
+        self.save_last_sync(sync_started).await?;
+        Ok(notifications)
+    }
+
+    // spawn polls every interval until the app side of the channel is dropped
+    pub fn spawn(mut self, interval: Duration, tx: UnboundedSender<Vec<Notification>>) {
+        tokio::spawn(async move {
+            loop {
+                tokio::time::sleep(interval).await;
+                match self.poll().await {
+                    Ok(notifications) if notifications.is_empty() => {}
+                    Ok(notifications) => {
+                        if tx.send(notifications).is_err() {
+                            return;
+                        }
+                    }
+                    Err(e) => debug!("Notification poll failed: {e}"),
+                }
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn issue(status: &str, assignee: Option<&str>, comments: &[(&str, &str)]) -> PollIssue {
+        PollIssue {
+            key: "ABC-1".to_string(),
+            fields: PollFields {
+                assignee: assignee.map(|name| Assignee {
+                    display_name: name.to_string(),
+                }),
+                comment: Some(PollComments {
+                    comments: comments
+                        .iter()
+                        .map(|(author, created)| PollComment {
+                            author: Some(CreatorReporter {
+                                display_name: author.to_string(),
+                            }),
+                            created: created.to_string(),
+                        })
+                        .collect(),
+                }),
+                status: Status {
+                    id: None,
+                    name: status.to_string(),
+                    status_category: None,
+                },
+                summary: "Summary".to_string(),
+            },
+        }
+    }
+
+    fn last_sync() -> DateTime<Utc> {
+        DateTime::parse_from_str("2024-05-01T09:00:00.000+0000", JIRA_DATE_FORMAT)
+            .unwrap()
+            .with_timezone(&Utc)
+    }
+
+    #[test]
+    fn reports_status_assignee_and_new_comments() {
+        let previous = TicketSnapshot {
+            ticket_key: "ABC-1".to_string(),
+            assignee: None,
+            status: "To Do".to_string(),
+        };
+        let issue = issue(
+            "In Progress",
+            Some("Ann"),
+            &[
+                ("Bob", "2024-05-01T08:00:00.000+0000"),
+                ("Bob", "2024-05-01T09:30:00.000+0000"),
+                ("Me", "2024-05-01T09:45:00.000+0000"),
+            ],
+        );
+        let kinds = changes(&issue, Some(&previous), last_sync(), Some("Me"));
+        assert_eq!(
+            kinds,
+            vec![
+                NotificationKind::NewComment {
+                    author: "Bob".to_string()
+                },
+                NotificationKind::StatusChanged {
+                    from: "To Do".to_string(),
+                    to: "In Progress".to_string()
+                },
+                NotificationKind::Reassigned {
+                    from: None,
+                    to: Some("Ann".to_string())
+                },
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn last_sync_survives_a_restart() {
+        let db = surrealdb::engine::any::connect("mem://").await.unwrap();
+        db.use_ns("noc").use_db("database").await.unwrap();
+        let client = JiraClient {
+            agile_url: String::new(),
+            api_key: String::new(),
+            api_version: "3".to_string(),
+            email: String::new(),
+            url: "https://example.atlassian.net/rest/api/3".to_string(),
+            user: None,
+        };
+
+        let mut poller = NotificationPoller::new(client.clone(), db.clone());
+        poller.restore_last_sync().await.unwrap();
+        assert!(poller.last_sync > last_sync());
+        poller.save_last_sync(last_sync()).await.unwrap();
+
+        let mut poller = NotificationPoller::new(client, db);
+        poller.restore_last_sync().await.unwrap();
+        assert_eq!(poller.last_sync, last_sync());
+    }
+
+    #[test]
+    fn unknown_tickets_are_reported_as_updated() {
+        let issue = issue("To Do", None, &[]);
+        assert_eq!(
+            changes(&issue, None, last_sync(), None),
+            vec![NotificationKind::Updated]
+        );
+
+        let previous = TicketSnapshot::from_issue(&issue);
+        assert!(changes(&issue, Some(&previous), last_sync(), None).is_empty());
+    }
+}
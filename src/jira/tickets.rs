@@ -1,6 +1,7 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 use super::auth::JiraClient;
+use super::changelog::Changelog;
 use super::SurrealAny;
 use htmltoadf::convert_html_str_to_adf_str;
 use log::debug;
@@ -64,8 +65,27 @@ pub struct ProjectDetails {
     pub name: String,
 }
 #[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct StatusCategory {
+    pub key: String,
+    pub name: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
 pub struct Status {
+    pub id: Option<String>,
     pub name: String,
+    pub status_category: Option<StatusCategory>,
+}
+
+impl Status {
+    // category_key is one of "new", "indeterminate" or "done"
+    pub fn category_key(&self) -> &str {
+        match &self.status_category {
+            Some(category) => category.key.as_str(),
+            None => "",
+        }
+    }
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -107,6 +127,7 @@ pub struct Fields {
     pub assignee: Option<Assignee>,
     pub comments: Option<Comments>,
     pub components: Vec<Components>,
+    pub created: Option<String>,
     pub creator: Option<CreatorReporter>,
     pub issuelinks: Vec<Links>,
     pub issuetype: TicketType,
@@ -116,12 +137,19 @@ pub struct Fields {
     pub project: ProjectDetails,
     pub reporter: Option<CreatorReporter>,
     pub status: Status,
+    #[serde(default)]
+    pub subtasks: Vec<LinkInwardOutwardParent>,
     pub summary: String,
+    pub updated: Option<String>,
+    // custom_fields holds every other requested field, keyed by field id
+    #[serde(flatten)]
+    pub custom_fields: BTreeMap<String, serde_json::Value>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct TicketData {
+    pub changelog: Option<Changelog>,
     pub fields: Fields,
     pub key: String,
     pub rendered_fields: RenderedFields,
@@ -170,6 +198,8 @@ pub struct TicketTransition {
     pub name: Option<String>,
     pub has_screen: Option<bool>,
     pub fields: Option<CustomField>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub to: Option<Status>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -218,6 +248,28 @@ impl TicketData {
         }
     }
 
+    pub async fn get_changelog(
+        &self,
+        db: &SurrealAny,
+        jira_client: &JiraClient,
+    ) -> anyhow::Result<Changelog> {
+        let ticket: Option<TicketData> = db.select(("tickets", &self.key)).await?;
+        let Some(ticket) = ticket else {
+            return Changelog::from_jira_api(&self.key, jira_client).await;
+        };
+        if let Some(changelog) = ticket.changelog {
+            debug!("Changelog for {} found on cache", self.key);
+            return Ok(changelog);
+        }
+
+        let changelog = Changelog::from_jira_api(&self.key, jira_client).await?;
+        let _db_update: Option<TicketData> = db
+            .update(("tickets", &self.key))
+            .merge(json!({ "changelog": changelog }))
+            .await?;
+        Ok(changelog)
+    }
+
     pub async fn add_comment(
         &self,
         db: &SurrealAny,
@@ -225,15 +277,10 @@ impl TicketData {
         jira_client: &JiraClient,
     ) -> anyhow::Result<CommentBody> {
         let url = format!("/issue/{}/comment?expand=renderedBody", self.key);
-        let html = markdown::to_html(comment);
-        let adf = convert_html_str_to_adf_str(html);
-        let adf = format!("{{ \"body\": {} }}", adf);
         let response = jira_client
-            .post_to_jira_api(&url, adf)
-            .await
-            .expect("unable to save comment");
-        let comments: CommentBody =
-            serde_json::from_str(response.as_str()).expect("unable to deserialize comments");
+            .post_to_jira_api(&url, comment_body(comment))
+            .await?;
+        let comments: CommentBody = serde_json::from_str(response.as_str())?;
 
         let _db_update: TicketData = db
             .update(("tickets", &self.key))
@@ -266,58 +313,29 @@ impl TicketData {
     }
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+// markdown_to_adf converts markdown text to the document format of Jira rich text fields
+pub fn markdown_to_adf(text: &str) -> String {
+    let html = markdown::to_html(text);
+    convert_html_str_to_adf_str(html)
+}
+
+// comment_body converts a markdown comment to the ADF body Jira expects
+pub fn comment_body(comment: &str) -> String {
+    format!("{{ \"body\": {} }}", markdown_to_adf(comment))
+}
+
+// CreateTicket holds the create form, the typed values are kept by field id
+// so they survive switching the issue type
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct CreateTicket {
-    pub description: String,
-    pub summary: String,
     pub ticket_types: Vec<TicketType>,
+    pub values: BTreeMap<String, String>,
 }
 
 impl CreateTicket {
     pub fn new() -> Self {
-        Self {
-            description: String::new(),
-            summary: String::new(),
-            ticket_types: vec![],
-        }
-    }
-
-    // transcode_jira returns serde_json string version of struct
-    // it is ready to be sent to jira
-    pub fn transcode_jira(
-        &self,
-        ticket_type_id: String,
-        project_id: String,
-        user_id: String,
-    ) -> anyhow::Result<String> {
-        let data = json!({
-            "fields": {
-                "assignee": {
-                    "id": user_id
-                },
-                "description": {
-                    "content": [{
-                        "content": [{
-                            "text": self.description,
-                            "type": "text"
-                        }],
-                        "type": "paragraph"
-                    }],
-                    "type": "doc",
-                    "version": 1
-                },
-                "issuetype": {
-                    "id": ticket_type_id
-                },
-                "project": {
-                    "id": project_id
-                },
-                "summary": self.summary
-            }
-        });
-
-        Ok(serde_json::to_string(&data)?)
+        Self::default()
     }
 }
 
@@ -376,32 +394,4 @@ impl JiraTicketsAPI {
         let obj: Vec<TicketType> = serde_json::from_str(&response)?;
         Ok(obj)
     }
-
-    // create_ticket_api posts to JIRA API to create ticket
-    pub async fn create_ticket_api(
-        &self,
-        jira_client: &JiraClient,
-        create_ticket_data: CreateTicket,
-        ticket_type_id_index: usize,
-        project_id: &str,
-    ) -> anyhow::Result<()> {
-        let user_data = jira_client.user.clone();
-        let user_id = match user_data {
-            Some(data) => data.account_id,
-            None => {
-                debug!("Unable to locate user data");
-                String::new()
-            }
-        };
-        let url = String::from("/issue");
-        let ticket_type_id = &create_ticket_data.ticket_types[ticket_type_id_index].id;
-        let data = create_ticket_data.transcode_jira(
-            ticket_type_id.to_string(),
-            project_id.to_string(),
-            user_id,
-        )?;
-        debug!("{:#?}", data);
-        jira_client.post_to_jira_api(&url, data).await?;
-        Ok(())
-    }
 }
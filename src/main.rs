@@ -1,7 +1,10 @@
 mod app;
 mod config;
+mod editor;
 mod events;
 mod jira;
+mod templates;
+mod theme;
 mod widgets;
 
 // mod log;
@@ -10,6 +13,7 @@ use crate::events::{Event, Events};
 use app::App;
 use crossterm::{
     cursor,
+    event::{DisableBracketedPaste, DisableMouseCapture, EnableBracketedPaste, EnableMouseCapture},
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
     ExecutableCommand,
 };
@@ -46,18 +50,40 @@ async fn main() -> anyhow::Result<()> {
             }
         })?;
         match events.next()? {
-            Event::Input(key) => match app.event(key).await {
-                Ok(state) => {
-                    if !state.is_consumed()
-                        && (key == app.config.key_config.quit || key == app.config.key_config.exit)
-                    {
-                        break;
-                    }
+            Event::Input(key) => {
+                if let Err(err) = app.event(key).await {
+                    app.error.set(err.to_string())?
                 }
-                Err(err) => app.error.set(err.to_string())?,
-                // Err(_err) => {}
-            },
-            Event::Tick => (),
+                if app.quit {
+                    break;
+                }
+            }
+            Event::Paste(text) => {
+                if let Err(err) = app.paste(&text) {
+                    app.error.set(err.to_string())?
+                }
+            }
+            Event::Mouse(mouse) => {
+                if let Err(err) = app.mouse(mouse).await {
+                    app.error.set(err.to_string())?
+                }
+            }
+            Event::Tick => {
+                if let Err(err) = app.tick().await {
+                    app.error.set(err.to_string())?
+                }
+            }
+        }
+        if let Some(request) = app.editor_request.take() {
+            events.pause();
+            shutdown_terminal();
+            let edited = editor::edit(&request.text);
+            setup_terminal()?;
+            terminal.clear()?;
+            events.resume();
+            if let Err(err) = app.editor_finished(request.target, edited).await {
+                app.error.set(err.to_string())?
+            }
         }
     }
 
@@ -69,10 +95,24 @@ async fn main() -> anyhow::Result<()> {
 fn setup_terminal() -> anyhow::Result<()> {
     enable_raw_mode()?;
     io::stdout().execute(EnterAlternateScreen)?;
+    io::stdout().execute(EnableBracketedPaste)?;
+    io::stdout().execute(EnableMouseCapture)?;
     Ok(())
 }
 
 fn shutdown_terminal() {
+    let disable_mouse = io::stdout().execute(DisableMouseCapture).map(|_| ());
+
+    if let Err(e) = disable_mouse {
+        eprintln!("disable_mouse failed:\n{}", e);
+    }
+
+    let disable_paste = io::stdout().execute(DisableBracketedPaste).map(|_| ());
+
+    if let Err(e) = disable_paste {
+        eprintln!("disable_paste failed:\n{}", e);
+    }
+
     let leave_screen = io::stdout().execute(LeaveAlternateScreen).map(|_f| ());
 
     if let Err(e) = leave_screen {
@@ -0,0 +1,276 @@
+use std::{collections::BTreeMap, fs, path::Path};
+
+use chrono::Local;
+use serde::{Deserialize, Serialize};
+
+use crate::config::config_dir;
+
+// TicketTemplate pre-fills the create ticket form. Templates are read from
+// <config dir>/templates, either as .toml files or as .md files with a TOML
+// frontmatter between `---` or `+++` lines and the description as body.
+// The same document is used to write a ticket in $EDITOR.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+pub struct TicketTemplate {
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub name: String,
+    #[serde(rename = "type")]
+    pub ticket_type: Option<String>,
+    pub summary: Option<String>,
+    #[serde(skip_serializing)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub labels: Vec<String>,
+    #[serde(default)]
+    pub components: Vec<String>,
+    // fields are other values of the form by field id, like customfield_10016
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub fields: BTreeMap<String, String>,
+}
+
+const DOCUMENT_FIELDS: [&str; 4] = ["summary", "description", "labels", "components"];
+
+// TemplateVars are substituted for {date}, {user} and {project} in the texts
+// of a template
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TemplateVars {
+    pub date: String,
+    pub user: String,
+    pub project: String,
+}
+
+impl TemplateVars {
+    pub fn new(user: &str, project: &str) -> Self {
+        Self {
+            date: Local::now().format("%Y-%m-%d").to_string(),
+            user: user.to_string(),
+            project: project.to_string(),
+        }
+    }
+
+    fn substitute(&self, text: &str) -> String {
+        text.replace("{date}", &self.date)
+            .replace("{user}", &self.user)
+            .replace("{project}", &self.project)
+    }
+}
+
+impl TicketTemplate {
+    // parse reads a template file, the file name is used when it has no name
+    pub fn parse(file_name: &str, contents: &str) -> Result<Self, String> {
+        let (stem, extension) = file_name.rsplit_once('.').unwrap_or((file_name, ""));
+        let mut template: TicketTemplate = match extension {
+            "toml" => toml::from_str(contents).map_err(|e| e.to_string())?,
+            "md" => {
+                let (frontmatter, body) = split_frontmatter(contents)
+                    .ok_or_else(|| String::from("missing frontmatter"))?;
+                let mut template: TicketTemplate =
+                    toml::from_str(frontmatter).map_err(|e| e.to_string())?;
+                let body = body.trim();
+                if !body.is_empty() {
+                    template.description = Some(body.to_string());
+                }
+                template
+            }
+            _ => return Err(String::from("not a .toml or .md file")),
+        };
+        if template.name.is_empty() {
+            template.name = stem.to_string();
+        }
+        Ok(template)
+    }
+
+    // from_values is the inverse of values, field_ids are the other fields of
+    // the form so they can be filled in the document too
+    pub fn from_values(
+        ticket_type: Option<String>,
+        values: &BTreeMap<String, String>,
+        field_ids: &[String],
+    ) -> Self {
+        let value = |field_id: &str| values.get(field_id).cloned().unwrap_or_default();
+        let fields = field_ids
+            .iter()
+            .filter(|field_id| !DOCUMENT_FIELDS.contains(&field_id.as_str()))
+            .map(|field_id| (field_id.clone(), value(field_id)))
+            .collect();
+        Self {
+            name: String::new(),
+            ticket_type,
+            summary: Some(value("summary")),
+            description: Some(value("description")),
+            labels: value("labels")
+                .split(|c: char| c == ',' || c.is_whitespace())
+                .filter(|label| !label.is_empty())
+                .map(String::from)
+                .collect(),
+            components: value("components")
+                .split(',')
+                .map(str::trim)
+                .filter(|component| !component.is_empty())
+                .map(String::from)
+                .collect(),
+            fields,
+        }
+    }
+
+    // to_document writes the template as markdown with the other values in
+    // the frontmatter
+    pub fn to_document(&self) -> Result<String, String> {
+        let frontmatter = toml::to_string(self).map_err(|e| e.to_string())?;
+        let description = self.description.as_deref().unwrap_or_default();
+        Ok(format!("---\n{frontmatter}---\n{description}\n"))
+    }
+
+    // substituted replaces the variables in every text of the template
+    pub fn substituted(&self, vars: &TemplateVars) -> Self {
+        let substitute_all = |texts: &[String]| texts.iter().map(|t| vars.substitute(t)).collect();
+        Self {
+            name: self.name.clone(),
+            ticket_type: self.ticket_type.clone(),
+            summary: self.summary.as_deref().map(|s| vars.substitute(s)),
+            description: self.description.as_deref().map(|d| vars.substitute(d)),
+            labels: substitute_all(&self.labels),
+            components: substitute_all(&self.components),
+            fields: self
+                .fields
+                .iter()
+                .map(|(field_id, value)| (field_id.clone(), vars.substitute(value)))
+                .collect(),
+        }
+    }
+
+    // values are the form values of the template by field id
+    pub fn values(&self) -> BTreeMap<String, String> {
+        let mut values = self.fields.clone();
+        if let Some(summary) = &self.summary {
+            values.insert(String::from("summary"), summary.clone());
+        }
+        if let Some(description) = &self.description {
+            values.insert(String::from("description"), description.clone());
+        }
+        if !self.labels.is_empty() {
+            values.insert(String::from("labels"), self.labels.join(" "));
+        }
+        if !self.components.is_empty() {
+            values.insert(String::from("components"), self.components.join(", "));
+        }
+        values
+    }
+}
+
+fn split_frontmatter(contents: &str) -> Option<(&str, &str)> {
+    let contents = contents.trim_start();
+    let delimiter = ["---", "+++"]
+        .into_iter()
+        .find(|delimiter| contents.starts_with(delimiter))?;
+    let rest = contents[delimiter.len()..]
+        .strip_prefix('\n')
+        .or_else(|| contents[delimiter.len()..].strip_prefix("\r\n"))?;
+    let end = rest.find(&format!("\n{delimiter}"))?;
+    let body = &rest[end + 1 + delimiter.len()..];
+    Some((&rest[..end], body))
+}
+
+// load_templates reads every template of the templates dir, sorted by name.
+// Files that can not be read are reported back.
+pub fn load_templates() -> (Vec<TicketTemplate>, Vec<String>) {
+    let dir = format!("{}/templates", config_dir());
+    let Ok(entries) = fs::read_dir(Path::new(&dir)) else {
+        return (vec![], vec![]);
+    };
+    let mut templates = vec![];
+    let mut problems = vec![];
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if !path.is_file() {
+            continue;
+        }
+        let file_name = entry.file_name().to_string_lossy().to_string();
+        let parsed = fs::read_to_string(&path)
+            .map_err(|e| e.to_string())
+            .and_then(|contents| TicketTemplate::parse(&file_name, &contents));
+        match parsed {
+            Ok(template) => templates.push(template),
+            Err(e) => problems.push(format!("{file_name}: {e}")),
+        }
+    }
+    templates.sort_by(|a, b| a.name.cmp(&b.name));
+    (templates, problems)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vars() -> TemplateVars {
+        TemplateVars {
+            date: "2024-05-31".to_string(),
+            user: "Jane Doe".to_string(),
+            project: "ABC".to_string(),
+        }
+    }
+
+    #[test]
+    fn markdown_template_with_frontmatter() {
+        let contents = "---\n\
+            type = \"Bug\"\n\
+            summary = \"[Bug] \"\n\
+            labels = [\"triage\", \"{project}-bugs\"]\n\
+            components = [\"API\", \"Web\"]\n\
+            ---\n\
+            ## Steps\n\nReported by {user} on {date}\n";
+        let template = TicketTemplate::parse("bug.md", contents).unwrap();
+        assert_eq!(template.name, "bug");
+        assert_eq!(template.ticket_type.as_deref(), Some("Bug"));
+        assert_eq!(
+            template.substituted(&vars()).values(),
+            BTreeMap::from([
+                ("components".to_string(), "API, Web".to_string()),
+                (
+                    "description".to_string(),
+                    "## Steps\n\nReported by Jane Doe on 2024-05-31".to_string()
+                ),
+                ("labels".to_string(), "triage ABC-bugs".to_string()),
+                ("summary".to_string(), "[Bug] ".to_string()),
+            ])
+        );
+    }
+
+    #[test]
+    fn toml_template_with_fields() {
+        let contents = "name = \"Spike\"\n\
+            type = \"Task\"\n\
+            summary = \"Spike: \"\n\
+            [fields]\n\
+            customfield_10016 = \"3\"\n";
+        let template = TicketTemplate::parse("spike.toml", contents).unwrap();
+        assert_eq!(template.name, "Spike");
+        let values = template.substituted(&vars()).values();
+        assert_eq!(
+            values.get("customfield_10016").map(String::as_str),
+            Some("3")
+        );
+        assert!(TicketTemplate::parse("notes.txt", "").is_err());
+        assert!(TicketTemplate::parse("bug.md", "no frontmatter").is_err());
+    }
+
+    #[test]
+    fn document_round_trip() {
+        let values = BTreeMap::from([
+            ("components".to_string(), "API, Web".to_string()),
+            (
+                "description".to_string(),
+                "Line one\n\nLine two".to_string(),
+            ),
+            ("labels".to_string(), "triage ops".to_string()),
+            ("summary".to_string(), "Payment {date} fails".to_string()),
+        ]);
+        let field_ids = ["summary".to_string(), "customfield_10016".to_string()];
+        let template = TicketTemplate::from_values(Some("Bug".to_string()), &values, &field_ids);
+        let document = template.to_document().unwrap();
+        let parsed = TicketTemplate::parse("ticket.md", &document).unwrap();
+        assert_eq!(parsed.ticket_type.as_deref(), Some("Bug"));
+        let mut expected = values.clone();
+        expected.insert("customfield_10016".to_string(), String::new());
+        assert_eq!(parsed.values(), expected);
+    }
+}
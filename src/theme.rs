@@ -0,0 +1,356 @@
+use std::{collections::BTreeMap, sync::OnceLock};
+
+use tui::style::{Color, Modifier, Style};
+
+use crate::{config::JiraConfigTheme, jira::tickets::Status};
+
+// Theme holds the styles the widgets draw with. It starts from a preset and
+// every style can be overridden under [theme] in config.toml, written as
+// words like "bold yellow", "black on cyan" or "#ff8700 on 236".
+#[derive(Debug, Clone, PartialEq)]
+pub struct Theme {
+    pub focused_border: Style,
+    pub highlight: Style,
+    pub edit_mode: Style,
+    pub error: Style,
+    pub success: Style,
+    pub muted: Style,
+    pub updated: Style,
+    // status styles are keyed by lowercase status name or category key
+    // ("new", "indeterminate" or "done")
+    pub status: BTreeMap<String, Style>,
+    // priority styles are keyed by lowercase priority name
+    pub priority: BTreeMap<String, Style>,
+}
+
+pub const PRESETS: [&str; 3] = ["dark", "light", "high-contrast"];
+
+static THEME: OnceLock<Theme> = OnceLock::new();
+
+// theme is the theme set at startup, or the dark preset before that
+pub fn theme() -> &'static Theme {
+    THEME.get_or_init(Theme::dark)
+}
+
+// set_theme sets the theme once, from the config, before anything is drawn
+pub fn set_theme(theme: Theme) -> Result<(), String> {
+    THEME
+        .set(theme)
+        .map_err(|_| "the theme has already been set".to_string())
+}
+
+fn fg(color: Color) -> Style {
+    Style::default().fg(color)
+}
+
+fn bold(color: Color) -> Style {
+    fg(color).add_modifier(Modifier::BOLD)
+}
+
+fn styles<const N: usize>(styles: [(&str, Style); N]) -> BTreeMap<String, Style> {
+    styles
+        .into_iter()
+        .map(|(name, style)| (name.to_string(), style))
+        .collect()
+}
+
+impl Theme {
+    pub fn dark() -> Self {
+        Self {
+            focused_border: fg(Color::Green),
+            highlight: Style::default().bg(Color::Blue),
+            edit_mode: fg(Color::Yellow),
+            error: fg(Color::Red),
+            success: fg(Color::Green),
+            muted: fg(Color::DarkGray),
+            updated: bold(Color::Yellow),
+            status: styles([
+                ("new", fg(Color::Cyan)),
+                ("indeterminate", fg(Color::Yellow)),
+                ("done", fg(Color::Green)),
+            ]),
+            priority: styles([
+                ("highest", bold(Color::Red)),
+                ("high", fg(Color::LightRed)),
+                ("medium", fg(Color::Yellow)),
+                ("low", fg(Color::Green)),
+                ("lowest", fg(Color::DarkGray)),
+            ]),
+        }
+    }
+
+    pub fn light() -> Self {
+        Self {
+            focused_border: fg(Color::Blue),
+            highlight: Style::default().fg(Color::Black).bg(Color::LightCyan),
+            edit_mode: fg(Color::Magenta),
+            error: fg(Color::Red),
+            success: fg(Color::Green),
+            muted: fg(Color::Gray),
+            updated: bold(Color::Magenta),
+            status: styles([
+                ("new", fg(Color::Blue)),
+                ("indeterminate", fg(Color::Magenta)),
+                ("done", fg(Color::Green)),
+            ]),
+            priority: styles([
+                ("highest", bold(Color::Red)),
+                ("high", fg(Color::Red)),
+                ("medium", fg(Color::Magenta)),
+                ("low", fg(Color::Green)),
+                ("lowest", fg(Color::Gray)),
+            ]),
+        }
+    }
+
+    pub fn high_contrast() -> Self {
+        Self {
+            focused_border: bold(Color::White),
+            highlight: Style::default()
+                .fg(Color::Black)
+                .bg(Color::White)
+                .add_modifier(Modifier::BOLD),
+            edit_mode: bold(Color::LightYellow),
+            error: Style::default()
+                .fg(Color::White)
+                .bg(Color::Red)
+                .add_modifier(Modifier::BOLD),
+            success: bold(Color::LightGreen),
+            muted: fg(Color::Gray),
+            updated: bold(Color::LightYellow).add_modifier(Modifier::UNDERLINED),
+            status: styles([
+                ("new", bold(Color::LightCyan)),
+                ("indeterminate", bold(Color::LightYellow)),
+                ("done", bold(Color::LightGreen)),
+            ]),
+            priority: styles([
+                ("highest", bold(Color::LightRed)),
+                ("high", fg(Color::LightRed)),
+                ("medium", fg(Color::LightYellow)),
+                ("low", fg(Color::LightGreen)),
+                ("lowest", fg(Color::White)),
+            ]),
+        }
+    }
+
+    pub fn preset(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "dark" => Some(Self::dark()),
+            "light" => Some(Self::light()),
+            "high-contrast" | "high_contrast" => Some(Self::high_contrast()),
+            _ => None,
+        }
+    }
+
+    // status_style looks the status up by name first, then by category
+    pub fn status_style(&self, status: &Status) -> Style {
+        self.status
+            .get(&status.name.to_lowercase())
+            .or_else(|| self.status.get(status.category_key()))
+            .copied()
+            .unwrap_or_default()
+    }
+
+    pub fn priority_style(&self, priority: &str) -> Style {
+        self.priority
+            .get(&priority.to_lowercase())
+            .copied()
+            .unwrap_or_default()
+    }
+}
+
+fn parse_color(word: &str) -> Option<Color> {
+    if let Some(hex) = word.strip_prefix('#') {
+        if hex.len() != 6 || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+            return None;
+        }
+        let channel = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).ok();
+        return Some(Color::Rgb(channel(0)?, channel(2)?, channel(4)?));
+    }
+    if let Ok(index) = word.parse::<u8>() {
+        return Some(Color::Indexed(index));
+    }
+    let color = match word.to_lowercase().replace(['-', '_'], "").as_str() {
+        "reset" | "default" => Color::Reset,
+        "black" => Color::Black,
+        "red" => Color::Red,
+        "green" => Color::Green,
+        "yellow" => Color::Yellow,
+        "blue" => Color::Blue,
+        "magenta" => Color::Magenta,
+        "cyan" => Color::Cyan,
+        "gray" | "grey" => Color::Gray,
+        "darkgray" | "darkgrey" => Color::DarkGray,
+        "lightred" => Color::LightRed,
+        "lightgreen" => Color::LightGreen,
+        "lightyellow" => Color::LightYellow,
+        "lightblue" => Color::LightBlue,
+        "lightmagenta" => Color::LightMagenta,
+        "lightcyan" => Color::LightCyan,
+        "white" => Color::White,
+        _ => return None,
+    };
+    Some(color)
+}
+
+fn parse_modifier(word: &str) -> Option<Modifier> {
+    let modifier = match word.to_lowercase().as_str() {
+        "bold" => Modifier::BOLD,
+        "dim" => Modifier::DIM,
+        "italic" => Modifier::ITALIC,
+        "underlined" | "underline" => Modifier::UNDERLINED,
+        "reversed" | "reverse" => Modifier::REVERSED,
+        "crossed" | "crossed-out" => Modifier::CROSSED_OUT,
+        _ => return None,
+    };
+    Some(modifier)
+}
+
+// parse_style reads modifiers and a foreground color, followed by "on" and a
+// background color
+pub fn parse_style(text: &str) -> Result<Style, String> {
+    let mut style = Style::default();
+    let mut words = text.split_whitespace();
+    while let Some(word) = words.next() {
+        if word.eq_ignore_ascii_case("on") {
+            let background = words
+                .next()
+                .ok_or_else(|| format!("missing color after \"on\" in \"{text}\""))?;
+            let color =
+                parse_color(background).ok_or_else(|| format!("unknown color \"{background}\""))?;
+            style = style.bg(color);
+        } else if let Some(modifier) = parse_modifier(word) {
+            style = style.add_modifier(modifier);
+        } else if let Some(color) = parse_color(word) {
+            style = style.fg(color);
+        } else {
+            return Err(format!("unknown color or modifier \"{word}\""));
+        }
+    }
+    Ok(style)
+}
+
+// from_config builds the theme of the [theme] section, styles that can not
+// be read keep the preset style and are reported back
+pub fn from_config(config: &JiraConfigTheme) -> (Theme, Vec<String>) {
+    let mut problems = vec![];
+    let mut theme = match config.preset.as_deref() {
+        None => Theme::dark(),
+        Some(name) => Theme::preset(name).unwrap_or_else(|| {
+            problems.push(format!(
+                "unknown preset \"{name}\", expected one of {}",
+                PRESETS.join(", ")
+            ));
+            Theme::dark()
+        }),
+    };
+    let mut apply = |name: &str, text: &Option<String>, style: &mut Style| {
+        if let Some(text) = text {
+            match parse_style(text) {
+                Ok(parsed) => *style = parsed,
+                Err(e) => problems.push(format!("{name}: {e}")),
+            }
+        }
+    };
+    apply(
+        "focused_border",
+        &config.focused_border,
+        &mut theme.focused_border,
+    );
+    apply("highlight", &config.highlight, &mut theme.highlight);
+    apply("edit_mode", &config.edit_mode, &mut theme.edit_mode);
+    apply("error", &config.error, &mut theme.error);
+    apply("success", &config.success, &mut theme.success);
+    apply("muted", &config.muted, &mut theme.muted);
+    apply("updated", &config.updated, &mut theme.updated);
+    for (section, overrides, styles) in [
+        ("status", &config.status, &mut theme.status),
+        ("priority", &config.priority, &mut theme.priority),
+    ] {
+        for (name, text) in overrides.iter().flatten() {
+            match parse_style(text) {
+                Ok(style) => {
+                    styles.insert(name.to_lowercase(), style);
+                }
+                Err(e) => problems.push(format!("{section}.{name}: {e}")),
+            }
+        }
+    }
+    (theme, problems)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::jira::tickets::StatusCategory;
+
+    #[test]
+    fn parse_styles() {
+        assert_eq!(
+            parse_style("bold yellow").unwrap(),
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD)
+        );
+        assert_eq!(
+            parse_style("Black on light-cyan").unwrap(),
+            Style::default().fg(Color::Black).bg(Color::LightCyan)
+        );
+        assert_eq!(
+            parse_style("#ff8700 on 236").unwrap(),
+            Style::default()
+                .fg(Color::Rgb(255, 135, 0))
+                .bg(Color::Indexed(236))
+        );
+        assert!(parse_style("purple").is_err());
+        assert!(parse_style("red on").is_err());
+        assert!(parse_style("#ff87é").is_err());
+    }
+
+    #[test]
+    fn config_overrides_the_preset() {
+        let config = JiraConfigTheme {
+            preset: Some("light".to_string()),
+            highlight: Some("reversed".to_string()),
+            error: Some("blinking red".to_string()),
+            status: Some(BTreeMap::from([(
+                "In Review".to_string(),
+                "magenta".to_string(),
+            )])),
+            ..Default::default()
+        };
+        let (theme, problems) = from_config(&config);
+        assert_eq!(
+            theme.highlight,
+            Style::default().add_modifier(Modifier::REVERSED)
+        );
+        assert_eq!(theme.error, Theme::light().error);
+        assert_eq!(
+            problems,
+            vec!["error: unknown color or modifier \"blinking\"".to_string()]
+        );
+
+        let status = |name: &str, key: &str| Status {
+            id: None,
+            name: name.to_string(),
+            status_category: Some(StatusCategory {
+                key: key.to_string(),
+                name: String::new(),
+            }),
+        };
+        assert_eq!(
+            theme.status_style(&status("In Review", "indeterminate")),
+            fg(Color::Magenta)
+        );
+        assert_eq!(
+            theme.status_style(&status("Closed", "done")),
+            fg(Color::Green)
+        );
+        assert!(from_config(&JiraConfigTheme {
+            preset: Some("solarized".to_string()),
+            ..Default::default()
+        })
+        .1[0]
+            .starts_with("unknown preset"));
+    }
+}
@@ -1,26 +1,41 @@
+pub mod boards;
+pub mod bulk;
 pub mod commands;
 pub mod comments;
 pub mod comments_add;
 pub mod components;
 pub mod create_ticket;
+pub mod epics;
 pub mod error;
+pub mod filters;
 pub mod help;
+pub mod history;
+pub mod jql;
+pub mod kanban;
 pub mod labels;
+pub mod my_work;
+pub mod notifications;
+pub mod palette;
+pub mod pane_layout;
 pub mod parent;
 pub mod projects;
 pub mod search_projects;
 pub mod search_tickets;
+pub mod text_area;
+pub mod ticket_columns;
+pub mod ticket_finder;
 pub mod ticket_relation;
 pub mod ticket_transition;
 pub mod tickets;
 
+use crate::theme::theme;
 use commands::CommandInfo;
 
 use async_trait::async_trait;
 use tui::{
     backend::Backend,
     layout::Rect,
-    style::{Color, Style},
+    style::Style,
     widgets::{Block, BorderType, Borders},
     Frame,
 };
@@ -53,6 +68,65 @@ impl From<bool> for EventState {
     }
 }
 
+// contains tells whether the terminal cell is inside the rect
+pub fn contains(rect: Rect, column: u16, row: u16) -> bool {
+    column >= rect.x && column < rect.right() && row >= rect.y && row < rect.bottom()
+}
+
+// clicked_row is the index of the one line item drawn at the row of a
+// bordered list or table, header_height being the lines above the items
+pub fn clicked_row(rect: Rect, header_height: u16, offset: usize, row: u16) -> Option<usize> {
+    let first = rect.y + 1 + header_height;
+    let end = rect.bottom().saturating_sub(1);
+    (row >= first && row < end).then(|| offset + (row - first) as usize)
+}
+
+// row_height is the number of lines tui gives a table row, the most lines
+// of any of its cells
+pub fn row_height<S: AsRef<str>>(cells: &[S]) -> u16 {
+    let lines = cells
+        .iter()
+        .map(|content| content.as_ref().lines().count())
+        .max()
+        .unwrap_or(0);
+    lines.max(1) as u16
+}
+
+// clicked_table_row is the index of the table row drawn at the row of a
+// bordered table, heights being the lines of every row from row_height
+pub fn clicked_table_row(
+    rect: Rect,
+    header_height: u16,
+    offset: usize,
+    heights: &[u16],
+    row: u16,
+) -> Option<usize> {
+    let first = rect.y + 1 + header_height;
+    let end = rect.bottom().saturating_sub(1);
+    if row < first || row >= end {
+        return None;
+    }
+    let mut top = first;
+    for (index, height) in heights.iter().enumerate().skip(offset) {
+        if row < top + height {
+            return Some(index);
+        }
+        top += height;
+    }
+    None
+}
+
+// list_offset follows the first item tui shows of a list of one line items,
+// which only scrolls to keep the selection in view
+pub fn list_offset(offset: usize, selected: Option<usize>, height: usize) -> usize {
+    match selected {
+        None => 0,
+        Some(selected) if selected < offset => selected,
+        Some(selected) if height > 0 && selected >= offset + height => selected + 1 - height,
+        Some(_) => offset,
+    }
+}
+
 pub trait DrawableComponent {
     fn draw<B: Backend>(&self, f: &mut Frame<B>, rect: Rect, focused: bool) -> anyhow::Result<()>;
 }
@@ -115,7 +189,7 @@ pub fn draw_block_style(focused: bool, title: &str) -> Block {
         Block::default()
             .border_type(BorderType::Double)
             .borders(Borders::ALL)
-            .border_style(Style::default().fg(Color::Green))
+            .border_style(theme().focused_border)
             .title(title)
             .title_alignment(tui::layout::Alignment::Center)
     } else {
@@ -129,16 +203,49 @@ pub fn draw_block_style(focused: bool, title: &str) -> Block {
 
 pub fn draw_edit_block_style(focused: bool, input_mode: &InputMode) -> Style {
     if focused && matches!(input_mode, InputMode::Editing) {
-        return Style::default().fg(Color::Yellow);
+        return theme().edit_mode;
     }
 
     if focused && matches!(input_mode, InputMode::Normal) {
-        return Style::default().fg(Color::Green);
+        return theme().focused_border;
     }
 
     Style::default()
 }
 
 pub fn draw_highlight_style() -> Style {
-    Style::default().bg(Color::Blue)
+    theme().highlight
+}
+
+// draw_edit_style is the style of a text being edited
+pub fn draw_edit_style(editing: bool) -> Style {
+    match editing {
+        true => theme().edit_mode,
+        false => Style::default(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn clicks_on_rows_of_several_lines() {
+        assert_eq!(row_height(&["ABC-1", "first\nsecond\nthird", ""]), 3);
+        assert_eq!(row_height(&["", ""]), 1);
+
+        // A border and a header line come before the rows, drawn from row 2
+        let rect = Rect::new(0, 0, 40, 12);
+        let heights = [1, 3, 1, 2];
+        let clicked = |offset, row| clicked_table_row(rect, 1, offset, &heights, row);
+        assert_eq!(clicked(0, 1), None);
+        assert_eq!(clicked(0, 2), Some(0));
+        assert_eq!(clicked(0, 3), Some(1));
+        assert_eq!(clicked(0, 5), Some(1));
+        assert_eq!(clicked(0, 6), Some(2));
+        assert_eq!(clicked(0, 8), Some(3));
+        assert_eq!(clicked(0, 9), None);
+        assert_eq!(clicked(1, 5), Some(2));
+        assert_eq!(clicked(0, 11), None);
+    }
 }
@@ -0,0 +1,297 @@
+use std::collections::HashMap;
+
+use tui::{
+    backend::Backend,
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Span, Spans},
+    widgets::{Clear, List, ListItem, ListState, Paragraph, Wrap},
+    Frame,
+};
+
+use crate::{
+    config::KeyConfig,
+    events::key::Key,
+    jira::agile::{Board, Sprint},
+};
+
+use super::{
+    commands::{CommandInfo, CommandText},
+    draw_block_style, draw_highlight_style, Component, EventState,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BoardsFocus {
+    Boards,
+    Sprints,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum Action {
+    Next(usize),
+    Previous(usize),
+    Last,
+    First,
+    NextFocus,
+}
+
+impl Action {
+    pub fn to_command_text(self, key: Key) -> CommandText {
+        const CMD_GROUP_GENERAL: &str = "-- General --";
+        match self {
+            Self::Next(line) => CommandText::new(format!("Next {line} [{key}]"), CMD_GROUP_GENERAL),
+            Self::Previous(line) => {
+                CommandText::new(format!("Previous {line} [{key}]"), CMD_GROUP_GENERAL)
+            }
+            Self::Last => CommandText::new(format!("Go to last [{key}]"), CMD_GROUP_GENERAL),
+            Self::First => CommandText::new(format!("Go to first [{key}]"), CMD_GROUP_GENERAL),
+            Self::NextFocus => CommandText::new(
+                format!("Switch between boards and sprints [{key}]"),
+                CMD_GROUP_GENERAL,
+            ),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct BoardsWidget {
+    boards: Vec<Board>,
+    boards_state: ListState,
+    sprints: Vec<Sprint>,
+    sprints_state: ListState,
+    pub focus: BoardsFocus,
+    pub move_tickets: Vec<String>,
+    pub project_key: Option<String>,
+    pub key_mappings: HashMap<Key, Action>,
+}
+
+impl BoardsWidget {
+    pub fn draw<B: Backend>(
+        &mut self,
+        f: &mut Frame<B>,
+        focused: bool,
+        rect: Rect,
+    ) -> anyhow::Result<()> {
+        f.render_widget(Clear, rect);
+        let main_chunks = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(35), Constraint::Percentage(65)])
+            .split(rect);
+        let list_chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Percentage(40), Constraint::Percentage(60)])
+            .split(main_chunks[0]);
+
+        let boards_title = "Boards";
+        let board_items: Vec<ListItem> = self
+            .boards
+            .iter()
+            .map(|board| ListItem::new(format!("{} ({})", board.name, board.board_type)))
+            .collect();
+        let boards_list = List::new(board_items)
+            .block(draw_block_style(
+                focused && self.focus == BoardsFocus::Boards,
+                boards_title,
+            ))
+            .highlight_style(draw_highlight_style());
+        f.render_stateful_widget(boards_list, list_chunks[0], &mut self.boards_state);
+
+        let sprints_title = match self.move_tickets.is_empty() {
+            true => String::from("Sprints"),
+            false => format!("Move {} to", self.move_tickets.join(", ")),
+        };
+        let mut sprint_items: Vec<ListItem> = self
+            .sprints
+            .iter()
+            .map(|sprint| ListItem::new(format!("{} [{}]", sprint.name, sprint.state)))
+            .collect();
+        sprint_items.push(ListItem::new("Backlog"));
+        let sprints_list = List::new(sprint_items)
+            .block(draw_block_style(
+                focused && self.focus == BoardsFocus::Sprints,
+                &sprints_title,
+            ))
+            .highlight_style(draw_highlight_style());
+        f.render_stateful_widget(sprints_list, list_chunks[1], &mut self.sprints_state);
+
+        let bold = Style::default().add_modifier(Modifier::BOLD);
+        let details = match (self.focus, self.selected_board(), self.selected_sprint()) {
+            (BoardsFocus::Boards, Some(board), _) => vec![
+                Spans::from(vec![Span::styled("Board: ", bold), Span::raw(&board.name)]),
+                Spans::from(vec![
+                    Span::styled("Type: ", bold),
+                    Span::raw(&board.board_type),
+                ]),
+            ],
+            (BoardsFocus::Sprints, _, Some(sprint)) => vec![
+                Spans::from(vec![
+                    Span::styled("Sprint: ", bold),
+                    Span::raw(&sprint.name),
+                ]),
+                Spans::from(vec![
+                    Span::styled("State: ", bold),
+                    Span::raw(&sprint.state),
+                ]),
+                Spans::from(vec![
+                    Span::styled("Dates: ", bold),
+                    Span::raw(sprint.date_range()),
+                ]),
+                Spans::from(""),
+                Spans::from(Span::styled("Goal", bold)),
+                Spans::from(sprint.goal.clone().unwrap_or_default()),
+            ],
+            (BoardsFocus::Sprints, Some(_), None) => vec![Spans::from(
+                "Backlog: tickets of this board that are not planned in a sprint",
+            )],
+            _ => vec![],
+        };
+        let details_paragraph = Paragraph::new(details)
+            .block(draw_block_style(false, "Details"))
+            .alignment(Alignment::Left)
+            .wrap(Wrap { trim: true });
+        f.render_widget(details_paragraph, main_chunks[1]);
+
+        Ok(())
+    }
+}
+
+impl BoardsWidget {
+    pub fn new(key_config: KeyConfig) -> Self {
+        let key_mappings = {
+            let mut map = HashMap::new();
+            map.insert(key_config.scroll_down, Action::Next(1));
+            map.insert(key_config.scroll_up, Action::Previous(1));
+            map.insert(key_config.scroll_down_multiple_lines, Action::Next(10));
+            map.insert(key_config.scroll_up_multiple_lines, Action::Previous(10));
+            map.insert(key_config.scroll_to_bottom, Action::Last);
+            map.insert(key_config.scroll_to_top, Action::First);
+            map.insert(key_config.next, Action::NextFocus);
+            map.insert(key_config.previous, Action::NextFocus);
+            map
+        };
+
+        Self {
+            boards: vec![],
+            boards_state: ListState::default(),
+            focus: BoardsFocus::Boards,
+            key_mappings,
+            move_tickets: vec![],
+            project_key: None,
+            sprints: vec![],
+            sprints_state: ListState::default(),
+        }
+    }
+
+    fn focused_len(&self) -> usize {
+        match self.focus {
+            BoardsFocus::Boards => self.boards.len(),
+            // The trailing entry is the board backlog
+            BoardsFocus::Sprints => self.sprints.len() + 1,
+        }
+    }
+
+    fn focused_state(&mut self) -> &mut ListState {
+        match self.focus {
+            BoardsFocus::Boards => &mut self.boards_state,
+            BoardsFocus::Sprints => &mut self.sprints_state,
+        }
+    }
+
+    pub fn next(&mut self, line: usize) {
+        let len = self.focused_len();
+        if len == 0 {
+            return;
+        }
+        let state = self.focused_state();
+        let i = state.selected().map(|i| (i + line).min(len - 1));
+        state.select(i);
+    }
+
+    pub fn previous(&mut self, line: usize) {
+        let state = self.focused_state();
+        let i = state.selected().map(|i| i.saturating_sub(line));
+        state.select(i);
+    }
+
+    pub fn go_to_top(&mut self) {
+        if self.focused_len() == 0 {
+            return;
+        }
+        self.focused_state().select(Some(0));
+    }
+
+    pub fn go_to_bottom(&mut self) {
+        let len = self.focused_len();
+        if len == 0 {
+            return;
+        }
+        self.focused_state().select(Some(len - 1));
+    }
+
+    pub fn next_focus(&mut self) {
+        self.focus = match self.focus {
+            BoardsFocus::Boards => BoardsFocus::Sprints,
+            BoardsFocus::Sprints => BoardsFocus::Boards,
+        };
+    }
+
+    pub fn selected_board(&self) -> Option<&Board> {
+        match self.boards_state.selected() {
+            Some(i) => self.boards.get(i),
+            None => None,
+        }
+    }
+
+    pub fn selected_sprint(&self) -> Option<&Sprint> {
+        match self.sprints_state.selected() {
+            Some(i) => self.sprints.get(i),
+            None => None,
+        }
+    }
+
+    pub fn backlog_selected(&self) -> bool {
+        self.sprints_state.selected() == Some(self.sprints.len())
+    }
+
+    pub fn update_boards(&mut self, project_key: &str, boards: &[Board]) {
+        self.boards = boards.to_vec();
+        self.project_key = Some(project_key.to_string());
+        self.boards_state
+            .select(if boards.is_empty() { None } else { Some(0) });
+        self.sprints.clear();
+        self.sprints_state.select(None);
+        self.focus = BoardsFocus::Boards;
+    }
+
+    pub fn update_sprints(&mut self, sprints: &[Sprint]) {
+        self.sprints = sprints.to_vec();
+        // Start on the first sprint that is still open, the list is newest first
+        let open_sprint = self.sprints.iter().rposition(|sprint| !sprint.is_closed());
+        self.sprints_state.select(Some(open_sprint.unwrap_or(0)));
+        self.focus = BoardsFocus::Sprints;
+    }
+
+    pub fn has_boards(&self) -> bool {
+        !self.boards.is_empty()
+    }
+}
+
+impl Component for BoardsWidget {
+    fn commands(&self, _out: &mut Vec<CommandInfo>) {}
+
+    fn event(&mut self, key: Key) -> anyhow::Result<EventState> {
+        if let Some(action) = self.key_mappings.get(&key) {
+            use Action::*;
+            match *action {
+                Next(line) => self.next(line),
+                Previous(line) => self.previous(line),
+                Last => self.go_to_bottom(),
+                First => self.go_to_top(),
+                NextFocus => self.next_focus(),
+            }
+            Ok(EventState::Consumed)
+        } else {
+            Ok(EventState::NotConsumed)
+        }
+    }
+}
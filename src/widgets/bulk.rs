@@ -0,0 +1,479 @@
+use std::collections::HashMap;
+
+use tui::{
+    backend::Backend,
+    layout::{Constraint, Direction, Layout, Rect},
+    widgets::{Clear, Gauge, List, ListItem, ListState},
+    Frame,
+};
+
+use crate::{
+    config::KeyConfig,
+    events::key::Key,
+    jira::bulk::{BulkOperation, BulkResult, CommonTransition},
+    theme::theme,
+};
+
+use super::{
+    commands::{CommandInfo, CommandText},
+    draw_block_style, draw_edit_block_style, draw_highlight_style,
+    text_area::TextArea,
+    Component, EventState, InputMode,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BulkMenuItem {
+    Transition,
+    AddLabel,
+    RemoveLabel,
+    Assign,
+    Comment,
+    MoveToSprint,
+    SelectMatching,
+}
+
+impl BulkMenuItem {
+    pub const ALL: [BulkMenuItem; 7] = [
+        Self::Transition,
+        Self::AddLabel,
+        Self::RemoveLabel,
+        Self::Assign,
+        Self::Comment,
+        Self::MoveToSprint,
+        Self::SelectMatching,
+    ];
+
+    pub fn title(self) -> &'static str {
+        match self {
+            Self::Transition => "Transition to a common status",
+            Self::AddLabel => "Add label",
+            Self::RemoveLabel => "Remove label",
+            Self::Assign => "Assign",
+            Self::Comment => "Add comment",
+            Self::MoveToSprint => "Move to sprint",
+            Self::SelectMatching => "Select tickets matching a filter",
+        }
+    }
+
+    // prompt is the title of the text input of the items that need one
+    fn prompt(self) -> Option<&'static str> {
+        match self {
+            Self::AddLabel | Self::RemoveLabel => Some("Label"),
+            Self::Assign => Some("User name or email, \"me\" or empty to unassign"),
+            Self::Comment => Some("Comment"),
+            Self::SelectMatching => Some("Key, summary, status, assignee or label contains"),
+            Self::Transition | Self::MoveToSprint => None,
+        }
+    }
+}
+
+// BulkRequest is what the app has to do after an event of the widget
+#[derive(Debug, Clone, PartialEq)]
+pub enum BulkRequest {
+    LoadTransitions,
+    Run(BulkOperation),
+    Assign(String),
+    MoveToSprint,
+    SelectMatching(String),
+    Retry(BulkOperation, Vec<String>),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum BulkStep {
+    Menu,
+    Input(BulkMenuItem),
+    Statuses,
+    Progress,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum Action {
+    Next(usize),
+    Previous(usize),
+    Select,
+}
+
+impl Action {
+    pub fn to_command_text(self, key: Key) -> CommandText {
+        const CMD_GROUP_GENERAL: &str = "-- General --";
+        match self {
+            Self::Next(line) => CommandText::new(format!("Next {line} [{key}]"), CMD_GROUP_GENERAL),
+            Self::Previous(line) => {
+                CommandText::new(format!("Previous {line} [{key}]"), CMD_GROUP_GENERAL)
+            }
+            Self::Select => CommandText::new(format!("Select [{key}]"), CMD_GROUP_GENERAL),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct BulkWidget {
+    input: TextArea,
+    operation: String,
+    results: Vec<BulkResult>,
+    // retry is the running operation when it can be sent again for the failed tickets
+    retry: Option<BulkOperation>,
+    state: ListState,
+    step: BulkStep,
+    total: usize,
+    transitions: Vec<CommonTransition>,
+    pub key_mappings: HashMap<Key, Action>,
+    pub request: Option<BulkRequest>,
+    // sprint_move is set while the boards view picks the sprint of a bulk move
+    pub sprint_move: bool,
+}
+
+impl BulkWidget {
+    pub fn draw<B: Backend>(
+        &mut self,
+        f: &mut Frame<B>,
+        focused: bool,
+        selected_count: usize,
+        rect: Rect,
+    ) -> anyhow::Result<()> {
+        f.render_widget(Clear, rect);
+        match self.step {
+            BulkStep::Menu | BulkStep::Input(_) => {
+                let chunks = Layout::default()
+                    .direction(Direction::Vertical)
+                    .constraints([Constraint::Min(3), Constraint::Length(3)])
+                    .split(rect);
+                let items: Vec<ListItem> = BulkMenuItem::ALL
+                    .iter()
+                    .map(|item| ListItem::new(item.title()))
+                    .collect();
+                let title = format!("Bulk actions - {selected_count} tickets selected");
+                let list = List::new(items)
+                    .block(draw_block_style(
+                        focused && self.step == BulkStep::Menu,
+                        &title,
+                    ))
+                    .highlight_style(draw_highlight_style());
+                f.render_stateful_widget(list, chunks[0], &mut self.state);
+
+                let (prompt, input_mode) = match self.step {
+                    BulkStep::Input(item) => {
+                        (item.prompt().unwrap_or_default(), InputMode::Editing)
+                    }
+                    _ => ("", InputMode::Normal),
+                };
+                self.input.draw(
+                    f,
+                    chunks[1],
+                    draw_block_style(false, prompt),
+                    draw_edit_block_style(focused, &input_mode),
+                    focused && input_mode == InputMode::Editing,
+                );
+            }
+            BulkStep::Statuses => {
+                let items: Vec<ListItem> = self
+                    .transitions
+                    .iter()
+                    .map(|transition| ListItem::new(transition.status.as_str()))
+                    .collect();
+                let title = format!("Transition {selected_count} tickets to");
+                let list = List::new(items)
+                    .block(draw_block_style(focused, &title))
+                    .highlight_style(draw_highlight_style());
+                f.render_stateful_widget(list, rect, &mut self.state);
+            }
+            BulkStep::Progress => self.draw_progress(f, focused, rect),
+        }
+        Ok(())
+    }
+
+    fn draw_progress<B: Backend>(&mut self, f: &mut Frame<B>, focused: bool, rect: Rect) {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(3), Constraint::Min(3)])
+            .split(rect);
+        let done = self.results.len();
+        let ratio = match self.total {
+            0 => 1.0,
+            total => done as f64 / total as f64,
+        };
+        let gauge = Gauge::default()
+            .block(draw_block_style(focused, &self.operation))
+            .gauge_style(theme().success)
+            .label(format!("{done}/{}", self.total))
+            .ratio(ratio.min(1.0));
+        f.render_widget(gauge, chunks[0]);
+
+        let failed = self.results.iter().filter(|r| r.outcome.is_err()).count();
+        let retry_key = self
+            .key_mappings
+            .iter()
+            .find(|(_, action)| matches!(action, Action::Select))
+            .map(|(key, _)| *key);
+        let title = match (self.finished(), retry_key) {
+            (true, Some(key)) if failed > 0 && self.retry.is_some() => format!(
+                "Done - {} succeeded, {failed} failed - [{key}] retry failed",
+                done - failed
+            ),
+            (true, _) => format!("Done - {} succeeded, {failed} failed", done - failed),
+            (false, _) => format!("Running - {failed} failed so far"),
+        };
+        let items: Vec<ListItem> = self
+            .results
+            .iter()
+            .map(|result| match &result.outcome {
+                Ok(()) => ListItem::new(format!("✓ {}", result.ticket_key)).style(theme().success),
+                Err(e) => {
+                    ListItem::new(format!("✗ {} {e}", result.ticket_key)).style(theme().error)
+                }
+            })
+            .collect();
+        let list = List::new(items)
+            .block(draw_block_style(focused, &title))
+            .highlight_style(draw_highlight_style());
+        f.render_stateful_widget(list, chunks[1], &mut self.state);
+    }
+}
+
+impl BulkWidget {
+    pub fn new(key_config: KeyConfig) -> Self {
+        let key_mappings = {
+            let mut map = HashMap::new();
+            map.insert(key_config.scroll_down, Action::Next(1));
+            map.insert(key_config.scroll_up, Action::Previous(1));
+            map.insert(key_config.scroll_down_multiple_lines, Action::Next(10));
+            map.insert(key_config.scroll_up_multiple_lines, Action::Previous(10));
+            map.insert(key_config.enter, Action::Select);
+            map
+        };
+
+        Self {
+            input: TextArea::single_line(),
+            key_mappings,
+            operation: String::new(),
+            request: None,
+            results: vec![],
+            retry: None,
+            sprint_move: false,
+            state: ListState::default(),
+            step: BulkStep::Menu,
+            total: 0,
+            transitions: vec![],
+        }
+    }
+
+    // open shows the menu again unless a bulk operation is still running
+    pub fn open(&mut self) {
+        if self.step == BulkStep::Progress && !self.finished() {
+            return;
+        }
+        self.step = BulkStep::Menu;
+        self.input.clear();
+        self.state.select(Some(0));
+    }
+
+    fn len(&self) -> usize {
+        match self.step {
+            BulkStep::Menu | BulkStep::Input(_) => BulkMenuItem::ALL.len(),
+            BulkStep::Statuses => self.transitions.len(),
+            BulkStep::Progress => self.results.len(),
+        }
+    }
+
+    pub fn next(&mut self, line: usize) {
+        let len = self.len();
+        if len == 0 {
+            return;
+        }
+        let i = self.state.selected().map_or(0, |i| (i + line).min(len - 1));
+        self.state.select(Some(i));
+    }
+
+    pub fn previous(&mut self, line: usize) {
+        let i = self.state.selected().map(|i| i.saturating_sub(line));
+        self.state.select(i);
+    }
+
+    pub fn set_transitions(&mut self, transitions: Vec<CommonTransition>) {
+        self.transitions = transitions;
+        self.step = BulkStep::Statuses;
+        self.state.select(Some(0));
+    }
+
+    // start shows the progress of an operation on the given number of tickets
+    pub fn start(&mut self, operation: String, total: usize, retry: Option<BulkOperation>) {
+        self.operation = operation;
+        self.retry = retry;
+        self.total = total;
+        self.results.clear();
+        self.step = BulkStep::Progress;
+        self.state.select(None);
+    }
+
+    pub fn push(&mut self, result: BulkResult) {
+        self.results.push(result);
+    }
+
+    pub fn finished(&self) -> bool {
+        self.results.len() >= self.total
+    }
+
+    pub fn succeeded_keys(&self) -> Vec<String> {
+        self.results
+            .iter()
+            .filter(|result| result.outcome.is_ok())
+            .map(|result| result.ticket_key.clone())
+            .collect()
+    }
+
+    pub fn is_editing(&self) -> bool {
+        matches!(self.step, BulkStep::Input(_))
+    }
+
+    pub fn failed_keys(&self) -> Vec<String> {
+        self.results
+            .iter()
+            .filter(|result| result.outcome.is_err())
+            .map(|result| result.ticket_key.clone())
+            .collect()
+    }
+
+    // retry_failed runs the finished operation again on the tickets it failed for
+    fn retry_failed(&mut self) {
+        let failed_keys = self.failed_keys();
+        if !self.finished() || failed_keys.is_empty() {
+            return;
+        }
+        if let Some(operation) = &self.retry {
+            self.request = Some(BulkRequest::Retry(operation.clone(), failed_keys));
+        }
+    }
+
+    fn select(&mut self) {
+        if self.step == BulkStep::Progress {
+            return self.retry_failed();
+        }
+        let Some(index) = self.state.selected() else {
+            return;
+        };
+        match self.step {
+            BulkStep::Menu => {
+                let item = BulkMenuItem::ALL[index];
+                match item {
+                    BulkMenuItem::Transition => self.request = Some(BulkRequest::LoadTransitions),
+                    BulkMenuItem::MoveToSprint => self.request = Some(BulkRequest::MoveToSprint),
+                    _ => {
+                        self.input.clear();
+                        self.step = BulkStep::Input(item);
+                    }
+                }
+            }
+            BulkStep::Statuses => {
+                if let Some(transition) = self.transitions.get(index) {
+                    self.request = Some(BulkRequest::Run(BulkOperation::Transition(
+                        transition.clone(),
+                    )));
+                }
+            }
+            BulkStep::Input(_) | BulkStep::Progress => {}
+        }
+    }
+
+    fn submit_input(&mut self, item: BulkMenuItem) {
+        let input = self.input.text().trim().to_string();
+        self.request = match item {
+            BulkMenuItem::Assign => Some(BulkRequest::Assign(input)),
+            _ if input.is_empty() => return,
+            BulkMenuItem::AddLabel => Some(BulkRequest::Run(BulkOperation::AddLabel(input))),
+            BulkMenuItem::RemoveLabel => Some(BulkRequest::Run(BulkOperation::RemoveLabel(input))),
+            BulkMenuItem::Comment => Some(BulkRequest::Run(BulkOperation::Comment(input))),
+            BulkMenuItem::SelectMatching => Some(BulkRequest::SelectMatching(input)),
+            BulkMenuItem::Transition | BulkMenuItem::MoveToSprint => None,
+        };
+        self.step = BulkStep::Menu;
+    }
+
+    fn edit_mode_key_event(&mut self, item: BulkMenuItem, key: Key) -> EventState {
+        match key {
+            Key::Enter => self.submit_input(item),
+            Key::Esc => self.step = BulkStep::Menu,
+            _ => return self.input.input(key),
+        }
+        EventState::Consumed
+    }
+
+    pub fn paste(&mut self, text: &str) -> EventState {
+        if !matches!(self.step, BulkStep::Input(_)) {
+            return EventState::NotConsumed;
+        }
+        self.input.insert_str(text);
+        EventState::Consumed
+    }
+}
+
+impl Component for BulkWidget {
+    fn commands(&self, _out: &mut Vec<CommandInfo>) {}
+
+    fn event(&mut self, key: Key) -> anyhow::Result<EventState> {
+        if let BulkStep::Input(item) = self.step {
+            return Ok(self.edit_mode_key_event(item, key));
+        }
+        if let Some(action) = self.key_mappings.get(&key) {
+            use Action::*;
+            match *action {
+                Next(line) => self.next(line),
+                Previous(line) => self.previous(line),
+                Select => self.select(),
+            }
+            Ok(EventState::Consumed)
+        } else {
+            Ok(EventState::NotConsumed)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn result(ticket_key: &str, outcome: Result<(), &str>) -> BulkResult {
+        BulkResult {
+            ticket_key: ticket_key.to_string(),
+            outcome: outcome.map_err(String::from),
+        }
+    }
+
+    #[test]
+    fn retries_the_tickets_that_failed() {
+        let mut bulk = BulkWidget::new(KeyConfig::default());
+        let operation = BulkOperation::AddLabel("triage".to_string());
+        bulk.start(operation.to_string(), 3, Some(operation.clone()));
+        bulk.push(result("ABC-1", Ok(())));
+        bulk.push(result("ABC-2", Err("404 Not Found")));
+        assert!(!bulk.finished());
+        bulk.event(Key::Enter).unwrap();
+        assert_eq!(bulk.request.take(), None);
+
+        bulk.push(result("ABC-3", Err("403 Forbidden")));
+        assert!(bulk.finished());
+        assert_eq!(bulk.succeeded_keys(), vec!["ABC-1".to_string()]);
+        bulk.event(Key::Enter).unwrap();
+        let failed = vec!["ABC-2".to_string(), "ABC-3".to_string()];
+        assert_eq!(
+            bulk.request.take(),
+            Some(BulkRequest::Retry(operation.clone(), failed.clone()))
+        );
+
+        bulk.start(operation.to_string(), failed.len(), Some(operation));
+        bulk.push(result("ABC-2", Ok(())));
+        bulk.push(result("ABC-3", Ok(())));
+        assert_eq!(bulk.succeeded_keys(), failed);
+        bulk.event(Key::Enter).unwrap();
+        assert_eq!(bulk.request.take(), None);
+    }
+
+    #[test]
+    fn sprint_moves_are_not_retried() {
+        let mut bulk = BulkWidget::new(KeyConfig::default());
+        bulk.start("Move to backlog".to_string(), 1, None);
+        bulk.push(result("ABC-1", Err("400 Bad Request")));
+        bulk.event(Key::Enter).unwrap();
+        assert_eq!(bulk.request.take(), None);
+        assert_eq!(bulk.failed_keys(), vec!["ABC-1".to_string()]);
+    }
+}
@@ -129,7 +129,8 @@ impl CommentsList {
         let paragraph = Paragraph::new(Span::styled(text, Style::default()))
             .alignment(Alignment::Left)
             .block(draw_block_style(focused, title))
-            .wrap(Wrap { trim: true });
+            .wrap(Wrap { trim: true })
+            .scroll((self.scroll, 0));
 
         f.render_widget(paragraph, chunks[1]);
 
@@ -5,36 +5,35 @@ use log::{debug, trace};
 use tui::{
     backend::Backend,
     layout::{Constraint, Direction, Layout},
-    style::{Color, Modifier, Style},
+    style::{Modifier, Style},
     text::{Span, Spans, Text},
-    widgets::{Block, Borders, List, ListItem, Paragraph, Wrap},
+    widgets::{Block, Borders, Paragraph},
     Frame,
 };
 
-use super::{EventState, InputMode};
+use super::{draw_edit_style, text_area::TextArea, EventState, InputMode};
 
 #[derive(Debug, Clone, Copy)]
 pub enum NormalModeAction {
     EditMode,
+    OpenEditor,
     Push,
 }
 
 #[derive(Debug, Clone, Copy)]
 pub enum EditModeAction {
-    Backspace,
-    Enter,
     Esc,
 }
 
 // CommentPopup holds the state of the application
 #[derive(Debug)]
 pub struct CommentAdd {
-    /// Current value of the input box
-    input: String,
+    /// Comment being written
+    input: TextArea,
     /// Current input mode
     input_mode: InputMode,
-    /// History of recorded messages
-    pub messages: Vec<String>,
+    // open_editor asks for the comment to be written in $EDITOR
+    pub open_editor: bool,
     pub push_comment: bool,
     pub normal_key_mappings: HashMap<Key, NormalModeAction>,
     pub edit_key_mappings: HashMap<Key, EditModeAction>,
@@ -42,12 +41,7 @@ pub struct CommentAdd {
 
 impl CommentAdd {
     pub fn draw<B: Backend>(&mut self, f: &mut Frame<B>) -> anyhow::Result<()> {
-        let chunk_constraints = [
-            Constraint::Length(1),
-            Constraint::Length(5),
-            Constraint::Min(1),
-        ]
-        .as_ref();
+        let chunk_constraints = [Constraint::Length(1), Constraint::Min(5)].as_ref();
         let chunks = Layout::default()
             .direction(Direction::Vertical)
             .margin(2)
@@ -63,7 +57,9 @@ impl CommentAdd {
                 Span::styled("e", Style::default().add_modifier(Modifier::BOLD)),
                 Span::raw(" to start editing."),
                 Span::styled(" P", Style::default().add_modifier(Modifier::BOLD)),
-                Span::raw(" to push comments to jira."),
+                Span::raw(" to push comments to jira, "),
+                Span::styled("Ctrl-e", Style::default().add_modifier(Modifier::BOLD)),
+                Span::raw(" to write it in $EDITOR."),
             ],
             Style::default().add_modifier(Modifier::UNDERLINED),
         );
@@ -72,8 +68,8 @@ impl CommentAdd {
                 Span::raw("Press "),
                 Span::styled("Esc", Style::default().add_modifier(Modifier::BOLD)),
                 Span::raw(" to stop editing, "),
-                Span::styled("Enter", Style::default().add_modifier(Modifier::BOLD)),
-                Span::raw(" to record the message"),
+                Span::styled("Ctrl-z", Style::default().add_modifier(Modifier::BOLD)),
+                Span::raw(" to undo"),
             ],
             Style::default(),
         );
@@ -86,42 +82,10 @@ impl CommentAdd {
         let help_message = Paragraph::new(text);
         f.render_widget(help_message, chunks[0]);
 
-        let input = Paragraph::new(self.input.as_ref())
-            .wrap(Wrap { trim: true })
-            .style(match self.input_mode {
-                InputMode::Normal => Style::default(),
-                InputMode::Editing => Style::default().fg(Color::Yellow),
-            })
-            .block(Block::default().borders(Borders::ALL).title(input_title));
-        f.render_widget(input, chunks[1]);
-
-        // Make the cursor visible and ask tui-rs to put it at the specified coordinates after rendering
-        let cursor_end_of_text = (
-            // Put cursor past the end of the input text
-            chunks[1].x + self.input.len() as u16 + 1,
-            // Move one line down, from the border to the input line
-            chunks[1].y + 1,
-        );
-        match self.input_mode {
-            InputMode::Normal =>
-                // Hide the cursor. `Frame` does this by default, so we don't need to do anything here
-                {}
-
-            InputMode::Editing => f.set_cursor(cursor_end_of_text.0, cursor_end_of_text.1),
-        }
-
-        let messages: Vec<ListItem> = self
-            .messages
-            .iter()
-            .enumerate()
-            .map(|(i, m)| {
-                let content = vec![Spans::from(Span::raw(format!("{}: {}", i, m)))];
-                ListItem::new(content)
-            })
-            .collect();
-        let messages =
-            List::new(messages).block(Block::default().borders(Borders::ALL).title("Messages"));
-        f.render_widget(messages, chunks[2]);
+        let editing = self.input_mode == InputMode::Editing;
+        let style = draw_edit_style(editing);
+        let block = Block::default().borders(Borders::ALL).title(input_title);
+        self.input.draw(f, chunks[1], block, style, editing);
         Ok(())
     }
 }
@@ -132,26 +96,43 @@ impl CommentAdd {
             let mut normal_map = HashMap::new();
             normal_map.insert(key_config.edit, NormalModeAction::EditMode);
             normal_map.insert(key_config.push, NormalModeAction::Push);
+            normal_map.insert(key_config.open_editor, NormalModeAction::OpenEditor);
             normal_map
         };
 
         let edit_key_mappings = {
             let mut edit_map = HashMap::new();
-            edit_map.insert(key_config.backspace, EditModeAction::Backspace);
-            edit_map.insert(key_config.enter, EditModeAction::Enter);
             edit_map.insert(key_config.esc, EditModeAction::Esc);
             edit_map
         };
         Self {
-            input: String::new(),
+            input: TextArea::new(),
             input_mode: InputMode::Normal,
-            messages: Vec::new(),
+            open_editor: false,
             push_comment: false,
             edit_key_mappings,
             normal_key_mappings,
         }
     }
 
+    // text is the comment written so far
+    pub fn text(&self) -> String {
+        self.input.text()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.input.text().trim().is_empty()
+    }
+
+    // set_text keeps a comment written in the external editor until it is sent
+    pub fn set_text(&mut self, text: &str) {
+        self.input.set_text(text);
+    }
+
+    pub fn clear(&mut self) {
+        self.input.clear();
+    }
+
     pub fn edit_mode(&mut self) {
         self.input_mode = InputMode::Editing
     }
@@ -160,11 +141,16 @@ impl CommentAdd {
         self.input_mode = InputMode::Normal
     }
 
+    pub fn is_editing(&self) -> bool {
+        self.input_mode == InputMode::Editing
+    }
+
     fn normal_mode_key_event(&mut self, key: Key) -> anyhow::Result<EventState> {
         if let Some(action) = self.normal_key_mappings.get(&key) {
             use NormalModeAction::*;
             match *action {
                 EditMode => self.edit_mode(),
+                OpenEditor => self.open_editor = true,
                 Push => self.push_comment = true,
             }
             Ok(EventState::Consumed)
@@ -178,14 +164,6 @@ impl CommentAdd {
         if let Some(action) = self.edit_key_mappings.get(&key) {
             use EditModeAction::*;
             match *action {
-                Backspace => {
-                    trace!("Backspace event");
-                    self.input.pop();
-                }
-                Enter => {
-                    self.messages.push(self.input.clone());
-                    self.input.clear();
-                }
                 Esc => {
                     debug!("Going back to normal mode");
                     self.normal_mode();
@@ -193,14 +171,16 @@ impl CommentAdd {
             }
             Ok(EventState::Consumed)
         } else {
-            match key {
-                Key::Char(c) => {
-                    self.input.push(c);
-                    Ok(EventState::Consumed)
-                }
-                _ => Ok(EventState::NotConsumed),
-            }
+            Ok(self.input.input(key))
+        }
+    }
+
+    pub fn paste(&mut self, text: &str) -> EventState {
+        if self.input_mode != InputMode::Editing {
+            return EventState::NotConsumed;
         }
+        self.input.insert_str(text);
+        EventState::Consumed
     }
 
     pub fn event(&mut self, key: Key) -> anyhow::Result<EventState> {
@@ -8,12 +8,14 @@ use tui::{
 
 use crate::{config::KeyConfig, jira::tickets::TicketData};
 
-use super::{draw_block_style, draw_highlight_style, EventState};
+use super::{clicked_row, draw_block_style, draw_highlight_style, list_offset, EventState};
 
 #[derive(Debug)]
 pub struct ComponentsWidget {
     key_config: KeyConfig,
     components: Vec<String>,
+    // offset is the first component shown, to find the component that is clicked
+    offset: usize,
     state: ListState,
 }
 
@@ -40,6 +42,17 @@ impl ComponentsWidget {
             Some(ticket_data) => ticket_data,
         };
 
+        self.components = ticket
+            .fields
+            .components
+            .iter()
+            .map(|component| component.name.clone())
+            .collect();
+        self.offset = list_offset(
+            self.offset,
+            self.state.selected(),
+            rect.height.saturating_sub(2) as usize,
+        );
         let list_items: Vec<_> = ticket
             .fields
             .components
@@ -64,6 +77,7 @@ impl ComponentsWidget {
         Self {
             key_config,
             components: vec![],
+            offset: 0,
             state,
         }
     }
@@ -110,6 +124,14 @@ impl ComponentsWidget {
         }
     }
 
+    // click selects the component drawn at the row of the rect
+    pub fn click(&mut self, rect: Rect, row: u16) {
+        let index = clicked_row(rect, 0, self.offset, row).filter(|i| *i < self.components.len());
+        if index.is_some() {
+            self.state.select(index);
+        }
+    }
+
     pub async fn update(&mut self, components: &[Components]) -> anyhow::Result<()> {
         self.components = components
             .iter()
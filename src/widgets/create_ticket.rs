@@ -4,21 +4,34 @@ use tui::{
     layout::{Constraint, Direction, Layout},
     style::{Modifier, Style},
     text::{Span, Spans, Text},
-    widgets::{Cell, Paragraph, Row, Table, TableState, Wrap},
+    widgets::{Cell, Clear, Paragraph, Row, Table, TableState, Wrap},
     Frame,
 };
 
-use crate::{config::KeyConfig, events::key::Key};
-use std::{char, collections::HashMap};
+use crate::{
+    config::KeyConfig,
+    events::key::Key,
+    jira::{
+        clone::{clone_values, CloneSource},
+        createmeta::{CreateMetaField, FieldKind},
+        tickets::{TicketData, TicketType},
+    },
+    templates::{TemplateVars, TicketTemplate},
+    theme::theme,
+};
+use std::collections::{BTreeMap, HashMap};
 
 use crate::jira::tickets::CreateTicket;
 
-use super::{draw_block_style, draw_edit_block_style, draw_highlight_style, EventState, InputMode};
+use super::{
+    draw_block_style, draw_edit_block_style, draw_highlight_style, text_area::TextArea, EventState,
+    InputMode,
+};
 
 #[derive(Debug)]
 pub enum FocusCreateTicket {
-    Description,
-    Summary,
+    Fields,
+    Template,
     TicketType,
 }
 
@@ -29,6 +42,7 @@ pub enum TicketTypeAction {
     Last,
     First,
     NextFocus,
+    Select,
 }
 #[derive(Debug, Clone, Copy)]
 pub enum Action {
@@ -36,17 +50,39 @@ pub enum Action {
     PushCreateTicketContent,
     NextFocus,
     PreviousFocus,
+    OpenEditor,
+    ToggleCloneLink,
+    ToggleCloneSubtasks,
 }
 
 #[derive(Debug)]
 pub struct CreateTicketWidget {
+    field_state: TableState,
     focus: FocusCreateTicket,
     input_mode: InputMode,
+    template_state: TableState,
+    templates: Vec<TicketTemplate>,
     ticket_type_state: TableState,
     ticket_type_key_mapping: HashMap<Key, TicketTypeAction>,
+    // clone is set when the form was filled from another ticket
+    pub clone: Option<CloneSource>,
     pub contents: CreateTicket,
+    // editor edits the value of the selected field
+    editor: TextArea,
+    // editor_buffer keeps a document from $EDITOR that could not be read, it
+    // is opened again instead of the form
+    editor_buffer: Option<String>,
+    // errors are the problems of the form by field id, found before sending
+    // or returned by Jira
+    pub errors: BTreeMap<String, String>,
+    pub fields: Vec<CreateMetaField>,
+    // load_fields asks for the fields of the selected ticket type
+    pub load_fields: bool,
+    pub open_editor: bool,
     pub push_content: bool,
     pub key_mappings: HashMap<Key, Action>,
+    // template_vars are substituted in the template picked for the ticket
+    pub template_vars: TemplateVars,
 }
 
 impl CreateTicketWidget {
@@ -57,6 +93,12 @@ impl CreateTicketWidget {
             map.insert(key_config.next, Action::NextFocus);
             map.insert(key_config.previous, Action::PreviousFocus);
             map.insert(key_config.edit, Action::Edit);
+            map.insert(key_config.open_editor, Action::OpenEditor);
+            map.insert(key_config.toggle_clone_link, Action::ToggleCloneLink);
+            map.insert(
+                key_config.toggle_clone_subtasks,
+                Action::ToggleCloneSubtasks,
+            );
             map
         };
         let ticket_type_key_mapping = {
@@ -66,27 +108,43 @@ impl CreateTicketWidget {
             map.insert(key_config.scroll_to_top, TicketTypeAction::First);
             map.insert(key_config.scroll_to_bottom, TicketTypeAction::Last);
             map.insert(key_config.next, TicketTypeAction::NextFocus);
+            map.insert(key_config.enter, TicketTypeAction::Select);
             map
         };
 
         let mut ticket_type_state = TableState::default();
         ticket_type_state.select(Some(0));
         Self {
+            clone: None,
             contents: CreateTicket::new(),
+            editor: TextArea::new(),
+            editor_buffer: None,
+            errors: BTreeMap::new(),
+            field_state: TableState::default(),
+            fields: vec![],
             focus: FocusCreateTicket::TicketType,
             input_mode: InputMode::Normal,
+            load_fields: false,
+            open_editor: false,
             push_content: false,
             key_mappings,
+            template_state: TableState::default(),
+            template_vars: TemplateVars::default(),
+            templates: vec![],
             ticket_type_state,
             ticket_type_key_mapping,
         }
     }
 
     pub fn draw<B: Backend>(&mut self, f: &mut Frame<B>) -> anyhow::Result<()> {
+        f.render_widget(Clear, f.size());
+        let helper_height = match self.clone {
+            Some(_) => 2,
+            None => 1,
+        };
         let constraints = [
-            Constraint::Percentage(5),  // Helper
-            Constraint::Percentage(20), // Ticket type and Summary
-            Constraint::Percentage(75), // Description
+            Constraint::Length(helper_height), // Helper
+            Constraint::Min(10),               // Ticket type and fields
         ];
 
         let main_chunks = Layout::default()
@@ -94,42 +152,41 @@ impl CreateTicketWidget {
             .constraints(constraints)
             .split(f.size());
 
-        let helper_constraint = [Constraint::Percentage(100)];
-        let helper_layout = Layout::default()
-            .direction(Direction::Vertical)
-            .constraints(helper_constraint)
-            .split(main_chunks[0]);
-
-        let type_and_summary_constraint = [Constraint::Percentage(30), Constraint::Percentage(70)];
-        let type_and_summary_layout = Layout::default()
+        let type_and_fields_constraint = [Constraint::Percentage(25), Constraint::Percentage(75)];
+        let type_and_fields_layout = Layout::default()
             .direction(Direction::Horizontal)
-            .constraints(type_and_summary_constraint)
+            .constraints(type_and_fields_constraint)
             .split(main_chunks[1]);
 
-        let description_constraint = [Constraint::Percentage(100)];
-        let description_layout = Layout::default()
+        let fields_layout = Layout::default()
             .direction(Direction::Vertical)
-            .constraints(description_constraint)
-            .split(main_chunks[2]);
-
-        let ticket_type_chunk = type_and_summary_layout[0];
+            .constraints([Constraint::Min(5), Constraint::Length(10)])
+            .split(type_and_fields_layout[1]);
+
+        let (template_chunk, ticket_type_chunk) = match self.templates.is_empty() {
+            true => (None, type_and_fields_layout[0]),
+            false => {
+                let template_height = (self.templates.len() as u16 + 3).min(12);
+                let chunks = Layout::default()
+                    .direction(Direction::Vertical)
+                    .constraints([Constraint::Length(template_height), Constraint::Min(5)])
+                    .split(type_and_fields_layout[0]);
+                (Some(chunks[0]), chunks[1])
+            }
+        };
         let ticket_type_title = "Ticket Type";
 
-        let summary_chunk = type_and_summary_layout[1];
-        let summary_title = "Summary";
-
-        let description_chunk = description_layout[0];
-        let description_title = "Description";
-
         let normal_mode_style = (
             vec![
                 Span::raw("Press "),
                 Span::styled("ESC", Style::default().add_modifier(Modifier::BOLD)),
                 Span::raw(" to exit, "),
                 Span::styled("e", Style::default().add_modifier(Modifier::BOLD)),
-                Span::raw(" to start editing."),
+                Span::raw(" to edit the selected field."),
                 Span::styled(" P", Style::default().add_modifier(Modifier::BOLD)),
-                Span::raw(" to create ticket in JIRA."),
+                Span::raw(" to create ticket in JIRA, "),
+                Span::styled("Ctrl-e", Style::default().add_modifier(Modifier::BOLD)),
+                Span::raw(" to write it in $EDITOR."),
             ],
             Style::default().add_modifier(Modifier::UNDERLINED),
         );
@@ -138,6 +195,8 @@ impl CreateTicketWidget {
                 Span::raw("Press "),
                 Span::styled("Esc", Style::default().add_modifier(Modifier::BOLD)),
                 Span::raw(" to stop editing, "),
+                Span::styled("Tab", Style::default().add_modifier(Modifier::BOLD)),
+                Span::raw(" for the next field."),
             ],
             Style::default(),
         );
@@ -147,8 +206,51 @@ impl CreateTicketWidget {
         };
         let mut text = Text::from(Spans::from(msg));
         text.patch_style(style);
+        if let Some(clone) = &self.clone {
+            let checkbox = |checked: bool| if checked { "[x]" } else { "[ ]" };
+            text.extend(Text::from(Spans::from(vec![
+                Span::raw(format!("Cloning {}: ", clone.ticket_key)),
+                Span::raw(format!("{} link as clone ", checkbox(clone.link))),
+                Span::styled("L", Style::default().add_modifier(Modifier::BOLD)),
+                Span::raw(format!(
+                    ", {} copy {} sub-tasks ",
+                    checkbox(clone.copy_subtasks),
+                    clone.subtask_keys.len()
+                )),
+                Span::styled("T", Style::default().add_modifier(Modifier::BOLD)),
+            ])));
+        }
         let help_message = Paragraph::new(text);
-        f.render_widget(help_message, helper_layout[0]);
+        f.render_widget(help_message, main_chunks[0]);
+
+        if let Some(template_chunk) = template_chunk {
+            // templates of a type the project does not have are greyed out
+            let template_rows = self.templates.iter().map(|template| {
+                let known_type = template.ticket_type.as_ref().is_none_or(|name| {
+                    self.contents
+                        .ticket_types
+                        .iter()
+                        .any(|ticket_type| ticket_type.name.eq_ignore_ascii_case(name))
+                });
+                let row = Row::new([
+                    template.name.clone(),
+                    template.ticket_type.clone().unwrap_or_default(),
+                ]);
+                match known_type {
+                    true => row,
+                    false => row.style(theme().muted),
+                }
+            });
+            let template_table = Table::new(template_rows)
+                .header(Row::new(["Template", "Type"]))
+                .highlight_style(draw_highlight_style())
+                .block(draw_block_style(
+                    matches!(self.focus, FocusCreateTicket::Template),
+                    "Templates",
+                ))
+                .widths(&[Constraint::Percentage(60), Constraint::Percentage(40)]);
+            f.render_stateful_widget(template_table, template_chunk, &mut self.template_state);
+        }
 
         let ticket_type_headers_cells = ["Id", "Name"];
         let ticket_type_headers = Row::new(ticket_type_headers_cells);
@@ -171,73 +273,273 @@ impl CreateTicketWidget {
                 matches!(self.focus, FocusCreateTicket::TicketType),
                 ticket_type_title,
             ))
-            .widths(&[Constraint::Percentage(20), Constraint::Percentage(80)]);
+            .widths(&[Constraint::Percentage(30), Constraint::Percentage(70)]);
         f.render_stateful_widget(
             ticket_type_table,
             ticket_type_chunk,
             &mut self.ticket_type_state,
         );
 
-        let summary_input = Paragraph::new(self.contents.summary.as_ref())
-            .wrap(Wrap { trim: true })
-            .style(draw_edit_block_style(
-                matches!(self.focus, FocusCreateTicket::Summary),
-                &self.input_mode,
-            ))
-            .block(draw_block_style(
-                matches!(self.focus, FocusCreateTicket::Summary),
-                summary_title,
-            ));
-        f.render_widget(summary_input, summary_chunk);
-
-        let description_input = Paragraph::new(self.contents.description.as_ref())
-            .wrap(Wrap { trim: true })
-            .style(draw_edit_block_style(
-                matches!(self.focus, FocusCreateTicket::Description),
-                &self.input_mode,
-            ))
-            .block(draw_block_style(
-                matches!(self.focus, FocusCreateTicket::Description),
-                description_title,
-            ));
-        f.render_widget(description_input, description_chunk);
+        let fields_focused = matches!(self.focus, FocusCreateTicket::Fields);
+        let field_rows = self.fields.iter().map(|field| {
+            let name = match field.required && !field.has_default_value {
+                true => format!("{} *", field.name),
+                false => field.name.clone(),
+            };
+            let value = self
+                .contents
+                .values
+                .get(&field.field_id)
+                .map(|value| value.lines().next().unwrap_or_default().to_string())
+                .unwrap_or_default();
+            let row = Row::new([Cell::from(name), Cell::from(value)]);
+            match self.errors.contains_key(&field.field_id) {
+                true => row.style(theme().error),
+                false => row,
+            }
+        });
+        let fields_table = Table::new(field_rows)
+            .header(Row::new(["Field", "Value"]))
+            .highlight_style(draw_highlight_style())
+            .block(draw_block_style(fields_focused, "Fields"))
+            .widths(&[Constraint::Percentage(30), Constraint::Percentage(70)]);
+        f.render_stateful_widget(fields_table, fields_layout[0], &mut self.field_state);
+
+        let (details_title, details) = match self.selected_field() {
+            Some(field) => {
+                let mut lines = vec![Spans::from(Span::styled(
+                    field.hint(),
+                    Style::default().add_modifier(Modifier::ITALIC),
+                ))];
+                if let Some(error) = self.errors.get(&field.field_id) {
+                    lines.push(Spans::from(Span::styled(
+                        format!("{} {error}", field.name),
+                        theme().error,
+                    )));
+                }
+                lines.push(Spans::from(""));
+                let value = self
+                    .contents
+                    .values
+                    .get(&field.field_id)
+                    .cloned()
+                    .unwrap_or_default();
+                lines.extend(value.lines().map(|line| Spans::from(line.to_string())));
+                (field.name.clone(), lines)
+            }
+            None => (String::from("Field"), vec![]),
+        };
+        let style = draw_edit_block_style(fields_focused, &self.input_mode);
+        if self.input_mode == InputMode::Editing {
+            let title = match self.selected_field() {
+                Some(field) => format!("{} - {}", field.name, field.hint()),
+                None => details_title,
+            };
+            let block = draw_block_style(fields_focused, &title);
+            self.editor
+                .draw(f, fields_layout[1], block, style, fields_focused);
+            return Ok(());
+        }
+        let details = Paragraph::new(details)
+            .wrap(Wrap { trim: false })
+            .style(style)
+            .block(draw_block_style(fields_focused, &details_title));
+        f.render_widget(details, fields_layout[1]);
 
         Ok(())
     }
 
-    pub fn input_pop(&mut self) {
-        match self.focus {
-            FocusCreateTicket::Description => {
-                self.contents.description.pop();
+    pub fn selected_field(&self) -> Option<&CreateMetaField> {
+        self.fields.get(self.field_state.selected()?)
+    }
+
+    pub fn selected_ticket_type(&self) -> Option<&TicketType> {
+        self.contents
+            .ticket_types
+            .get(self.ticket_type_state.selected()?)
+    }
+
+    // set_fields shows the fields of the selected ticket type, values typed for
+    // fields of another type are kept
+    pub fn set_fields(&mut self, fields: Vec<CreateMetaField>) {
+        self.fields = fields;
+        self.errors
+            .retain(|field_id, _| self.fields.iter().any(|f| f.field_id == *field_id));
+        self.field_state.select(if self.fields.is_empty() {
+            None
+        } else {
+            Some(0)
+        });
+    }
+
+    // set_ticket_types resets the form for a new ticket, the templates are
+    // offered first when there are any
+    pub fn set_ticket_types(&mut self, ticket_types: Vec<TicketType>) {
+        self.contents.ticket_types = ticket_types;
+        self.ticket_type_state.select(Some(0));
+        self.focus = match self.templates.is_empty() {
+            true => FocusCreateTicket::TicketType,
+            false => FocusCreateTicket::Template,
+        };
+        self.input_mode = InputMode::Normal;
+        self.load_fields = true;
+    }
+
+    pub fn set_templates(&mut self, templates: Vec<TicketTemplate>) {
+        self.template_state
+            .select(if templates.is_empty() { None } else { Some(0) });
+        self.templates = templates;
+    }
+
+    // select_ticket_type_named selects the ticket type with the given name,
+    // it returns false when the project has no such type
+    pub fn select_ticket_type_named(&mut self, name: &str) -> bool {
+        let index = self
+            .contents
+            .ticket_types
+            .iter()
+            .position(|ticket_type| ticket_type.name.eq_ignore_ascii_case(name));
+        self.select_type(index);
+        index.is_some()
+    }
+
+    // apply_template replaces the form values with the selected template
+    pub fn apply_template(&mut self) {
+        let Some(template) = self
+            .template_state
+            .selected()
+            .and_then(|i| self.templates.get(i))
+            .cloned()
+        else {
+            return;
+        };
+        self.contents.values = template.substituted(&self.template_vars).values();
+        self.errors.clear();
+        if let Some(ticket_type) = &template.ticket_type {
+            self.select_ticket_type_named(ticket_type);
+        }
+        self.focus = FocusCreateTicket::Fields;
+    }
+
+    // start_clone fills the form from the ticket to clone
+    pub fn start_clone(&mut self, ticket: &TicketData) {
+        self.contents.values = clone_values(ticket);
+        self.errors.clear();
+        self.clone = Some(CloneSource::from_ticket(ticket));
+        self.select_ticket_type_named(&ticket.fields.issuetype.name);
+        self.focus = FocusCreateTicket::Fields;
+    }
+
+    // document is the form as markdown with a frontmatter, to be written in
+    // the external editor
+    pub fn document(&self) -> Result<String, String> {
+        if let Some(buffer) = &self.editor_buffer {
+            return Ok(buffer.clone());
+        }
+        let field_ids: Vec<String> = self.fields.iter().map(|f| f.field_id.clone()).collect();
+        TicketTemplate::from_values(
+            self.selected_ticket_type().map(|t| t.name.clone()),
+            &self.contents.values,
+            &field_ids,
+        )
+        .to_document()
+    }
+
+    // apply_document fills the form from a document of the external editor,
+    // a document that can not be read is kept for the next edit
+    pub fn apply_document(&mut self, text: &str) -> Result<(), String> {
+        let document = match TicketTemplate::parse("ticket.md", text) {
+            Ok(document) => document,
+            Err(e) => {
+                self.editor_buffer = Some(text.to_string());
+                return Err(e);
             }
-            FocusCreateTicket::Summary => {
-                self.contents.summary.pop();
+        };
+        self.editor_buffer = None;
+        self.contents.values = document.values();
+        self.errors.clear();
+        if let Some(ticket_type) = &document.ticket_type {
+            if !self.select_ticket_type_named(ticket_type) {
+                self.editor_buffer = Some(text.to_string());
+                return Err(format!("unknown ticket type {ticket_type}"));
             }
-            _ => {}
+        }
+        Ok(())
+    }
+
+    pub fn clear(&mut self) {
+        self.clone = None;
+        self.editor_buffer = None;
+        self.contents.values.clear();
+        self.errors.clear();
+        self.input_mode = InputMode::Normal;
+    }
+
+    pub fn is_editing(&self) -> bool {
+        self.input_mode == InputMode::Editing
+    }
+
+    // start_editing puts the value of the selected field in the editor, rich
+    // text fields get a multi-line one
+    fn start_editing(&mut self) {
+        self.focus = FocusCreateTicket::Fields;
+        self.input_mode = InputMode::Editing;
+        let Some(field) = self.selected_field() else {
+            return;
+        };
+        let value = self.contents.values.get(&field.field_id).cloned();
+        self.editor = match field.kind() {
+            FieldKind::RichText => TextArea::new(),
+            _ => TextArea::single_line(),
         };
+        self.editor.set_text(&value.unwrap_or_default());
     }
-    pub fn input(&mut self, c: char) {
-        match self.focus {
-            FocusCreateTicket::Description => self.contents.description.push(c),
-            FocusCreateTicket::Summary => self.contents.summary.push(c),
-            _ => (),
+
+    // input sends the key to the editor and keeps its text as the field value
+    fn input(&mut self, key: Key) -> EventState {
+        let Some(field_id) = self.selected_field().map(|f| f.field_id.clone()) else {
+            return EventState::NotConsumed;
         };
+        let state = self.editor.input(key);
+        if state.is_consumed() {
+            self.errors.remove(&field_id);
+            self.contents.values.insert(field_id, self.editor.text());
+        }
+        state
     }
 
     pub fn next_focus(&mut self) {
-        match self.focus {
-            FocusCreateTicket::TicketType => self.focus = FocusCreateTicket::Summary,
-            FocusCreateTicket::Summary => self.focus = FocusCreateTicket::Description,
-            FocusCreateTicket::Description => self.focus = FocusCreateTicket::TicketType,
+        self.focus = match self.focus {
+            FocusCreateTicket::Template => FocusCreateTicket::TicketType,
+            FocusCreateTicket::TicketType => FocusCreateTicket::Fields,
+            FocusCreateTicket::Fields if self.templates.is_empty() => FocusCreateTicket::TicketType,
+            FocusCreateTicket::Fields => FocusCreateTicket::Template,
         };
     }
     pub fn previous_focus(&mut self) {
-        match self.focus {
-            FocusCreateTicket::TicketType => self.focus = FocusCreateTicket::Description,
-            FocusCreateTicket::Summary => self.focus = FocusCreateTicket::TicketType,
-            FocusCreateTicket::Description => self.focus = FocusCreateTicket::Summary,
+        self.focus = match self.focus {
+            FocusCreateTicket::Fields => FocusCreateTicket::TicketType,
+            FocusCreateTicket::TicketType if self.templates.is_empty() => FocusCreateTicket::Fields,
+            FocusCreateTicket::TicketType => FocusCreateTicket::Template,
+            FocusCreateTicket::Template => FocusCreateTicket::Fields,
         };
     }
+
+    pub fn next_field(&mut self, line: usize) {
+        if self.fields.is_empty() {
+            return;
+        }
+        let i = self
+            .field_state
+            .selected()
+            .map_or(0, |i| (i + line).min(self.fields.len() - 1));
+        self.field_state.select(Some(i));
+    }
+
+    pub fn previous_field(&mut self, line: usize) {
+        let i = self.field_state.selected().map(|i| i.saturating_sub(line));
+        self.field_state.select(i);
+    }
 }
 
 impl CreateTicketWidget {
@@ -277,17 +579,12 @@ impl CreateTicketWidget {
     }
 
     pub fn select_type(&mut self, index: Option<usize>) {
-        if index.is_some() {
-            self.ticket_type_state.select(index)
+        if index.is_some() && index != self.ticket_type_state.selected() {
+            self.ticket_type_state.select(index);
+            self.load_fields = true;
         }
     }
 
-    // get_ticket_type returns the index of selected ticket type
-    pub fn get_ticket_type(&self) -> usize {
-        let index = self.ticket_type_state.selected();
-        index.unwrap_or(0)
-    }
-
     fn ticket_type_key_event(&mut self, key: Key) -> anyhow::Result<EventState> {
         if let Some(ticket_type_action) = self.ticket_type_key_mapping.get(&key) {
             use TicketTypeAction::*;
@@ -297,6 +594,47 @@ impl CreateTicketWidget {
                 Last => self.go_to_bottom_ticket_type(),
                 First => self.go_to_top_ticket_type(),
                 NextFocus => self.next_focus(),
+                Select => self.focus = FocusCreateTicket::Fields,
+            }
+            return Ok(EventState::Consumed);
+        }
+        Ok(EventState::NotConsumed)
+    }
+
+    // field_key_event moves through the fields with the same keys as the
+    // ticket type list
+    fn field_key_event(&mut self, key: Key) -> anyhow::Result<EventState> {
+        if let Some(action) = self.ticket_type_key_mapping.get(&key) {
+            use TicketTypeAction::*;
+            match *action {
+                Next(line) => self.next_field(line),
+                Previous(line) => self.previous_field(line),
+                Last => self.next_field(self.fields.len()),
+                First => self.previous_field(self.fields.len()),
+                NextFocus => self.next_focus(),
+                Select => self.start_editing(),
+            }
+            return Ok(EventState::Consumed);
+        }
+        Ok(EventState::NotConsumed)
+    }
+
+    fn template_key_event(&mut self, key: Key) -> anyhow::Result<EventState> {
+        if let Some(action) = self.ticket_type_key_mapping.get(&key) {
+            let len = self.templates.len();
+            let selected = self.template_state.selected().unwrap_or(0);
+            use TicketTypeAction::*;
+            match *action {
+                Next(line) => self
+                    .template_state
+                    .select(Some((selected + line).min(len.saturating_sub(1)))),
+                Previous(line) => self
+                    .template_state
+                    .select(Some(selected.saturating_sub(line))),
+                Last => self.template_state.select(Some(len.saturating_sub(1))),
+                First => self.template_state.select(Some(0)),
+                NextFocus => self.next_focus(),
+                Select => self.apply_template(),
             }
             return Ok(EventState::Consumed);
         }
@@ -312,7 +650,7 @@ impl CreateTicketWidget {
             match *action {
                 Edit => {
                     debug!("Entering edit mode");
-                    self.input_mode = InputMode::Editing;
+                    self.start_editing();
                 }
                 PushCreateTicketContent => {
                     debug!("Pushing new ticket content");
@@ -326,13 +664,25 @@ impl CreateTicketWidget {
                     debug!("Going to previous focus");
                     self.previous_focus()
                 }
+                OpenEditor => self.open_editor = true,
+                ToggleCloneLink => {
+                    if let Some(clone) = &mut self.clone {
+                        clone.link = !clone.link;
+                    }
+                }
+                ToggleCloneSubtasks => {
+                    if let Some(clone) = &mut self.clone {
+                        clone.copy_subtasks = !clone.copy_subtasks;
+                    }
+                }
             }
             return Ok(EventState::Consumed);
         }
-        if let FocusCreateTicket::TicketType = self.focus {
-            return self.ticket_type_key_event(key);
+        match self.focus {
+            FocusCreateTicket::Template => self.template_key_event(key),
+            FocusCreateTicket::TicketType => self.ticket_type_key_event(key),
+            FocusCreateTicket::Fields => self.field_key_event(key),
         }
-        Ok(EventState::NotConsumed)
     }
 
     pub fn edit_mode_key_event(&mut self, key: Key) -> anyhow::Result<EventState> {
@@ -340,27 +690,43 @@ impl CreateTicketWidget {
         trace!("Key: {:?}", key);
         match key {
             Key::Tab => {
-                self.next_focus();
+                self.next_field(1);
+                self.start_editing();
                 Ok(EventState::Consumed)
             }
             Key::BackTab => {
-                self.previous_focus();
-                Ok(EventState::Consumed)
-            }
-            Key::Char(c) => {
-                self.input(c);
-                Ok(EventState::Consumed)
-            }
-            Key::Backspace => {
-                self.input_pop();
+                self.previous_field(1);
+                self.start_editing();
                 Ok(EventState::Consumed)
             }
             Key::Esc => {
                 self.input_mode = InputMode::Normal;
                 Ok(EventState::Consumed)
             }
-            _ => Ok(EventState::NotConsumed),
+            // Enter is a new line in rich text fields and ends the others
+            _ => match self.input(key) {
+                EventState::NotConsumed if key == Key::Enter => {
+                    self.input_mode = InputMode::Normal;
+                    Ok(EventState::Consumed)
+                }
+                state => Ok(state),
+            },
+        }
+    }
+
+    // paste fills the field being edited in one go, newlines only stay in
+    // rich text fields
+    pub fn paste(&mut self, text: &str) -> EventState {
+        if self.input_mode != InputMode::Editing {
+            return EventState::NotConsumed;
         }
+        let Some(field_id) = self.selected_field().map(|f| f.field_id.clone()) else {
+            return EventState::NotConsumed;
+        };
+        self.editor.insert_str(text);
+        self.errors.remove(&field_id);
+        self.contents.values.insert(field_id, self.editor.text());
+        EventState::Consumed
     }
 
     pub fn event(&mut self, key: Key) -> anyhow::Result<EventState> {
@@ -0,0 +1,203 @@
+use std::collections::HashMap;
+
+use tui::{
+    backend::Backend,
+    layout::{Constraint, Direction, Layout, Rect},
+    widgets::{Cell, Clear, Gauge, Row, Table, TableState},
+    Frame,
+};
+
+use crate::{config::KeyConfig, events::key::Key, jira::epics::EpicProgress, theme::theme};
+
+use super::{
+    commands::{CommandInfo, CommandText},
+    draw_block_style, draw_highlight_style, Component, EventState,
+};
+
+const PROGRESS_BAR_WIDTH: usize = 20;
+
+#[derive(Debug, Clone, Copy)]
+pub enum Action {
+    Next(usize),
+    Previous(usize),
+    Last,
+    First,
+}
+
+impl Action {
+    pub fn to_command_text(self, key: Key) -> CommandText {
+        const CMD_GROUP_GENERAL: &str = "-- General --";
+        match self {
+            Self::Next(line) => CommandText::new(format!("Next {line} [{key}]"), CMD_GROUP_GENERAL),
+            Self::Previous(line) => {
+                CommandText::new(format!("Previous {line} [{key}]"), CMD_GROUP_GENERAL)
+            }
+            Self::Last => CommandText::new(format!("Go to last [{key}]"), CMD_GROUP_GENERAL),
+            Self::First => CommandText::new(format!("Go to first [{key}]"), CMD_GROUP_GENERAL),
+        }
+    }
+}
+
+// progress_bar renders the done ratio as a fixed width text bar for table cells
+fn progress_bar(epic: &EpicProgress) -> String {
+    let filled = (epic.ratio() * PROGRESS_BAR_WIDTH as f64).round() as usize;
+    format!(
+        "{}{} {:>3}%",
+        "█".repeat(filled),
+        "░".repeat(PROGRESS_BAR_WIDTH - filled),
+        (epic.ratio() * 100.0).round()
+    )
+}
+
+#[derive(Debug)]
+pub struct EpicsWidget {
+    epics: Vec<EpicProgress>,
+    state: TableState,
+    pub project_key: Option<String>,
+    pub key_mappings: HashMap<Key, Action>,
+}
+
+impl EpicsWidget {
+    pub fn draw<B: Backend>(
+        &mut self,
+        f: &mut Frame<B>,
+        focused: bool,
+        rect: Rect,
+    ) -> anyhow::Result<()> {
+        f.render_widget(Clear, rect);
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(3), Constraint::Length(3)])
+            .split(rect);
+        let title = match &self.project_key {
+            Some(project_key) => format!("Epics - {project_key}"),
+            None => String::from("Epics"),
+        };
+
+        let headers = Row::new(["Key", "Summary", "Status", "Children", "Progress"]);
+        let rows = self.epics.iter().map(|epic| {
+            let children = format!(
+                "{}/{} done, {} in progress",
+                epic.done, epic.total, epic.in_progress
+            );
+            Row::new([
+                Cell::from(epic.key.clone()),
+                Cell::from(epic.summary.clone()),
+                Cell::from(epic.status.clone()),
+                Cell::from(children),
+                Cell::from(progress_bar(epic)),
+            ])
+        });
+        let table = Table::new(rows)
+            .header(headers)
+            .block(draw_block_style(focused, &title))
+            .highlight_style(draw_highlight_style())
+            .widths(&[
+                Constraint::Percentage(10),
+                Constraint::Percentage(35),
+                Constraint::Percentage(12),
+                Constraint::Percentage(18),
+                Constraint::Percentage(25),
+            ]);
+        f.render_stateful_widget(table, chunks[0], &mut self.state);
+
+        let (gauge_title, ratio) = match self.selected() {
+            Some(epic) => (
+                format!("{} - {}/{} children done", epic.key, epic.done, epic.total),
+                epic.ratio(),
+            ),
+            None => (String::from("Progress"), 0.0),
+        };
+        let gauge = Gauge::default()
+            .block(draw_block_style(false, &gauge_title))
+            .gauge_style(theme().success)
+            .ratio(ratio.clamp(0.0, 1.0));
+        f.render_widget(gauge, chunks[1]);
+
+        Ok(())
+    }
+}
+
+impl EpicsWidget {
+    pub fn new(key_config: KeyConfig) -> Self {
+        let key_mappings = {
+            let mut map = HashMap::new();
+            map.insert(key_config.scroll_down, Action::Next(1));
+            map.insert(key_config.scroll_up, Action::Previous(1));
+            map.insert(key_config.scroll_down_multiple_lines, Action::Next(10));
+            map.insert(key_config.scroll_up_multiple_lines, Action::Previous(10));
+            map.insert(key_config.scroll_to_bottom, Action::Last);
+            map.insert(key_config.scroll_to_top, Action::First);
+            map
+        };
+
+        Self {
+            epics: vec![],
+            key_mappings,
+            project_key: None,
+            state: TableState::default(),
+        }
+    }
+
+    pub fn next(&mut self, line: usize) {
+        if self.epics.is_empty() {
+            return;
+        }
+        let i = self
+            .state
+            .selected()
+            .map(|i| (i + line).min(self.epics.len() - 1));
+        self.state.select(i);
+    }
+
+    pub fn previous(&mut self, line: usize) {
+        let i = self.state.selected().map(|i| i.saturating_sub(line));
+        self.state.select(i);
+    }
+
+    pub fn go_to_top(&mut self) {
+        if !self.epics.is_empty() {
+            self.state.select(Some(0));
+        }
+    }
+
+    pub fn go_to_bottom(&mut self) {
+        if !self.epics.is_empty() {
+            self.state.select(Some(self.epics.len() - 1));
+        }
+    }
+
+    pub fn selected(&self) -> Option<&EpicProgress> {
+        self.epics.get(self.state.selected()?)
+    }
+
+    pub fn has_epics(&self) -> bool {
+        !self.epics.is_empty()
+    }
+
+    pub fn update(&mut self, project_key: &str, epics: &[EpicProgress]) {
+        self.epics = epics.to_vec();
+        self.project_key = Some(project_key.to_string());
+        self.state
+            .select(if epics.is_empty() { None } else { Some(0) });
+    }
+}
+
+impl Component for EpicsWidget {
+    fn commands(&self, _out: &mut Vec<CommandInfo>) {}
+
+    fn event(&mut self, key: Key) -> anyhow::Result<EventState> {
+        if let Some(action) = self.key_mappings.get(&key) {
+            use Action::*;
+            match *action {
+                Next(line) => self.next(line),
+                Previous(line) => self.previous(line),
+                Last => self.go_to_bottom(),
+                First => self.go_to_top(),
+            }
+            Ok(EventState::Consumed)
+        } else {
+            Ok(EventState::NotConsumed)
+        }
+    }
+}
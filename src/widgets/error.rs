@@ -1,11 +1,11 @@
 use super::{commands::CommandInfo, Component, DrawableComponent, EventState};
 use crate::config::KeyConfig;
 use crate::events::key::Key;
+use crate::theme::theme;
 use anyhow::Result;
 use tui::{
     backend::Backend,
     layout::{Alignment, Rect},
-    style::{Color, Style},
     widgets::{Block, Borders, Clear, Paragraph, Wrap},
     Frame,
 };
@@ -40,7 +40,7 @@ impl DrawableComponent for ErrorComponent {
             let height = 10;
             let error = Paragraph::new(self.error.to_string())
                 .block(Block::default().title("Error").borders(Borders::ALL))
-                .style(Style::default().fg(Color::Red))
+                .style(theme().error)
                 .alignment(Alignment::Left)
                 .wrap(Wrap { trim: true });
             let area = Rect::new(
@@ -0,0 +1,246 @@
+use std::collections::HashMap;
+
+use tui::{
+    backend::Backend,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Span, Spans},
+    widgets::{Clear, List, ListItem, ListState, Paragraph, Wrap},
+    Frame,
+};
+
+use crate::{config::KeyConfig, events::key::Key, jira::filters::Filter};
+
+use super::{
+    commands::{CommandInfo, CommandText},
+    draw_block_style, draw_edit_block_style, draw_highlight_style,
+    text_area::TextArea,
+    Component, EventState, InputMode,
+};
+
+#[derive(Debug, Clone, Copy)]
+pub enum Action {
+    Next(usize),
+    Previous(usize),
+    Last,
+    First,
+    Search,
+}
+
+impl Action {
+    pub fn to_command_text(self, key: Key) -> CommandText {
+        const CMD_GROUP_GENERAL: &str = "-- General --";
+        match self {
+            Self::Next(line) => CommandText::new(format!("Next {line} [{key}]"), CMD_GROUP_GENERAL),
+            Self::Previous(line) => {
+                CommandText::new(format!("Previous {line} [{key}]"), CMD_GROUP_GENERAL)
+            }
+            Self::Last => CommandText::new(format!("Go to last [{key}]"), CMD_GROUP_GENERAL),
+            Self::First => CommandText::new(format!("Go to first [{key}]"), CMD_GROUP_GENERAL),
+            Self::Search => {
+                CommandText::new(format!("Search filters by name [{key}]"), CMD_GROUP_GENERAL)
+            }
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct FiltersWidget {
+    filters: Vec<Filter>,
+    state: ListState,
+    editor: TextArea,
+    pub input_mode: InputMode,
+    pub key_mappings: HashMap<Key, Action>,
+    pub loaded: bool,
+    pub search_requested: bool,
+}
+
+impl FiltersWidget {
+    pub fn draw<B: Backend>(
+        &mut self,
+        f: &mut Frame<B>,
+        focused: bool,
+        rect: Rect,
+    ) -> anyhow::Result<()> {
+        f.render_widget(Clear, rect);
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(3), Constraint::Min(3)])
+            .split(rect);
+        let body = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+            .split(chunks[1]);
+
+        self.editor.draw(
+            f,
+            chunks[0],
+            draw_block_style(false, "Search filters"),
+            draw_edit_block_style(focused, &self.input_mode),
+            focused && self.input_mode == InputMode::Editing,
+        );
+
+        let input = self.input();
+        let title = match input.trim().is_empty() {
+            true => String::from("Favourite filters"),
+            false => format!("Filters matching \"{}\"", input.trim()),
+        };
+        let items: Vec<ListItem> = self
+            .filters
+            .iter()
+            .map(|filter| {
+                let star = match filter.favourite {
+                    Some(true) => "★ ",
+                    _ => "  ",
+                };
+                ListItem::new(format!("{star}{}", filter.name))
+            })
+            .collect();
+        let list = List::new(items)
+            .block(draw_block_style(
+                focused && self.input_mode == InputMode::Normal,
+                &title,
+            ))
+            .highlight_style(draw_highlight_style());
+        f.render_stateful_widget(list, body[0], &mut self.state);
+
+        let bold = Style::default().add_modifier(Modifier::BOLD);
+        let details = match self.selected() {
+            Some(filter) => {
+                let owner = filter
+                    .owner
+                    .as_ref()
+                    .map(|owner| owner.display_name.clone())
+                    .unwrap_or_default();
+                vec![
+                    Spans::from(vec![Span::styled("Owner: ", bold), Span::raw(owner)]),
+                    Spans::from(""),
+                    Spans::from(Span::styled("JQL", bold)),
+                    Spans::from(filter.jql.clone()),
+                    Spans::from(""),
+                    Spans::from(Span::styled("Description", bold)),
+                    Spans::from(filter.description.clone().unwrap_or_default()),
+                ]
+            }
+            None => vec![],
+        };
+        let details = Paragraph::new(details)
+            .wrap(Wrap { trim: false })
+            .block(draw_block_style(false, "Details"));
+        f.render_widget(details, body[1]);
+
+        Ok(())
+    }
+}
+
+impl FiltersWidget {
+    pub fn new(key_config: KeyConfig) -> Self {
+        let key_mappings = {
+            let mut map = HashMap::new();
+            map.insert(key_config.scroll_down, Action::Next(1));
+            map.insert(key_config.scroll_up, Action::Previous(1));
+            map.insert(key_config.scroll_down_multiple_lines, Action::Next(10));
+            map.insert(key_config.scroll_up_multiple_lines, Action::Previous(10));
+            map.insert(key_config.scroll_to_bottom, Action::Last);
+            map.insert(key_config.scroll_to_top, Action::First);
+            map.insert(key_config.filter, Action::Search);
+            map
+        };
+
+        Self {
+            filters: vec![],
+            editor: TextArea::single_line(),
+            input_mode: InputMode::Normal,
+            key_mappings,
+            loaded: false,
+            search_requested: false,
+            state: ListState::default(),
+        }
+    }
+
+    pub fn next(&mut self, line: usize) {
+        if self.filters.is_empty() {
+            return;
+        }
+        let i = self
+            .state
+            .selected()
+            .map(|i| (i + line).min(self.filters.len() - 1));
+        self.state.select(i);
+    }
+
+    pub fn previous(&mut self, line: usize) {
+        let i = self.state.selected().map(|i| i.saturating_sub(line));
+        self.state.select(i);
+    }
+
+    pub fn go_to_top(&mut self) {
+        if !self.filters.is_empty() {
+            self.state.select(Some(0));
+        }
+    }
+
+    pub fn go_to_bottom(&mut self) {
+        if !self.filters.is_empty() {
+            self.state.select(Some(self.filters.len() - 1));
+        }
+    }
+
+    pub fn selected(&self) -> Option<&Filter> {
+        self.filters.get(self.state.selected()?)
+    }
+
+    pub fn update(&mut self, filters: &[Filter]) {
+        self.filters = filters.to_vec();
+        self.loaded = true;
+        self.state
+            .select(if filters.is_empty() { None } else { Some(0) });
+    }
+
+    pub fn input(&self) -> String {
+        self.editor.text()
+    }
+
+    fn edit_mode_key_event(&mut self, key: Key) -> anyhow::Result<EventState> {
+        match key {
+            Key::Enter => {
+                self.search_requested = true;
+                self.input_mode = InputMode::Normal;
+            }
+            Key::Esc => self.input_mode = InputMode::Normal,
+            _ => return Ok(self.editor.input(key)),
+        }
+        Ok(EventState::Consumed)
+    }
+
+    pub fn paste(&mut self, text: &str) -> EventState {
+        if self.input_mode != InputMode::Editing {
+            return EventState::NotConsumed;
+        }
+        self.editor.insert_str(text);
+        EventState::Consumed
+    }
+}
+
+impl Component for FiltersWidget {
+    fn commands(&self, _out: &mut Vec<CommandInfo>) {}
+
+    fn event(&mut self, key: Key) -> anyhow::Result<EventState> {
+        if self.input_mode == InputMode::Editing {
+            return self.edit_mode_key_event(key);
+        }
+        if let Some(action) = self.key_mappings.get(&key) {
+            use Action::*;
+            match *action {
+                Next(line) => self.next(line),
+                Previous(line) => self.previous(line),
+                Last => self.go_to_bottom(),
+                First => self.go_to_top(),
+                Search => self.input_mode = InputMode::Editing,
+            }
+            Ok(EventState::Consumed)
+        } else {
+            Ok(EventState::NotConsumed)
+        }
+    }
+}
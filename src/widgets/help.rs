@@ -1,4 +1,4 @@
-use super::{Component, DrawableComponent, EventState};
+use super::{draw_highlight_style, Component, DrawableComponent, EventState};
 use crate::config::KeyConfig;
 use crate::events::key::Key;
 use crate::widgets::CommandInfo;
@@ -8,7 +8,7 @@ use std::convert::From;
 use tui::{
     backend::Backend,
     layout::{Constraint, Direction, Layout, Rect},
-    style::{Color, Modifier, Style},
+    style::{Modifier, Style},
     text::{Span, Spans},
     widgets::{Block, BorderType, Borders, Clear, Paragraph},
     Frame,
@@ -143,7 +143,7 @@ impl HelpWidget {
                 txt.push(Spans::from(Span::styled(
                     format!(" {:w$}", command_info.text.name, w = width),
                     if is_selected {
-                        Style::default().bg(Color::Blue)
+                        draw_highlight_style()
                     } else {
                         Style::default()
                     },
@@ -0,0 +1,277 @@
+use std::collections::HashMap;
+
+use crate::{
+    config::KeyConfig,
+    events::key::Key,
+    jira::changelog::{Changelog, ChangelogHistory, ChangelogItem},
+};
+use chrono::DateTime;
+use tui::{
+    backend::Backend,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Span, Spans},
+    widgets::{Cell, Clear, Paragraph, Row, Table, TableState},
+    Frame,
+};
+
+use super::{
+    commands::{CommandInfo, CommandText},
+    draw_block_style, draw_highlight_style, Component, EventState,
+};
+
+#[derive(Debug, Clone, Copy)]
+pub enum Action {
+    Next(usize),
+    Previous(usize),
+    Last,
+    First,
+    NextFieldFilter,
+    ClearFieldFilter,
+}
+
+impl Action {
+    pub fn to_command_text(self, key: Key) -> CommandText {
+        const CMD_GROUP_GENERAL: &str = "-- General --";
+        match self {
+            Self::Next(line) => CommandText::new(format!("Next {line} [{key}]"), CMD_GROUP_GENERAL),
+            Self::Previous(line) => {
+                CommandText::new(format!("Previous {line} [{key}]"), CMD_GROUP_GENERAL)
+            }
+            Self::Last => CommandText::new(format!("Go to last [{key}]"), CMD_GROUP_GENERAL),
+            Self::First => CommandText::new(format!("Go to first [{key}]"), CMD_GROUP_GENERAL),
+            Self::NextFieldFilter => {
+                CommandText::new(format!("Filter by next field [{key}]"), CMD_GROUP_GENERAL)
+            }
+            Self::ClearFieldFilter => {
+                CommandText::new(format!("Clear field filter [{key}]"), CMD_GROUP_GENERAL)
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct HistoryRow {
+    pub author: String,
+    pub created: String,
+    pub field: String,
+    pub from: String,
+    pub to: String,
+}
+
+impl HistoryRow {
+    fn new(history: &ChangelogHistory, item: &ChangelogItem) -> Self {
+        let author = match &history.author {
+            Some(a) => a.display_name.clone(),
+            None => String::from("Automation"),
+        };
+        let created = match DateTime::parse_from_str(&history.created, "%Y-%m-%dT%H:%M:%S%.f%z") {
+            Ok(d) => d.format("%Y-%m-%d %H:%M").to_string(),
+            Err(_) => history.created.clone(),
+        };
+        Self {
+            author,
+            created,
+            field: item.field.clone(),
+            from: item.old_value(),
+            to: item.new_value(),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct HistoryWidget {
+    fields: Vec<String>,
+    field_filter: Option<usize>,
+    rows: Vec<HistoryRow>,
+    state: TableState,
+    pub ticket_key: String,
+    pub key_mappings: HashMap<Key, Action>,
+}
+
+impl HistoryWidget {
+    pub fn draw<B: Backend>(
+        &mut self,
+        f: &mut Frame<B>,
+        focused: bool,
+        rect: Rect,
+    ) -> anyhow::Result<()> {
+        f.render_widget(Clear, rect);
+        let chunk_constrains = [Constraint::Length(1), Constraint::Min(1)].as_ref();
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .margin(2)
+            .constraints(chunk_constrains)
+            .split(rect);
+        let title = format!("History - {}", self.ticket_key);
+
+        let filter = match self.selected_field() {
+            Some(field) => field.to_string(),
+            None => String::from("All fields"),
+        };
+        let filter_text = Paragraph::new(Spans::from(vec![
+            Span::raw("Field: "),
+            Span::styled(filter, Style::default().add_modifier(Modifier::BOLD)),
+        ]));
+        f.render_widget(filter_text, chunks[0]);
+
+        let header_cells = ["Created", "Author", "Field", "From", "To"];
+        let headers = Row::new(header_cells);
+        let history_rows: Vec<HistoryRow> = self.filtered_rows().into_iter().cloned().collect();
+        let rows = history_rows.iter().map(|row| {
+            let item = [
+                row.created.as_str(),
+                row.author.as_str(),
+                row.field.as_str(),
+                row.from.as_str(),
+                row.to.as_str(),
+            ];
+            let height = item
+                .iter()
+                .map(|content| content.chars().filter(|c| *c == '\n').count())
+                .max()
+                .unwrap_or(0)
+                + 1;
+            let cells = item.iter().map(|c| Cell::from(*c));
+            Row::new(cells).height(height as u16)
+        });
+        let table = Table::new(rows)
+            .header(headers)
+            .block(draw_block_style(focused, &title))
+            .highlight_style(draw_highlight_style())
+            .widths(&[
+                Constraint::Percentage(15),
+                Constraint::Percentage(15),
+                Constraint::Percentage(14),
+                Constraint::Percentage(28),
+                Constraint::Percentage(28),
+            ]);
+
+        f.render_stateful_widget(table, chunks[1], &mut self.state);
+
+        Ok(())
+    }
+}
+
+impl HistoryWidget {
+    pub fn new(key_config: KeyConfig) -> Self {
+        let mut state = TableState::default();
+        state.select(Some(0));
+
+        let key_mappings = {
+            let mut map = HashMap::new();
+            map.insert(key_config.scroll_down, Action::Next(1));
+            map.insert(key_config.scroll_up, Action::Previous(1));
+            map.insert(key_config.scroll_down_multiple_lines, Action::Next(10));
+            map.insert(key_config.scroll_up_multiple_lines, Action::Previous(10));
+            map.insert(key_config.scroll_to_bottom, Action::Last);
+            map.insert(key_config.scroll_to_top, Action::First);
+            map.insert(key_config.filter, Action::NextFieldFilter);
+            map.insert(key_config.reset, Action::ClearFieldFilter);
+            map
+        };
+        Self {
+            fields: vec![],
+            field_filter: None,
+            key_mappings,
+            rows: vec![],
+            state,
+            ticket_key: String::new(),
+        }
+    }
+
+    fn selected_field(&self) -> Option<&String> {
+        self.field_filter.and_then(|i| self.fields.get(i))
+    }
+
+    fn filtered_rows(&self) -> Vec<&HistoryRow> {
+        match self.selected_field() {
+            Some(field) => self.rows.iter().filter(|row| &row.field == field).collect(),
+            None => self.rows.iter().collect(),
+        }
+    }
+
+    pub fn next(&mut self, line: usize) {
+        let len = self.filtered_rows().len();
+        if len == 0 {
+            return;
+        }
+        let i = self.state.selected().map(|i| (i + line).min(len - 1));
+
+        self.state.select(i);
+    }
+
+    pub fn previous(&mut self, line: usize) {
+        let i = self.state.selected().map(|i| i.saturating_sub(line));
+
+        self.state.select(i);
+    }
+
+    pub fn go_to_top(&mut self) {
+        if self.filtered_rows().is_empty() {
+            return;
+        }
+        self.state.select(Some(0));
+    }
+
+    pub fn go_to_bottom(&mut self) {
+        let len = self.filtered_rows().len();
+        if len == 0 {
+            return;
+        }
+        self.state.select(Some(len - 1));
+    }
+
+    pub fn next_field_filter(&mut self) {
+        self.field_filter = match self.field_filter {
+            None if !self.fields.is_empty() => Some(0),
+            Some(i) if i + 1 < self.fields.len() => Some(i + 1),
+            _ => None,
+        };
+        self.state.select(Some(0));
+    }
+
+    pub fn clear_field_filter(&mut self) {
+        self.field_filter = None;
+        self.state.select(Some(0));
+    }
+
+    pub fn update(&mut self, ticket_key: &str, changelog: &Changelog) {
+        let mut histories = changelog.histories.clone();
+        histories.sort_by(|a, b| b.created.cmp(&a.created));
+        self.rows = histories
+            .iter()
+            .flat_map(|history| {
+                history
+                    .items
+                    .iter()
+                    .map(|item| HistoryRow::new(history, item))
+            })
+            .collect();
+        self.fields = changelog.fields();
+        self.field_filter = None;
+        self.ticket_key = ticket_key.to_string();
+        self.state.select(Some(0));
+    }
+}
+
+impl Component for HistoryWidget {
+    fn commands(&self, _out: &mut Vec<CommandInfo>) {}
+
+    fn event(&mut self, key: Key) -> anyhow::Result<EventState> {
+        if let Some(action) = self.key_mappings.get(&key) {
+            use Action::*;
+            match *action {
+                Next(line) => self.next(line),
+                Previous(line) => self.previous(line),
+                Last => self.go_to_bottom(),
+                First => self.go_to_top(),
+                NextFieldFilter => self.next_field_filter(),
+                ClearFieldFilter => self.clear_field_filter(),
+            }
+            Ok(EventState::Consumed)
+        } else {
+            Ok(EventState::NotConsumed)
+        }
+    }
+}
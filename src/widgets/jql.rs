@@ -0,0 +1,383 @@
+use std::collections::{BTreeMap, HashMap};
+
+use tui::{
+    backend::Backend,
+    layout::{Constraint, Direction, Layout},
+    style::{Modifier, Style},
+    text::{Span, Spans, Text},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph},
+    Frame,
+};
+
+use crate::{config::KeyConfig, events::key::Key, jira::jql::JqlAutocomplete};
+
+use super::{
+    commands::{self, CommandText},
+    draw_edit_style, draw_highlight_style,
+    text_area::TextArea,
+    EventState, InputMode,
+};
+
+#[derive(Debug, Clone, Copy)]
+pub enum Action {
+    Edit,
+    Run,
+    Save,
+    Complete,
+    StopEditing,
+    Cancel,
+    Next(usize),
+    Previous(usize),
+}
+
+impl Action {
+    pub fn to_command_text(self, key: Key) -> CommandText {
+        const CMD_GROUP_GENERAL: &str = "-- General --";
+        let name = match self {
+            Self::Edit => format!("Edit the query [{key}]"),
+            Self::Run => format!("Run the query [{key}]"),
+            Self::Save => format!("Save the query in config.toml [{key}]"),
+            Self::Complete => format!("Complete [{key}]"),
+            Self::StopEditing => format!("Stop editing [{key}]"),
+            Self::Cancel => format!("Cancel [{key}]"),
+            Self::Next(line) => format!("Next {line} [{key}]"),
+            Self::Previous(line) => format!("Previous {line} [{key}]"),
+        };
+        CommandText::new(name, CMD_GROUP_GENERAL)
+    }
+}
+
+// MenuEntry is a query that can be picked from the menu below the prompt
+#[derive(Debug, Clone)]
+struct MenuEntry {
+    label: String,
+    jql: String,
+}
+
+pub struct JqlWidget {
+    history: Vec<String>,
+    menu_state: ListState,
+    name_editor: TextArea,
+    naming: bool,
+    saved: BTreeMap<String, String>,
+    suggestions: Vec<String>,
+    suggestions_state: ListState,
+    pub autocomplete: Option<JqlAutocomplete>,
+    editor: TextArea,
+    pub input_mode: InputMode,
+    pub run_query: bool,
+    pub save_query: Option<(String, String)>,
+    go_back: CommandText,
+    pub normal_key_mappings: HashMap<Key, Action>,
+    pub edit_key_mappings: HashMap<Key, Action>,
+    pub naming_key_mappings: HashMap<Key, Action>,
+}
+
+impl JqlWidget {
+    pub fn new(key_config: KeyConfig) -> Self {
+        let normal_key_mappings = HashMap::from([
+            (key_config.edit, Action::Edit),
+            (key_config.enter, Action::Run),
+            (key_config.save_query, Action::Save),
+            (key_config.scroll_down, Action::Next(1)),
+            (key_config.move_down, Action::Next(1)),
+            (key_config.scroll_up, Action::Previous(1)),
+            (key_config.move_up, Action::Previous(1)),
+            (key_config.scroll_down_multiple_lines, Action::Next(10)),
+            (key_config.scroll_up_multiple_lines, Action::Previous(10)),
+        ]);
+        // Plain characters are typed into the query
+        let edit_key_mappings = HashMap::from([
+            (key_config.next, Action::Complete),
+            (key_config.enter, Action::Run),
+            (key_config.save_query, Action::Save),
+            (key_config.esc, Action::StopEditing),
+            (key_config.move_down, Action::Next(1)),
+            (key_config.move_up, Action::Previous(1)),
+            (key_config.scroll_down_multiple_lines, Action::Next(10)),
+            (key_config.scroll_up_multiple_lines, Action::Previous(10)),
+        ]);
+        let naming_key_mappings = HashMap::from([
+            (key_config.enter, Action::Save),
+            (key_config.esc, Action::Cancel),
+        ]);
+        Self {
+            go_back: commands::go_back(&key_config),
+            normal_key_mappings,
+            edit_key_mappings,
+            naming_key_mappings,
+            autocomplete: None,
+            history: vec![],
+            editor: TextArea::single_line(),
+            input_mode: InputMode::Normal,
+            menu_state: ListState::default(),
+            name_editor: TextArea::single_line(),
+            naming: false,
+            run_query: false,
+            save_query: None,
+            saved: BTreeMap::new(),
+            suggestions: vec![],
+            suggestions_state: ListState::default(),
+        }
+    }
+
+    fn menu_entries(&self) -> Vec<MenuEntry> {
+        let saved = self.saved.iter().map(|(name, jql)| MenuEntry {
+            label: format!("[{name}] {jql}"),
+            jql: jql.clone(),
+        });
+        let history = self.history.iter().map(|jql| MenuEntry {
+            label: jql.clone(),
+            jql: jql.clone(),
+        });
+        saved.chain(history).collect()
+    }
+
+    pub fn input(&self) -> String {
+        self.editor.text()
+    }
+
+    // is_editing is also true while the query is being given a name
+    pub fn is_editing(&self) -> bool {
+        self.naming || self.input_mode == InputMode::Editing
+    }
+
+    // current_word is the part of the query before the cursor the suggestions
+    // are matched against
+    fn current_word(&self) -> &str {
+        let before = self.editor.text_before_cursor();
+        let start = before
+            .rfind(|c: char| c.is_whitespace() || c == '(' || c == ',')
+            .map(|i| i + 1)
+            .unwrap_or(0);
+        &before[start..]
+    }
+
+    fn update_suggestions(&mut self) {
+        self.suggestions = match &self.autocomplete {
+            Some(autocomplete) => autocomplete.suggestions(self.current_word()),
+            None => vec![],
+        };
+        self.suggestions_state
+            .select(match self.suggestions.is_empty() {
+                true => None,
+                false => Some(0),
+            });
+    }
+
+    fn accept_suggestion(&mut self) {
+        let Some(suggestion) = self
+            .suggestions_state
+            .selected()
+            .and_then(|i| self.suggestions.get(i))
+            .cloned()
+        else {
+            return;
+        };
+        let word = self.current_word().to_string();
+        self.editor
+            .replace_before_cursor(&word, &format!("{suggestion} "));
+        self.update_suggestions();
+    }
+
+    pub fn update(&mut self, saved: &BTreeMap<String, String>, history: Vec<String>) {
+        self.saved = saved.clone();
+        self.history = history;
+        let entries = self.menu_entries().len();
+        self.menu_state
+            .select(if entries == 0 { None } else { Some(0) });
+        self.update_suggestions();
+    }
+
+    // help_text lists the keys of the current mode, the moves through the
+    // lists are left out
+    fn help_text(&self) -> String {
+        let (prefix, key_mappings) = match (self.naming, &self.input_mode) {
+            (true, _) => ("Type a name: ", &self.naming_key_mappings),
+            (false, InputMode::Normal) => ("", &self.normal_key_mappings),
+            (false, InputMode::Editing) => ("", &self.edit_key_mappings),
+        };
+        let mut commands: Vec<CommandText> = key_mappings
+            .iter()
+            .filter(|(_, action)| !matches!(action, Action::Next(_) | Action::Previous(_)))
+            .map(|(key, action)| action.to_command_text(*key))
+            .collect();
+        if !self.naming && self.input_mode == InputMode::Normal {
+            commands.push(self.go_back.clone());
+        }
+        commands.sort();
+        let names: Vec<String> = commands.into_iter().map(|command| command.name).collect();
+        format!("{prefix}{}", names.join(", "))
+    }
+}
+
+impl JqlWidget {
+    pub fn draw<B: Backend>(&mut self, f: &mut Frame<B>) -> anyhow::Result<()> {
+        let chunk_constrains = [
+            Constraint::Length(1),
+            Constraint::Length(5),
+            Constraint::Min(1),
+        ]
+        .as_ref();
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .margin(2)
+            .constraints(chunk_constrains)
+            .split(f.size());
+        f.render_widget(Clear, f.size());
+
+        let style = match (self.naming, &self.input_mode) {
+            (false, InputMode::Normal) => Style::default().add_modifier(Modifier::UNDERLINED),
+            _ => Style::default(),
+        };
+        let msg = Span::raw(self.help_text());
+        let mut text = Text::from(Spans::from(msg));
+        text.patch_style(style);
+        f.render_widget(Paragraph::new(text), chunks[0]);
+
+        let (input_title, editor) = match self.naming {
+            true => ("Save query as", &mut self.name_editor),
+            false => ("JQL", &mut self.editor),
+        };
+        let editing = self.naming || self.input_mode == InputMode::Editing;
+        let style = draw_edit_style(editing);
+        let block = Block::default().borders(Borders::ALL).title(input_title);
+        editor.draw(f, chunks[1], block, style, editing);
+
+        if self.input_mode == InputMode::Editing && !self.naming && !self.suggestions.is_empty() {
+            let items: Vec<ListItem> = self
+                .suggestions
+                .iter()
+                .map(|s| ListItem::new(s.clone()))
+                .collect();
+            let list = List::new(items)
+                .block(Block::default().borders(Borders::ALL).title("Suggestions"))
+                .highlight_style(draw_highlight_style());
+            f.render_stateful_widget(list, chunks[2], &mut self.suggestions_state);
+        } else {
+            let items: Vec<ListItem> = self
+                .menu_entries()
+                .into_iter()
+                .map(|entry| ListItem::new(entry.label))
+                .collect();
+            let list = List::new(items)
+                .block(
+                    Block::default()
+                        .borders(Borders::ALL)
+                        .title("Saved queries and history"),
+                )
+                .highlight_style(draw_highlight_style());
+            f.render_stateful_widget(list, chunks[2], &mut self.menu_state);
+        }
+
+        Ok(())
+    }
+}
+
+impl JqlWidget {
+    fn movement(state: &mut ListState, len: usize, action: Action) {
+        if len == 0 {
+            return;
+        }
+        let selected = state.selected().unwrap_or(0);
+        let i = match action {
+            Action::Next(lines) => (selected + lines).min(len - 1),
+            Action::Previous(lines) => selected.saturating_sub(lines),
+            _ => return,
+        };
+        state.select(Some(i));
+    }
+
+    fn start_naming(&mut self) {
+        self.name_editor.clear();
+        self.naming = true;
+    }
+
+    fn naming_key_event(&mut self, key: Key) -> anyhow::Result<EventState> {
+        match self.naming_key_mappings.get(&key) {
+            Some(Action::Save) => {
+                let name = self.name_editor.text().trim().to_string();
+                let jql = self.input().trim().to_string();
+                if !name.is_empty() && !jql.is_empty() {
+                    self.save_query = Some((name, jql));
+                }
+                self.naming = false;
+            }
+            Some(_) => self.naming = false,
+            None => return Ok(self.name_editor.input(key)),
+        }
+        Ok(EventState::Consumed)
+    }
+
+    fn normal_mode_key_event(&mut self, key: Key) -> anyhow::Result<EventState> {
+        let Some(action) = self.normal_key_mappings.get(&key).copied() else {
+            return Ok(EventState::NotConsumed);
+        };
+        match action {
+            Action::Edit => {
+                self.input_mode = InputMode::Editing;
+                self.update_suggestions();
+            }
+            Action::Save => self.start_naming(),
+            Action::Run => {
+                let entry = self
+                    .menu_state
+                    .selected()
+                    .and_then(|i| self.menu_entries().get(i).cloned());
+                if let Some(entry) = entry {
+                    self.editor.set_text(&entry.jql);
+                    self.run_query = true;
+                }
+            }
+            _ => {
+                let len = self.menu_entries().len();
+                if len == 0 {
+                    return Ok(EventState::NotConsumed);
+                }
+                Self::movement(&mut self.menu_state, len, action);
+            }
+        }
+        Ok(EventState::Consumed)
+    }
+
+    fn edit_mode_key_event(&mut self, key: Key) -> anyhow::Result<EventState> {
+        let Some(action) = self.edit_key_mappings.get(&key).copied() else {
+            let state = self.editor.input(key);
+            self.update_suggestions();
+            return Ok(state);
+        };
+        match action {
+            Action::Complete => self.accept_suggestion(),
+            Action::Run => self.run_query = !self.input().trim().is_empty(),
+            Action::Save => self.start_naming(),
+            Action::StopEditing => self.input_mode = InputMode::Normal,
+            _ => {
+                let len = self.suggestions.len();
+                Self::movement(&mut self.suggestions_state, len, action);
+            }
+        }
+        Ok(EventState::Consumed)
+    }
+
+    pub fn paste(&mut self, text: &str) -> EventState {
+        if self.naming {
+            self.name_editor.insert_str(text);
+        } else if self.input_mode == InputMode::Editing {
+            self.editor.insert_str(text);
+            self.update_suggestions();
+        } else {
+            return EventState::NotConsumed;
+        }
+        EventState::Consumed
+    }
+
+    pub fn event(&mut self, key: Key) -> anyhow::Result<EventState> {
+        if self.naming {
+            return self.naming_key_event(key);
+        }
+        match self.input_mode {
+            InputMode::Normal => self.normal_mode_key_event(key),
+            InputMode::Editing => self.edit_mode_key_event(key),
+        }
+    }
+}
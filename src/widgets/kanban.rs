@@ -0,0 +1,316 @@
+use std::collections::HashMap;
+
+use tui::{
+    backend::Backend,
+    layout::{Constraint, Direction, Layout, Rect},
+    widgets::{Clear, List, ListItem, ListState},
+    Frame,
+};
+
+use crate::{
+    config::KeyConfig,
+    events::key::Key,
+    jira::{agile::BoardColumn, tickets::TicketData},
+};
+
+use super::{
+    commands::{CommandInfo, CommandText},
+    draw_block_style, draw_highlight_style, Component, EventState,
+};
+
+const MIN_COLUMN_WIDTH: u16 = 24;
+
+#[derive(Debug, Clone, Copy)]
+pub enum Action {
+    Next(usize),
+    Previous(usize),
+    Last,
+    First,
+    NextColumn,
+    PreviousColumn,
+}
+
+impl Action {
+    pub fn to_command_text(self, key: Key) -> CommandText {
+        const CMD_GROUP_GENERAL: &str = "-- General --";
+        match self {
+            Self::Next(line) => CommandText::new(format!("Next {line} [{key}]"), CMD_GROUP_GENERAL),
+            Self::Previous(line) => {
+                CommandText::new(format!("Previous {line} [{key}]"), CMD_GROUP_GENERAL)
+            }
+            Self::Last => CommandText::new(format!("Go to last [{key}]"), CMD_GROUP_GENERAL),
+            Self::First => CommandText::new(format!("Go to first [{key}]"), CMD_GROUP_GENERAL),
+            Self::NextColumn => CommandText::new(format!("Next column [{key}]"), CMD_GROUP_GENERAL),
+            Self::PreviousColumn => {
+                CommandText::new(format!("Previous column [{key}]"), CMD_GROUP_GENERAL)
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct KanbanColumn {
+    pub name: String,
+    pub status_ids: Vec<String>,
+    pub tickets: Vec<TicketData>,
+    state: ListState,
+}
+
+impl KanbanColumn {
+    fn new(name: &str, status_ids: Vec<String>) -> Self {
+        Self {
+            name: name.to_string(),
+            status_ids,
+            tickets: vec![],
+            state: ListState::default(),
+        }
+    }
+
+    // holds reports whether a ticket in the given status belongs to this column
+    pub fn holds(&self, status_id: Option<&String>, status_name: &str) -> bool {
+        match status_id {
+            Some(id) if !self.status_ids.is_empty() => self.status_ids.contains(id),
+            _ => self.name == status_name,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct KanbanWidget {
+    columns: Vec<KanbanColumn>,
+    focus_column: usize,
+    pub board_columns: Option<Vec<BoardColumn>>,
+    // board_name is the board the columns come from, shown before the first
+    // column. Without one the columns follow the status categories.
+    pub board_name: Option<String>,
+    pub project_key: Option<String>,
+    pub key_mappings: HashMap<Key, Action>,
+}
+
+impl KanbanWidget {
+    pub fn draw<B: Backend>(
+        &mut self,
+        f: &mut Frame<B>,
+        focused: bool,
+        rect: Rect,
+    ) -> anyhow::Result<()> {
+        f.render_widget(Clear, rect);
+        if self.columns.is_empty() {
+            let title = "Kanban";
+            f.render_widget(
+                List::new(vec![]).block(draw_block_style(focused, title)),
+                rect,
+            );
+            return Ok(());
+        }
+
+        // Only show as many columns as fit and keep the focused one visible
+        let visible = ((rect.width / MIN_COLUMN_WIDTH).max(1) as usize).min(self.columns.len());
+        let offset = (self.focus_column + 1).saturating_sub(visible);
+        let constraints: Vec<Constraint> = (0..visible)
+            .map(|_| Constraint::Ratio(1, visible as u32))
+            .collect();
+        let chunks = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints(constraints)
+            .split(rect);
+
+        for (chunk, index) in chunks.iter().zip(offset..offset + visible) {
+            let column_focused = focused && index == self.focus_column;
+            let column = &mut self.columns[index];
+            let mut title = format!("{} ({})", column.name, column.tickets.len());
+            if index == offset {
+                let board = self.board_name.as_deref().unwrap_or("By status");
+                title = format!("{board} | {title}");
+            }
+            let items: Vec<ListItem> = column
+                .tickets
+                .iter()
+                .map(|ticket| ListItem::new(format!("{}\n{}", ticket.key, ticket.fields.summary)))
+                .collect();
+            if !column_focused {
+                column.state.select(None);
+            } else if column.state.selected().is_none() && !column.tickets.is_empty() {
+                column.state.select(Some(0));
+            }
+            let list = List::new(items)
+                .block(draw_block_style(column_focused, &title))
+                .highlight_style(draw_highlight_style());
+            f.render_stateful_widget(list, *chunk, &mut column.state);
+        }
+
+        Ok(())
+    }
+}
+
+impl KanbanWidget {
+    pub fn new(key_config: KeyConfig) -> Self {
+        let key_mappings = {
+            let mut map = HashMap::new();
+            map.insert(key_config.scroll_down, Action::Next(1));
+            map.insert(key_config.scroll_up, Action::Previous(1));
+            map.insert(key_config.scroll_down_multiple_lines, Action::Next(10));
+            map.insert(key_config.scroll_up_multiple_lines, Action::Previous(10));
+            map.insert(key_config.scroll_to_bottom, Action::Last);
+            map.insert(key_config.scroll_to_top, Action::First);
+            map.insert(key_config.scroll_right, Action::NextColumn);
+            map.insert(key_config.scroll_left, Action::PreviousColumn);
+            map.insert(key_config.move_right, Action::NextColumn);
+            map.insert(key_config.move_left, Action::PreviousColumn);
+            map
+        };
+
+        Self {
+            board_columns: None,
+            board_name: None,
+            columns: vec![],
+            focus_column: 0,
+            key_mappings,
+            project_key: None,
+        }
+    }
+
+    fn focused(&mut self) -> Option<&mut KanbanColumn> {
+        self.columns.get_mut(self.focus_column)
+    }
+
+    pub fn next(&mut self, line: usize) {
+        let Some(column) = self.focused() else {
+            return;
+        };
+        if column.tickets.is_empty() {
+            return;
+        }
+        let last = column.tickets.len() - 1;
+        let i = column.state.selected().map(|i| (i + line).min(last));
+        column.state.select(i);
+    }
+
+    pub fn previous(&mut self, line: usize) {
+        let Some(column) = self.focused() else {
+            return;
+        };
+        let i = column.state.selected().map(|i| i.saturating_sub(line));
+        column.state.select(i);
+    }
+
+    pub fn go_to_top(&mut self) {
+        let Some(column) = self.focused() else {
+            return;
+        };
+        if !column.tickets.is_empty() {
+            column.state.select(Some(0));
+        }
+    }
+
+    pub fn go_to_bottom(&mut self) {
+        let Some(column) = self.focused() else {
+            return;
+        };
+        if !column.tickets.is_empty() {
+            column.state.select(Some(column.tickets.len() - 1));
+        }
+    }
+
+    pub fn next_column(&mut self) {
+        if self.focus_column + 1 < self.columns.len() {
+            self.focus_column += 1;
+        }
+    }
+
+    pub fn previous_column(&mut self) {
+        self.focus_column = self.focus_column.saturating_sub(1);
+    }
+
+    pub fn selected(&self) -> Option<&TicketData> {
+        let column = self.columns.get(self.focus_column)?;
+        column.tickets.get(column.state.selected()?)
+    }
+
+    // neighbour_column returns the column next to the focused one, to the
+    // right when `right` is true
+    pub fn neighbour_column(&self, right: bool) -> Option<&KanbanColumn> {
+        match right {
+            true => self.columns.get(self.focus_column + 1),
+            false => self
+                .focus_column
+                .checked_sub(1)
+                .and_then(|i| self.columns.get(i)),
+        }
+    }
+
+    // select_ticket focuses the column and row holding the ticket
+    pub fn select_ticket(&mut self, ticket_key: &str) {
+        for (index, column) in self.columns.iter_mut().enumerate() {
+            if let Some(row) = column.tickets.iter().position(|t| t.key == ticket_key) {
+                column.state.select(Some(row));
+                self.focus_column = index;
+                return;
+            }
+        }
+    }
+
+    pub fn update(&mut self, tickets: &[TicketData]) {
+        let mut columns: Vec<KanbanColumn> = match &self.board_columns {
+            Some(board_columns) => board_columns
+                .iter()
+                .map(|column| {
+                    let status_ids = column.statuses.iter().map(|s| s.id.clone()).collect();
+                    KanbanColumn::new(&column.name, status_ids)
+                })
+                .collect(),
+            None => vec![],
+        };
+
+        let mut tickets = tickets.to_vec();
+        tickets.sort_by(|a, b| a.key.cmp(&b.key));
+        if self.board_columns.is_none() {
+            // Without a board configuration the columns follow the status categories
+            tickets.sort_by_key(|ticket| match ticket.fields.status.category_key() {
+                "new" => 0,
+                "indeterminate" => 1,
+                "done" => 2,
+                _ => 3,
+            });
+        }
+
+        for ticket in tickets {
+            let status = &ticket.fields.status;
+            let column = match columns
+                .iter_mut()
+                .find(|column| column.holds(status.id.as_ref(), &status.name))
+            {
+                Some(column) => column,
+                None => {
+                    columns.push(KanbanColumn::new(&status.name, vec![]));
+                    columns.last_mut().expect("column was just pushed")
+                }
+            };
+            column.tickets.push(ticket);
+        }
+
+        self.columns = columns;
+        self.focus_column = self.focus_column.min(self.columns.len().saturating_sub(1));
+    }
+}
+
+impl Component for KanbanWidget {
+    fn commands(&self, _out: &mut Vec<CommandInfo>) {}
+
+    fn event(&mut self, key: Key) -> anyhow::Result<EventState> {
+        if let Some(action) = self.key_mappings.get(&key) {
+            use Action::*;
+            match *action {
+                Next(line) => self.next(line),
+                Previous(line) => self.previous(line),
+                Last => self.go_to_bottom(),
+                First => self.go_to_top(),
+                NextColumn => self.next_column(),
+                PreviousColumn => self.previous_column(),
+            }
+            Ok(EventState::Consumed)
+        } else {
+            Ok(EventState::NotConsumed)
+        }
+    }
+}
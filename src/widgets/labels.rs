@@ -8,12 +8,14 @@ use tui::{
 
 use crate::{config::KeyConfig, jira::tickets::TicketData};
 
-use super::{draw_block_style, draw_highlight_style, EventState};
+use super::{clicked_row, draw_block_style, draw_highlight_style, list_offset, EventState};
 
 #[derive(Debug)]
 pub struct LabelsWidget {
     key_config: KeyConfig,
     labels: Vec<String>,
+    // offset is the first label shown, to find the label that is clicked
+    offset: usize,
     state: ListState,
 }
 
@@ -40,6 +42,12 @@ impl LabelsWidget {
             self.state.select(Some(0))
         }
 
+        self.labels = ticket.fields.labels.clone();
+        self.offset = list_offset(
+            self.offset,
+            self.state.selected(),
+            rect.height.saturating_sub(2) as usize,
+        );
         let list_items: Vec<_> = ticket
             .fields
             .labels
@@ -64,6 +72,7 @@ impl LabelsWidget {
         Self {
             key_config,
             labels: vec![],
+            offset: 0,
             state,
         }
     }
@@ -110,6 +119,14 @@ impl LabelsWidget {
         }
     }
 
+    // click selects the label drawn at the row of the rect
+    pub fn click(&mut self, rect: Rect, row: u16) {
+        let index = clicked_row(rect, 0, self.offset, row).filter(|i| *i < self.labels.len());
+        if index.is_some() {
+            self.state.select(index);
+        }
+    }
+
     pub async fn update(&mut self, labels: &[String]) -> anyhow::Result<()> {
         self.labels = labels.to_vec();
         Ok(())
@@ -0,0 +1,244 @@
+use std::collections::HashMap;
+
+use tui::{
+    backend::Backend,
+    layout::{Constraint, Direction, Layout, Rect},
+    widgets::{Clear, List, ListItem, ListState},
+    Frame,
+};
+
+use crate::{
+    config::KeyConfig,
+    events::key::Key,
+    jira::{my_work::MyWorkList, tickets::TicketData},
+};
+
+use super::{
+    commands::{CommandInfo, CommandText},
+    draw_block_style, draw_highlight_style, Component, EventState,
+};
+
+#[derive(Debug, Clone, Copy)]
+pub enum Action {
+    Next(usize),
+    Previous(usize),
+    Last,
+    First,
+    NextSection,
+    PreviousSection,
+}
+
+impl Action {
+    pub fn to_command_text(self, key: Key) -> CommandText {
+        const CMD_GROUP_GENERAL: &str = "-- General --";
+        match self {
+            Self::Next(line) => CommandText::new(format!("Next {line} [{key}]"), CMD_GROUP_GENERAL),
+            Self::Previous(line) => {
+                CommandText::new(format!("Previous {line} [{key}]"), CMD_GROUP_GENERAL)
+            }
+            Self::Last => CommandText::new(format!("Go to last [{key}]"), CMD_GROUP_GENERAL),
+            Self::First => CommandText::new(format!("Go to first [{key}]"), CMD_GROUP_GENERAL),
+            Self::NextSection => {
+                CommandText::new(format!("Next section [{key}]"), CMD_GROUP_GENERAL)
+            }
+            Self::PreviousSection => {
+                CommandText::new(format!("Previous section [{key}]"), CMD_GROUP_GENERAL)
+            }
+        }
+    }
+}
+
+#[derive(Debug)]
+struct MyWorkSectionState {
+    list: MyWorkList,
+    state: ListState,
+}
+
+#[derive(Debug)]
+pub struct MyWorkWidget {
+    focus_section: usize,
+    sections: Vec<MyWorkSectionState>,
+    pub key_mappings: HashMap<Key, Action>,
+}
+
+impl MyWorkWidget {
+    pub fn draw<B: Backend>(
+        &mut self,
+        f: &mut Frame<B>,
+        focused: bool,
+        rect: Rect,
+    ) -> anyhow::Result<()> {
+        f.render_widget(Clear, rect);
+        if self.sections.is_empty() {
+            f.render_widget(
+                List::new(vec![]).block(draw_block_style(focused, "My Work")),
+                rect,
+            );
+            return Ok(());
+        }
+
+        let constraints: Vec<Constraint> = self
+            .sections
+            .iter()
+            .map(|_| Constraint::Ratio(1, self.sections.len() as u32))
+            .collect();
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints(constraints)
+            .split(rect);
+
+        for (index, (chunk, section)) in chunks.iter().zip(self.sections.iter_mut()).enumerate() {
+            let section_focused = focused && index == self.focus_section;
+            let list = &section.list;
+            let count = match list.total as usize > list.tickets.len() {
+                true => format!("{} of {}", list.tickets.len(), list.total),
+                false => list.total.to_string(),
+            };
+            let title = format!("{} ({count})", list.section.title());
+            let items: Vec<ListItem> = list
+                .tickets
+                .iter()
+                .map(|ticket| {
+                    ListItem::new(format!(
+                        "{:<12} {:<14} {}",
+                        ticket.key, ticket.fields.status.name, ticket.fields.summary
+                    ))
+                })
+                .collect();
+            if !section_focused {
+                section.state.select(None);
+            } else if section.state.selected().is_none() && !list.tickets.is_empty() {
+                section.state.select(Some(0));
+            }
+            let widget = List::new(items)
+                .block(draw_block_style(section_focused, &title))
+                .highlight_style(draw_highlight_style());
+            f.render_stateful_widget(widget, *chunk, &mut section.state);
+        }
+
+        Ok(())
+    }
+}
+
+impl MyWorkWidget {
+    pub fn new(key_config: KeyConfig) -> Self {
+        let key_mappings = {
+            let mut map = HashMap::new();
+            map.insert(key_config.scroll_down, Action::Next(1));
+            map.insert(key_config.scroll_up, Action::Previous(1));
+            map.insert(key_config.scroll_down_multiple_lines, Action::Next(10));
+            map.insert(key_config.scroll_up_multiple_lines, Action::Previous(10));
+            map.insert(key_config.scroll_to_bottom, Action::Last);
+            map.insert(key_config.scroll_to_top, Action::First);
+            map.insert(key_config.next, Action::NextSection);
+            map.insert(key_config.previous, Action::PreviousSection);
+            map
+        };
+
+        Self {
+            focus_section: 0,
+            key_mappings,
+            sections: vec![],
+        }
+    }
+
+    fn focused(&mut self) -> Option<&mut MyWorkSectionState> {
+        self.sections.get_mut(self.focus_section)
+    }
+
+    pub fn next(&mut self, line: usize) {
+        let Some(section) = self.focused() else {
+            return;
+        };
+        if section.list.tickets.is_empty() {
+            return;
+        }
+        let last = section.list.tickets.len() - 1;
+        let i = section.state.selected().map(|i| (i + line).min(last));
+        section.state.select(i);
+    }
+
+    pub fn previous(&mut self, line: usize) {
+        let Some(section) = self.focused() else {
+            return;
+        };
+        let i = section.state.selected().map(|i| i.saturating_sub(line));
+        section.state.select(i);
+    }
+
+    pub fn go_to_top(&mut self) {
+        let Some(section) = self.focused() else {
+            return;
+        };
+        if !section.list.tickets.is_empty() {
+            section.state.select(Some(0));
+        }
+    }
+
+    pub fn go_to_bottom(&mut self) {
+        let Some(section) = self.focused() else {
+            return;
+        };
+        if !section.list.tickets.is_empty() {
+            section.state.select(Some(section.list.tickets.len() - 1));
+        }
+    }
+
+    pub fn next_section(&mut self) {
+        if !self.sections.is_empty() {
+            self.focus_section = (self.focus_section + 1) % self.sections.len();
+        }
+    }
+
+    pub fn previous_section(&mut self) {
+        if !self.sections.is_empty() {
+            self.focus_section =
+                (self.focus_section + self.sections.len() - 1) % self.sections.len();
+        }
+    }
+
+    pub fn has_lists(&self) -> bool {
+        !self.sections.is_empty()
+    }
+
+    // selected returns the focused list along with its selected ticket
+    pub fn selected(&self) -> Option<(&MyWorkList, &TicketData)> {
+        let section = self.sections.get(self.focus_section)?;
+        let ticket = section.list.tickets.get(section.state.selected()?)?;
+        Some((&section.list, ticket))
+    }
+
+    pub fn update(&mut self, lists: &[MyWorkList]) {
+        self.sections = lists
+            .iter()
+            .map(|list| MyWorkSectionState {
+                list: list.clone(),
+                state: ListState::default(),
+            })
+            .collect();
+        self.focus_section = self
+            .focus_section
+            .min(self.sections.len().saturating_sub(1));
+    }
+}
+
+impl Component for MyWorkWidget {
+    fn commands(&self, _out: &mut Vec<CommandInfo>) {}
+
+    fn event(&mut self, key: Key) -> anyhow::Result<EventState> {
+        if let Some(action) = self.key_mappings.get(&key) {
+            use Action::*;
+            match *action {
+                Next(line) => self.next(line),
+                Previous(line) => self.previous(line),
+                Last => self.go_to_bottom(),
+                First => self.go_to_top(),
+                NextSection => self.next_section(),
+                PreviousSection => self.previous_section(),
+            }
+            Ok(EventState::Consumed)
+        } else {
+            Ok(EventState::NotConsumed)
+        }
+    }
+}
@@ -0,0 +1,200 @@
+use std::collections::{HashMap, HashSet};
+
+use chrono::{Local, TimeZone};
+use tui::{
+    backend::Backend,
+    layout::Rect,
+    style::{Modifier, Style},
+    widgets::{Clear, List, ListItem, ListState},
+    Frame,
+};
+
+use crate::{config::KeyConfig, events::key::Key, jira::notifications::Notification};
+
+use super::{
+    commands::{CommandInfo, CommandText},
+    draw_block_style, draw_highlight_style, Component, EventState,
+};
+
+#[derive(Debug, Clone, Copy)]
+pub enum Action {
+    Next(usize),
+    Previous(usize),
+    Last,
+    First,
+}
+
+impl Action {
+    pub fn to_command_text(self, key: Key) -> CommandText {
+        const CMD_GROUP_GENERAL: &str = "-- General --";
+        match self {
+            Self::Next(line) => CommandText::new(format!("Next {line} [{key}]"), CMD_GROUP_GENERAL),
+            Self::Previous(line) => {
+                CommandText::new(format!("Previous {line} [{key}]"), CMD_GROUP_GENERAL)
+            }
+            Self::Last => CommandText::new(format!("Go to last [{key}]"), CMD_GROUP_GENERAL),
+            Self::First => CommandText::new(format!("Go to first [{key}]"), CMD_GROUP_GENERAL),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct NotificationsWidget {
+    notifications: Vec<Notification>,
+    state: ListState,
+    pub key_mappings: HashMap<Key, Action>,
+}
+
+impl NotificationsWidget {
+    pub fn draw<B: Backend>(
+        &mut self,
+        f: &mut Frame<B>,
+        focused: bool,
+        rect: Rect,
+    ) -> anyhow::Result<()> {
+        f.render_widget(Clear, rect);
+        let title = format!("Notifications ({} unread)", self.unread_count());
+        let items: Vec<ListItem> = self
+            .notifications
+            .iter()
+            .map(|notification| {
+                let created = Local
+                    .timestamp_opt(notification.created, 0)
+                    .single()
+                    .map(|date| date.format("%Y-%m-%d %H:%M").to_string())
+                    .unwrap_or_default();
+                let (marker, style) = match notification.read {
+                    true => (" ", Style::default()),
+                    false => ("●", Style::default().add_modifier(Modifier::BOLD)),
+                };
+                ListItem::new(format!(
+                    "{marker} {created} {:<12} {:<40} {}",
+                    notification.ticket_key, notification.kind, notification.summary
+                ))
+                .style(style)
+            })
+            .collect();
+        let list = List::new(items)
+            .block(draw_block_style(focused, &title))
+            .highlight_style(draw_highlight_style());
+        f.render_stateful_widget(list, rect, &mut self.state);
+        Ok(())
+    }
+}
+
+impl NotificationsWidget {
+    pub fn new(key_config: KeyConfig) -> Self {
+        let key_mappings = {
+            let mut map = HashMap::new();
+            map.insert(key_config.scroll_down, Action::Next(1));
+            map.insert(key_config.scroll_up, Action::Previous(1));
+            map.insert(key_config.scroll_down_multiple_lines, Action::Next(10));
+            map.insert(key_config.scroll_up_multiple_lines, Action::Previous(10));
+            map.insert(key_config.scroll_to_bottom, Action::Last);
+            map.insert(key_config.scroll_to_top, Action::First);
+            map
+        };
+
+        Self {
+            key_mappings,
+            notifications: vec![],
+            state: ListState::default(),
+        }
+    }
+
+    pub fn next(&mut self, line: usize) {
+        if self.notifications.is_empty() {
+            return;
+        }
+        let i = self
+            .state
+            .selected()
+            .map(|i| (i + line).min(self.notifications.len() - 1));
+        self.state.select(i);
+    }
+
+    pub fn previous(&mut self, line: usize) {
+        let i = self.state.selected().map(|i| i.saturating_sub(line));
+        self.state.select(i);
+    }
+
+    pub fn go_to_top(&mut self) {
+        if !self.notifications.is_empty() {
+            self.state.select(Some(0));
+        }
+    }
+
+    pub fn go_to_bottom(&mut self) {
+        if !self.notifications.is_empty() {
+            self.state.select(Some(self.notifications.len() - 1));
+        }
+    }
+
+    pub fn selected(&self) -> Option<&Notification> {
+        self.notifications.get(self.state.selected()?)
+    }
+
+    pub fn unread_count(&self) -> usize {
+        self.notifications.iter().filter(|n| !n.read).count()
+    }
+
+    // unread_keys are the tickets changed since they were last opened
+    pub fn unread_keys(&self) -> HashSet<String> {
+        self.notifications
+            .iter()
+            .filter(|n| !n.read)
+            .map(|n| n.ticket_key.clone())
+            .collect()
+    }
+
+    // ticket_keys lists every ticket of the inbox once, most recent first
+    pub fn ticket_keys(&self) -> Vec<String> {
+        let mut keys: Vec<String> = vec![];
+        for notification in &self.notifications {
+            if !keys.contains(&notification.ticket_key) {
+                keys.push(notification.ticket_key.clone());
+            }
+        }
+        keys
+    }
+
+    pub fn mark_read(&mut self, ticket_key: Option<&str>) {
+        for notification in self.notifications.iter_mut() {
+            if ticket_key.is_none() || ticket_key == Some(notification.ticket_key.as_str()) {
+                notification.read = true;
+            }
+        }
+    }
+
+    pub fn update(&mut self, notifications: Vec<Notification>) {
+        self.notifications = notifications;
+        if self.state.selected().is_none() && !self.notifications.is_empty() {
+            self.state.select(Some(0));
+        }
+    }
+
+    // push adds the notifications of a poll on top of the inbox
+    pub fn push(&mut self, mut notifications: Vec<Notification>) {
+        notifications.append(&mut self.notifications);
+        self.update(notifications);
+    }
+}
+
+impl Component for NotificationsWidget {
+    fn commands(&self, _out: &mut Vec<CommandInfo>) {}
+
+    fn event(&mut self, key: Key) -> anyhow::Result<EventState> {
+        if let Some(action) = self.key_mappings.get(&key) {
+            use Action::*;
+            match *action {
+                Next(line) => self.next(line),
+                Previous(line) => self.previous(line),
+                Last => self.go_to_bottom(),
+                First => self.go_to_top(),
+            }
+            Ok(EventState::Consumed)
+        } else {
+            Ok(EventState::NotConsumed)
+        }
+    }
+}
@@ -0,0 +1,249 @@
+use std::collections::HashMap;
+
+use simsearch::SimSearch;
+use tui::{
+    backend::Backend,
+    layout::{Constraint, Direction, Layout, Rect},
+    text::{Span, Spans},
+    widgets::{Block, BorderType, Borders, Clear, List, ListItem, ListState},
+    Frame,
+};
+use unicode_width::UnicodeWidthStr;
+
+use crate::{config::KeyConfig, events::key::Key, theme::theme};
+
+use super::{
+    commands::CommandText, draw_edit_style, draw_highlight_style, text_area::TextArea, EventState,
+};
+
+#[derive(Debug, Clone, Copy)]
+pub enum Action {
+    Close,
+    Choose,
+    Next,
+    Previous,
+}
+
+// PaletteEntry is an action the palette can run and the key it is bound to
+#[derive(Clone)]
+pub struct PaletteEntry<C> {
+    pub name: String,
+    pub key: Option<Key>,
+    pub command: C,
+}
+
+impl<C> PaletteEntry<C> {
+    // new names the entry after the help text of the action, without the
+    // key the help text ends with
+    pub fn new(text: CommandText, key: Option<Key>, command: C) -> Self {
+        let name = match text.name.rfind(" [") {
+            Some(end) if text.name.ends_with(']') => text.name[..end].to_string(),
+            _ => text.name,
+        };
+        Self { name, key, command }
+    }
+}
+
+// CommandPalette fuzzy searches the actions of the current screen by name
+// and hands the chosen one back to the app
+pub struct CommandPalette<C> {
+    editor: TextArea,
+    entries: Vec<PaletteEntry<C>>,
+    // matches are the indexes of the entries matching the input, best first
+    matches: Vec<usize>,
+    state: ListState,
+    visible: bool,
+    chosen: Option<C>,
+    // key_mappings only hold keys that can not be typed in the search, the
+    // rest goes to the input
+    pub key_mappings: HashMap<Key, Action>,
+}
+
+impl<C: Copy> CommandPalette<C> {
+    pub fn new(key_config: KeyConfig) -> Self {
+        let key_mappings = HashMap::from([
+            (key_config.esc, Action::Close),
+            (key_config.enter, Action::Choose),
+            (key_config.move_down, Action::Next),
+            (key_config.next, Action::Next),
+            (key_config.move_up, Action::Previous),
+            (key_config.previous, Action::Previous),
+        ]);
+        Self {
+            key_mappings,
+            editor: TextArea::single_line(),
+            entries: vec![],
+            matches: vec![],
+            state: ListState::default(),
+            visible: false,
+            chosen: None,
+        }
+    }
+
+    pub fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    pub fn open(&mut self, mut entries: Vec<PaletteEntry<C>>) {
+        entries.sort_by(|a, b| a.name.cmp(&b.name));
+        self.entries = entries;
+        self.editor.clear();
+        self.chosen = None;
+        self.visible = true;
+        self.search();
+    }
+
+    pub fn close(&mut self) {
+        self.visible = false;
+    }
+
+    // take_chosen is the command picked with enter, the palette is closed by then
+    pub fn take_chosen(&mut self) -> Option<C> {
+        self.chosen.take()
+    }
+
+    fn search(&mut self) {
+        let input = self.editor.text();
+        self.matches = match input.trim().is_empty() {
+            true => (0..self.entries.len()).collect(),
+            false => {
+                let mut engine: SimSearch<usize> = SimSearch::new();
+                for (index, entry) in self.entries.iter().enumerate() {
+                    engine.insert(index, &entry.name);
+                }
+                engine.search(&input)
+            }
+        };
+        self.state.select((!self.matches.is_empty()).then_some(0));
+    }
+
+    fn select(&mut self, forward: bool) {
+        if self.matches.is_empty() {
+            return;
+        }
+        let last = self.matches.len() - 1;
+        let selected = self.state.selected().unwrap_or(0);
+        self.state.select(Some(match forward {
+            true if selected >= last => 0,
+            true => selected + 1,
+            false if selected == 0 => last,
+            false => selected - 1,
+        }));
+    }
+
+    // event takes every key while the palette is open
+    pub fn event(&mut self, key: Key) -> EventState {
+        match self.key_mappings.get(&key).copied() {
+            Some(Action::Close) => self.close(),
+            Some(Action::Choose) => {
+                self.chosen = self
+                    .state
+                    .selected()
+                    .and_then(|i| self.matches.get(i))
+                    .map(|&index| self.entries[index].command);
+                if self.chosen.is_some() {
+                    self.close();
+                }
+            }
+            Some(Action::Next) => self.select(true),
+            Some(Action::Previous) => self.select(false),
+            None => {
+                if self.editor.input(key).is_consumed() {
+                    self.search();
+                }
+            }
+        }
+        EventState::Consumed
+    }
+
+    pub fn paste(&mut self, text: &str) -> EventState {
+        self.editor.insert_str(text);
+        self.search();
+        EventState::Consumed
+    }
+
+    pub fn draw<B: Backend>(&mut self, f: &mut Frame<B>) {
+        if !self.visible {
+            return;
+        }
+        const SIZE: (u16, u16) = (70, 20);
+        let area = Rect::new(
+            (f.size().width.saturating_sub(SIZE.0)) / 2,
+            (f.size().height.saturating_sub(SIZE.1)) / 4,
+            SIZE.0.min(f.size().width),
+            SIZE.1.min(f.size().height),
+        );
+        f.render_widget(Clear, area);
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(3), Constraint::Min(1)])
+            .split(area);
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Thick)
+            .title("Command");
+        self.editor
+            .draw(f, chunks[0], block, draw_edit_style(true), true);
+
+        let width = chunks[1].width.saturating_sub(2) as usize;
+        let items: Vec<ListItem> = self
+            .matches
+            .iter()
+            .map(|&index| {
+                let entry = &self.entries[index];
+                let key = entry.key.map(|key| key.to_string()).unwrap_or_default();
+                let padding = width.saturating_sub(entry.name.width() + key.width());
+                ListItem::new(Spans::from(vec![
+                    Span::raw(format!("{}{}", entry.name, " ".repeat(padding))),
+                    Span::styled(key, theme().muted),
+                ]))
+            })
+            .collect();
+        let title = format!("{}/{}", self.matches.len(), self.entries.len());
+        let list = List::new(items)
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .border_type(BorderType::Thick)
+                    .title(title),
+            )
+            .highlight_style(draw_highlight_style());
+        f.render_stateful_widget(list, chunks[1], &mut self.state);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(name: &str, key: Option<Key>, command: u8) -> PaletteEntry<u8> {
+        PaletteEntry::new(CommandText::new(name.to_string(), ""), key, command)
+    }
+
+    #[test]
+    fn searches_and_runs_the_chosen_entry() {
+        let mut palette = CommandPalette::new(KeyConfig::default());
+        palette.open(vec![
+            entry("Run a JQL query [Q]", Some(Key::Char('Q')), 1),
+            entry("Open epics of project [E]", Some(Key::Char('E')), 2),
+            entry("Rank ticket to the top", None, 3),
+        ]);
+        assert_eq!(palette.entries[0].name, "Open epics of project");
+        assert_eq!(palette.matches.len(), 3);
+
+        for c in "rank".chars() {
+            palette.event(Key::Char(c));
+        }
+        assert_eq!(palette.matches, vec![1]);
+        palette.event(Key::Enter);
+        assert!(!palette.is_visible());
+        assert_eq!(palette.take_chosen(), Some(3));
+
+        palette.open(vec![entry("Open Help [?]", None, 4)]);
+        palette.paste("nothing like it");
+        palette.event(Key::Enter);
+        assert!(palette.is_visible());
+        assert_eq!(palette.take_chosen(), None);
+    }
+}
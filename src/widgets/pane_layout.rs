@@ -0,0 +1,354 @@
+use tui::layout::{Constraint, Direction, Layout, Rect};
+
+use crate::config::JiraConfigLayout;
+
+// STACK_BELOW is the terminal width under which the panes are stacked in a
+// single column unless the config says otherwise
+const STACK_BELOW: u16 = 100;
+
+// Pane is a part of the tickets screen
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Pane {
+    Components,
+    Description,
+    Labels,
+    Parent,
+    Relation,
+    Tickets,
+}
+
+impl Pane {
+    pub fn from_name(name: &str) -> Option<Self> {
+        let pane = match name.to_lowercase().as_str() {
+            "components" => Self::Components,
+            "description" => Self::Description,
+            "labels" => Self::Labels,
+            "parent" => Self::Parent,
+            "relation" => Self::Relation,
+            "tickets" => Self::Tickets,
+            _ => return None,
+        };
+        Some(pane)
+    }
+}
+
+// LayoutColumn is a column of panes, sizes are percentages of the screen
+// width and of the column height
+#[derive(Debug, Clone, PartialEq)]
+struct LayoutColumn {
+    width: u16,
+    panes: Vec<(Pane, u16)>,
+}
+
+// PaneLayout places the panes of the tickets screen: columns side by side
+// with full width panes below them
+#[derive(Debug, Clone, PartialEq)]
+pub struct PaneLayout {
+    columns: Vec<LayoutColumn>,
+    bottom: Vec<Pane>,
+    bottom_height: u16,
+    stack_below: u16,
+}
+
+impl Default for PaneLayout {
+    fn default() -> Self {
+        Self {
+            columns: vec![
+                LayoutColumn {
+                    width: 40,
+                    panes: vec![
+                        (Pane::Tickets, 50),
+                        (Pane::Labels, 20),
+                        (Pane::Components, 20),
+                        (Pane::Parent, 10),
+                    ],
+                },
+                LayoutColumn {
+                    width: 60,
+                    panes: vec![(Pane::Description, 100)],
+                },
+            ],
+            bottom: vec![Pane::Relation],
+            bottom_height: 15,
+            stack_below: STACK_BELOW,
+        }
+    }
+}
+
+impl PaneLayout {
+    pub fn contains(&self, pane: Pane) -> bool {
+        self.bottom.contains(&pane)
+            || self
+                .columns
+                .iter()
+                .any(|column| column.panes.iter().any(|(p, _)| *p == pane))
+    }
+
+    // split lays the panes out over the area, a zoomed pane takes all of it
+    // and narrow areas get the panes stacked
+    pub fn split(&self, area: Rect, zoomed: Option<Pane>) -> Vec<(Pane, Rect)> {
+        if let Some(pane) = zoomed {
+            return vec![(pane, area)];
+        }
+        if area.width < self.stack_below {
+            return self.stacked(area);
+        }
+        let bottom_height = match self.bottom.is_empty() {
+            true => 0,
+            false => self.bottom_height,
+        };
+        let rows = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Percentage(100 - bottom_height),
+                Constraint::Percentage(bottom_height),
+            ])
+            .split(area);
+
+        let mut panes = vec![];
+        let column_rects = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints(
+                self.columns
+                    .iter()
+                    .map(|column| Constraint::Percentage(column.width))
+                    .collect::<Vec<_>>(),
+            )
+            .split(rows[0]);
+        for (column, rect) in self.columns.iter().zip(column_rects.iter()) {
+            let rects = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints(
+                    column
+                        .panes
+                        .iter()
+                        .map(|(_, height)| Constraint::Percentage(*height))
+                        .collect::<Vec<_>>(),
+                )
+                .split(*rect);
+            panes.extend(
+                column
+                    .panes
+                    .iter()
+                    .map(|(pane, _)| *pane)
+                    .zip(rects.iter().copied()),
+            );
+        }
+        let count = self.bottom.len() as u32;
+        let bottom_rects = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints(
+                self.bottom
+                    .iter()
+                    .map(|_| Constraint::Ratio(1, count))
+                    .collect::<Vec<_>>(),
+            )
+            .split(rows[1]);
+        panes.extend(
+            self.bottom
+                .iter()
+                .copied()
+                .zip(bottom_rects.iter().copied()),
+        );
+        panes
+    }
+
+    // stacked puts every pane under the other, keeping the share of the
+    // screen each pane has side by side
+    fn stacked(&self, area: Rect) -> Vec<(Pane, Rect)> {
+        let mut weights: Vec<(Pane, u32)> = vec![];
+        for column in &self.columns {
+            for (pane, height) in &column.panes {
+                weights.push((*pane, column.width as u32 * *height as u32));
+            }
+        }
+        let bottom_weight = self.bottom_height as u32 * 100 / self.bottom.len().max(1) as u32;
+        weights.extend(self.bottom.iter().map(|pane| (*pane, bottom_weight)));
+        let total = weights.iter().map(|(_, weight)| weight).sum::<u32>().max(1);
+        let rects = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints(
+                weights
+                    .iter()
+                    .map(|(_, weight)| Constraint::Ratio(*weight, total))
+                    .collect::<Vec<_>>(),
+            )
+            .split(area);
+        weights
+            .into_iter()
+            .map(|(pane, _)| pane)
+            .zip(rects.iter().copied())
+            .collect()
+    }
+}
+
+// shares fills in the sizes left out, splitting what is left of 100% evenly
+fn shares(sizes: &[Option<u16>]) -> Vec<u16> {
+    let given: u16 = sizes.iter().flatten().sum();
+    let missing = sizes.iter().filter(|size| size.is_none()).count() as u16;
+    let share = match missing {
+        0 => 0,
+        missing => 100u16.saturating_sub(given) / missing,
+    };
+    sizes.iter().map(|size| size.unwrap_or(share)).collect()
+}
+
+// from_config builds the layout of the [layout] section, unknown or repeated
+// panes are left out and reported back. Without a tickets pane the default
+// layout is used.
+pub fn from_config(config: &JiraConfigLayout) -> (PaneLayout, Vec<String>) {
+    let default = PaneLayout::default();
+    let mut problems = vec![];
+    let mut seen: Vec<Pane> = vec![];
+    let mut pane = |name: &str| {
+        let Some(pane) = Pane::from_name(name) else {
+            problems.push(format!("unknown pane {name}"));
+            return None;
+        };
+        if seen.contains(&pane) {
+            problems.push(format!("pane {name} is shown twice"));
+            return None;
+        }
+        seen.push(pane);
+        Some(pane)
+    };
+
+    let columns = match &config.columns {
+        None => default.columns.clone(),
+        Some(columns) => {
+            let widths = shares(&columns.iter().map(|c| c.width).collect::<Vec<_>>());
+            columns
+                .iter()
+                .zip(widths)
+                .map(|(column, width)| {
+                    let panes: Vec<(Pane, Option<u16>)> = column
+                        .panes
+                        .iter()
+                        .filter_map(|p| pane(&p.name).map(|found| (found, p.height)))
+                        .collect();
+                    let heights = shares(&panes.iter().map(|(_, h)| *h).collect::<Vec<_>>());
+                    LayoutColumn {
+                        width,
+                        panes: panes.into_iter().map(|(p, _)| p).zip(heights).collect(),
+                    }
+                })
+                .filter(|column| !column.panes.is_empty())
+                .collect()
+        }
+    };
+    let bottom = match (&config.bottom, &config.columns) {
+        (Some(bottom), _) => bottom.iter().filter_map(|name| pane(name)).collect(),
+        (None, None) => default.bottom.clone(),
+        (None, Some(_)) => vec![],
+    };
+    let bottom_height = config.bottom_height.unwrap_or(default.bottom_height);
+    if bottom_height > 90 {
+        problems.push(format!("bottom_height {bottom_height} is over 90"));
+    }
+
+    let layout = PaneLayout {
+        columns,
+        bottom,
+        bottom_height: bottom_height.min(90),
+        stack_below: config.stack_below.unwrap_or(STACK_BELOW),
+    };
+    if !layout.contains(Pane::Tickets) {
+        problems.push(String::from("the tickets pane is missing"));
+        return (default, problems);
+    }
+    (layout, problems)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{JiraConfigLayoutColumn, JiraConfigLayoutPane};
+
+    fn pane(name: &str, height: Option<u16>) -> JiraConfigLayoutPane {
+        JiraConfigLayoutPane {
+            name: name.to_string(),
+            height,
+        }
+    }
+
+    fn area(width: u16, height: u16) -> Rect {
+        Rect::new(0, 0, width, height)
+    }
+
+    #[test]
+    fn default_layout_splits_wide_and_narrow_screens() {
+        let layout = PaneLayout::default();
+        let panes = layout.split(area(200, 100), None);
+        let rect = |pane| panes.iter().find(|(p, _)| *p == pane).unwrap().1;
+        assert_eq!(rect(Pane::Tickets), Rect::new(0, 0, 80, 42));
+        assert_eq!(rect(Pane::Description), Rect::new(80, 0, 120, 85));
+        assert_eq!(rect(Pane::Relation), Rect::new(0, 85, 200, 15));
+
+        let stacked = layout.split(area(80, 100), None);
+        assert_eq!(stacked.len(), 6);
+        assert!(stacked.iter().all(|(_, rect)| rect.width == 80));
+        assert!(stacked.windows(2).all(|w| w[0].1.bottom() <= w[1].1.y));
+
+        assert_eq!(
+            layout.split(area(200, 100), Some(Pane::Labels)),
+            vec![(Pane::Labels, area(200, 100))]
+        );
+    }
+
+    #[test]
+    fn layout_from_config() {
+        let config = JiraConfigLayout {
+            columns: Some(vec![
+                JiraConfigLayoutColumn {
+                    width: Some(30),
+                    panes: vec![
+                        pane("tickets", None),
+                        pane("labels", Some(20)),
+                        pane("comments", None),
+                    ],
+                },
+                JiraConfigLayoutColumn {
+                    width: None,
+                    panes: vec![pane("description", None), pane("Tickets", None)],
+                },
+            ]),
+            bottom: None,
+            bottom_height: None,
+            stack_below: Some(0),
+        };
+        let (layout, problems) = from_config(&config);
+        assert_eq!(
+            problems,
+            vec![
+                "unknown pane comments".to_string(),
+                "pane Tickets is shown twice".to_string(),
+            ]
+        );
+        assert_eq!(
+            layout.columns,
+            vec![
+                LayoutColumn {
+                    width: 30,
+                    panes: vec![(Pane::Tickets, 80), (Pane::Labels, 20)],
+                },
+                LayoutColumn {
+                    width: 70,
+                    panes: vec![(Pane::Description, 100)],
+                },
+            ]
+        );
+        assert!(!layout.contains(Pane::Relation));
+        assert_eq!(layout.split(area(40, 10), None).len(), 3);
+
+        let (layout, problems) = from_config(&JiraConfigLayout {
+            bottom: Some(vec!["relation".to_string()]),
+            columns: Some(vec![JiraConfigLayoutColumn {
+                width: None,
+                panes: vec![pane("description", None)],
+            }]),
+            ..Default::default()
+        });
+        assert_eq!(layout, PaneLayout::default());
+        assert_eq!(problems, vec!["the tickets pane is missing".to_string()]);
+    }
+}
@@ -2,22 +2,22 @@ use simsearch::SimSearch;
 use tui::{
     backend::Backend,
     layout::{Constraint, Direction, Layout, Rect},
-    style::{Color, Modifier, Style},
+    style::{Modifier, Style},
     text::{Span, Spans, Text},
-    widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph, Wrap},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph},
     Frame,
 };
 
 use crate::{events::key::Key, jira::projects::Project};
 
-use super::{draw_highlight_style, EventState, InputMode};
+use super::{draw_edit_style, draw_highlight_style, text_area::TextArea, EventState, InputMode};
 
 pub struct SearchProjectsWidget {
     // projects: Vec<Project>,
     projects: Vec<String>,
     search_projects: Vec<String>,
     state: ListState,
-    pub input: String,
+    editor: TextArea,
     pub input_mode: InputMode,
 }
 
@@ -31,7 +31,7 @@ impl SearchProjectsWidget {
         }
 
         Self {
-            input: String::new(),
+            editor: TextArea::single_line(),
             input_mode: InputMode::Normal,
             projects: project_keys,
             search_projects: Vec::new(),
@@ -39,6 +39,10 @@ impl SearchProjectsWidget {
         }
     }
 
+    pub fn input(&self) -> String {
+        self.editor.text()
+    }
+
     pub fn normal_mode(&mut self) {
         self.input_mode = InputMode::Normal
     }
@@ -54,7 +58,7 @@ impl SearchProjectsWidget {
         }
 
         let results: Vec<_> = engine
-            .search(&self.input)
+            .search(&self.editor.text())
             .into_iter()
             .map(|project_id| {
                 let project = &self.projects[project_id];
@@ -138,22 +142,10 @@ impl SearchProjectsWidget {
         let help_message = Paragraph::new(text);
         f.render_widget(help_message, chunks[0]);
 
-        let input = Paragraph::new(self.input.as_ref())
-            .wrap(Wrap { trim: true })
-            .style(match self.input_mode {
-                InputMode::Normal => Style::default(),
-                InputMode::Editing => Style::default().fg(Color::Yellow),
-            })
-            .block(Block::default().borders(Borders::ALL).title(input_title));
-        f.render_widget(input, chunks[1]);
-
-        // Make the cursor visible and ask tui-rs to put it at the specified coordinates after rendering
-        let cursor_end_of_text = (
-            // Put cursor past the end of the input text
-            chunks[1].x + self.input.len() as u16 + 1,
-            // Move one line down, from the border to the input line
-            chunks[1].y + 1,
-        );
+        let editing = self.input_mode == InputMode::Editing;
+        let style = draw_edit_style(editing);
+        let block = Block::default().borders(Borders::ALL).title(input_title);
+        self.editor.draw(f, chunks[1], block, style, editing);
 
         match self.input_mode {
             InputMode::Normal => {
@@ -162,7 +154,6 @@ impl SearchProjectsWidget {
             }
 
             InputMode::Editing => {
-                f.set_cursor(cursor_end_of_text.0, cursor_end_of_text.1);
                 self.draw_edit(f, chunks[2])?;
             }
         };
@@ -237,22 +228,23 @@ impl SearchProjectsWidget {
 
     fn edit_mode_key_event(&mut self, key: Key) -> anyhow::Result<EventState> {
         match key {
-            Key::Char(c) => {
-                self.input.push(c);
-                Ok(EventState::Consumed)
-            }
-            Key::Backspace => {
-                self.input.pop();
-                Ok(EventState::Consumed)
-            }
             Key::Esc => {
                 self.normal_mode();
                 Ok(EventState::Consumed)
             }
+            _ if self.editor.input(key).is_consumed() => Ok(EventState::Consumed),
             _ => self.movement(key),
         }
     }
 
+    pub fn paste(&mut self, text: &str) -> EventState {
+        if self.input_mode != InputMode::Editing {
+            return EventState::NotConsumed;
+        }
+        self.editor.insert_str(text);
+        EventState::Consumed
+    }
+
     pub fn event(&mut self, key: Key) -> anyhow::Result<EventState> {
         match self.input_mode {
             InputMode::Normal => self.normal_mode_key_event(key),
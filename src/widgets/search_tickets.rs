@@ -2,18 +2,18 @@ use simsearch::SimSearch;
 use tui::{
     backend::Backend,
     layout::{Constraint, Direction, Layout, Rect},
-    style::{Color, Modifier, Style},
+    style::{Modifier, Style},
     text::{Span, Spans, Text},
-    widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph, Wrap},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph},
     Frame,
 };
 
 use crate::{events::key::Key, jira::tickets::TicketData};
 
-use super::{draw_highlight_style, EventState, InputMode};
+use super::{draw_edit_style, draw_highlight_style, text_area::TextArea, EventState, InputMode};
 
 pub struct SearchTicketsWidget {
-    pub input: String,
+    editor: TextArea,
     search_tickets: Vec<String>,
     state: ListState,
     tickets: Vec<String>,
@@ -24,7 +24,7 @@ impl SearchTicketsWidget {
     pub fn new() -> Self {
         let state = ListState::default();
         Self {
-            input: String::new(),
+            editor: TextArea::single_line(),
             input_mode: InputMode::Normal,
             search_tickets: Vec::new(),
             state,
@@ -32,6 +32,10 @@ impl SearchTicketsWidget {
         }
     }
 
+    pub fn input(&self) -> String {
+        self.editor.text()
+    }
+
     pub fn normal_mode(&mut self) {
         self.input_mode = InputMode::Normal
     }
@@ -47,7 +51,7 @@ impl SearchTicketsWidget {
         }
 
         let results: Vec<_> = engine
-            .search(&self.input)
+            .search(&self.editor.text())
             .into_iter()
             .map(|project_id| {
                 let ticket = &self.tickets[project_id];
@@ -128,22 +132,10 @@ impl SearchTicketsWidget {
         let help_message = Paragraph::new(text);
         f.render_widget(help_message, chunks[0]);
 
-        let input = Paragraph::new(self.input.as_ref())
-            .wrap(Wrap { trim: true })
-            .style(match self.input_mode {
-                InputMode::Normal => Style::default(),
-                InputMode::Editing => Style::default().fg(Color::Yellow),
-            })
-            .block(Block::default().borders(Borders::ALL).title(input_title));
-        f.render_widget(input, chunks[1]);
-
-        // Make the cursor visible and ask tui-rs to put it at the specified coordinates after rendering
-        let cursor_end_of_text = (
-            // Put cursor past the end of the input text
-            chunks[1].x + self.input.len() as u16 + 1,
-            // Move one line down, from the border to the input line
-            chunks[1].y + 1,
-        );
+        let editing = self.input_mode == InputMode::Editing;
+        let style = draw_edit_style(editing);
+        let block = Block::default().borders(Borders::ALL).title(input_title);
+        self.editor.draw(f, chunks[1], block, style, editing);
 
         match self.input_mode {
             InputMode::Normal => {
@@ -153,7 +145,6 @@ impl SearchTicketsWidget {
             }
             InputMode::Editing => {
                 self.draw_edit(f, chunks[2])?;
-                f.set_cursor(cursor_end_of_text.0, cursor_end_of_text.1)
             }
         }
 
@@ -229,22 +220,23 @@ impl SearchTicketsWidget {
 
     fn edit_mode_key_event(&mut self, key: Key) -> anyhow::Result<EventState> {
         match key {
-            Key::Char(c) => {
-                self.input.push(c);
-                Ok(EventState::Consumed)
-            }
-            Key::Backspace => {
-                self.input.pop();
-                Ok(EventState::Consumed)
-            }
             Key::Esc => {
                 self.normal_mode();
                 Ok(EventState::Consumed)
             }
+            _ if self.editor.input(key).is_consumed() => Ok(EventState::Consumed),
             _ => self.movement(key), // _ => return Ok(EventState::NotConsumed)
         }
     }
 
+    pub fn paste(&mut self, text: &str) -> EventState {
+        if self.input_mode != InputMode::Editing {
+            return EventState::NotConsumed;
+        }
+        self.editor.insert_str(text);
+        EventState::Consumed
+    }
+
     pub fn event(&mut self, key: Key) -> anyhow::Result<EventState> {
         match self.input_mode {
             InputMode::Normal => self.normal_mode_key_event(key),
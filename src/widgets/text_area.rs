@@ -0,0 +1,590 @@
+use tui::{
+    backend::Backend,
+    layout::Rect,
+    style::{Modifier, Style},
+    text::{Span, Spans},
+    widgets::{Block, Paragraph},
+    Frame,
+};
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
+
+use crate::events::key::Key;
+
+use super::EventState;
+
+const UNDO_LIMIT: usize = 100;
+
+// Position is a line and a grapheme index in that line
+type Position = (usize, usize);
+
+#[derive(Debug, Clone, PartialEq)]
+struct Snapshot {
+    lines: Vec<String>,
+    cursor: Position,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum EditKind {
+    Insert,
+    Delete,
+    Other,
+}
+
+// TextArea is the text input used by every editing surface. Positions are
+// counted in graphemes so multibyte text moves and deletes as one character.
+#[derive(Debug, Clone)]
+pub struct TextArea {
+    lines: Vec<String>,
+    cursor: Position,
+    // anchor is the other end of the selection while one is made
+    anchor: Option<Position>,
+    undo: Vec<Snapshot>,
+    redo: Vec<Snapshot>,
+    last_edit: Option<EditKind>,
+    single_line: bool,
+    scroll: usize,
+}
+
+impl Default for TextArea {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn graphemes(line: &str) -> Vec<&str> {
+    line.graphemes(true).collect()
+}
+
+fn grapheme_count(line: &str) -> usize {
+    line.graphemes(true).count()
+}
+
+fn byte_offset(line: &str, col: usize) -> usize {
+    line.grapheme_indices(true)
+        .nth(col)
+        .map_or(line.len(), |(i, _)| i)
+}
+
+fn is_space(grapheme: &str) -> bool {
+    grapheme.chars().all(char::is_whitespace)
+}
+
+// wrap splits a line in rows that fit the width, after a space when there is
+// one, as ranges of graphemes
+fn wrap(graphemes: &[&str], width: usize) -> Vec<(usize, usize)> {
+    let mut rows = vec![];
+    let mut start = 0;
+    while start < graphemes.len() {
+        let mut used = 0;
+        let mut end = start;
+        let mut last_space = None;
+        while end < graphemes.len() {
+            let grapheme_width = graphemes[end].width();
+            if width > 0 && used + grapheme_width > width {
+                break;
+            }
+            used += grapheme_width;
+            if is_space(graphemes[end]) {
+                last_space = Some(end);
+            }
+            end += 1;
+        }
+        if end < graphemes.len() {
+            match last_space {
+                Some(space) if space > start => end = space + 1,
+                _ if end == start => end = start + 1,
+                _ => {}
+            }
+        }
+        rows.push((start, end));
+        start = end;
+    }
+    if rows.is_empty() {
+        rows.push((0, 0));
+    }
+    rows
+}
+
+impl TextArea {
+    pub fn new() -> Self {
+        Self {
+            lines: vec![String::new()],
+            cursor: (0, 0),
+            anchor: None,
+            undo: vec![],
+            redo: vec![],
+            last_edit: None,
+            single_line: false,
+            scroll: 0,
+        }
+    }
+
+    // single_line inputs turn new lines into spaces and leave Enter, Up and
+    // Down to the widget using them
+    pub fn single_line() -> Self {
+        Self {
+            single_line: true,
+            ..Self::new()
+        }
+    }
+
+    pub fn text(&self) -> String {
+        self.lines.join("\n")
+    }
+
+    // text_before_cursor is the start of the line the cursor is on
+    pub fn text_before_cursor(&self) -> &str {
+        let (row, col) = self.cursor;
+        let line = &self.lines[row];
+        &line[..byte_offset(line, col)]
+    }
+
+    // replace_before_cursor swaps the end of text_before_cursor for the text,
+    // like a completed word
+    pub fn replace_before_cursor(&mut self, before: &str, text: &str) {
+        self.edit(EditKind::Other);
+        self.anchor = None;
+        let (row, col) = self.cursor;
+        let start = (row, col.saturating_sub(grapheme_count(before)));
+        self.delete_range(start, self.cursor);
+        self.insert_text(text);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.lines.len() == 1 && self.lines[0].is_empty()
+    }
+
+    // set_text replaces the text and its history, the cursor goes to the end
+    pub fn set_text(&mut self, text: &str) {
+        self.lines = vec![String::new()];
+        self.cursor = (0, 0);
+        self.insert_text(text);
+        self.anchor = None;
+        self.undo.clear();
+        self.redo.clear();
+        self.last_edit = None;
+        self.scroll = 0;
+    }
+
+    pub fn clear(&mut self) {
+        self.set_text("");
+    }
+
+    // insert_str types the text at the cursor as a single edit, replacing the
+    // selection, so a paste is undone at once
+    pub fn insert_str(&mut self, text: &str) {
+        self.edit(EditKind::Other);
+        self.delete_selection();
+        self.insert_text(text);
+    }
+
+    fn insert_text(&mut self, text: &str) {
+        let text = text.replace("\r\n", "\n").replace('\r', "\n");
+        let text = match self.single_line {
+            true => text.replace('\n', " "),
+            false => text,
+        };
+        let (row, col) = self.cursor;
+        let offset = byte_offset(&self.lines[row], col);
+        let tail = self.lines[row].split_off(offset);
+        let mut inserted = text.split('\n');
+        if let Some(first) = inserted.next() {
+            self.lines[row].push_str(first);
+        }
+        let mut last_row = row;
+        for line in inserted {
+            last_row += 1;
+            self.lines.insert(last_row, line.to_string());
+        }
+        let col = grapheme_count(&self.lines[last_row]);
+        self.lines[last_row].push_str(&tail);
+        self.cursor = (last_row, col);
+    }
+
+    fn line_len(&self, row: usize) -> usize {
+        grapheme_count(&self.lines[row])
+    }
+
+    // edit records the text before a change for undo. Characters typed one
+    // after the other are undone together.
+    fn edit(&mut self, kind: EditKind) {
+        self.redo.clear();
+        if kind != EditKind::Other && self.last_edit == Some(kind) && self.anchor.is_none() {
+            return;
+        }
+        self.last_edit = Some(kind);
+        self.undo.push(self.snapshot());
+        if self.undo.len() > UNDO_LIMIT {
+            self.undo.remove(0);
+        }
+    }
+
+    fn snapshot(&self) -> Snapshot {
+        Snapshot {
+            lines: self.lines.clone(),
+            cursor: self.cursor,
+        }
+    }
+
+    fn restore(&mut self, snapshot: Snapshot) {
+        self.lines = snapshot.lines;
+        self.cursor = snapshot.cursor;
+        self.anchor = None;
+        self.last_edit = None;
+    }
+
+    pub fn undo(&mut self) {
+        if let Some(snapshot) = self.undo.pop() {
+            self.redo.push(self.snapshot());
+            self.restore(snapshot);
+        }
+    }
+
+    pub fn redo(&mut self) {
+        if let Some(snapshot) = self.redo.pop() {
+            self.undo.push(self.snapshot());
+            self.restore(snapshot);
+        }
+    }
+
+    // selection is the ordered start and end of the selected text
+    fn selection(&self) -> Option<(Position, Position)> {
+        let anchor = self.anchor?;
+        match anchor.cmp(&self.cursor) {
+            std::cmp::Ordering::Less => Some((anchor, self.cursor)),
+            std::cmp::Ordering::Greater => Some((self.cursor, anchor)),
+            std::cmp::Ordering::Equal => None,
+        }
+    }
+
+    fn delete_range(&mut self, start: Position, end: Position) {
+        let end_line = &self.lines[end.0];
+        let tail = end_line[byte_offset(end_line, end.1)..].to_string();
+        let start_line = &mut self.lines[start.0];
+        start_line.truncate(byte_offset(start_line, start.1));
+        start_line.push_str(&tail);
+        self.lines.drain(start.0 + 1..=end.0);
+        self.cursor = start;
+    }
+
+    fn delete_selection(&mut self) -> bool {
+        let selection = self.selection();
+        self.anchor = None;
+        match selection {
+            Some((start, end)) => {
+                self.delete_range(start, end);
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn backspace(&mut self) {
+        self.edit(EditKind::Delete);
+        if self.delete_selection() {
+            return;
+        }
+        let end = self.cursor;
+        self.move_left();
+        if self.cursor != end {
+            self.delete_range(self.cursor, end);
+        }
+    }
+
+    fn delete_forward(&mut self) {
+        self.edit(EditKind::Delete);
+        if self.delete_selection() {
+            return;
+        }
+        let start = self.cursor;
+        self.move_right();
+        if self.cursor != start {
+            self.delete_range(start, self.cursor);
+        }
+    }
+
+    fn delete_word_backward(&mut self) {
+        self.edit(EditKind::Other);
+        if self.delete_selection() {
+            return;
+        }
+        let end = self.cursor;
+        self.move_word_left();
+        self.delete_range(self.cursor, end);
+    }
+
+    fn move_left(&mut self) {
+        let (row, col) = self.cursor;
+        if col > 0 {
+            self.cursor = (row, col - 1);
+        } else if row > 0 {
+            self.cursor = (row - 1, self.line_len(row - 1));
+        }
+    }
+
+    fn move_right(&mut self) {
+        let (row, col) = self.cursor;
+        if col < self.line_len(row) {
+            self.cursor = (row, col + 1);
+        } else if row + 1 < self.lines.len() {
+            self.cursor = (row + 1, 0);
+        }
+    }
+
+    fn move_up(&mut self) {
+        let (row, col) = self.cursor;
+        if row > 0 {
+            self.cursor = (row - 1, col.min(self.line_len(row - 1)));
+        }
+    }
+
+    fn move_down(&mut self) {
+        let (row, col) = self.cursor;
+        if row + 1 < self.lines.len() {
+            self.cursor = (row + 1, col.min(self.line_len(row + 1)));
+        }
+    }
+
+    // move_word_left goes to the start of the word before the cursor
+    fn move_word_left(&mut self) {
+        let (row, col) = self.cursor;
+        if col == 0 {
+            self.move_left();
+            return;
+        }
+        let line = graphemes(&self.lines[row]);
+        let mut col = col;
+        while col > 0 && is_space(line[col - 1]) {
+            col -= 1;
+        }
+        while col > 0 && !is_space(line[col - 1]) {
+            col -= 1;
+        }
+        self.cursor = (row, col);
+    }
+
+    // move_word_right goes past the end of the word after the cursor
+    fn move_word_right(&mut self) {
+        let (row, col) = self.cursor;
+        let line = graphemes(&self.lines[row]);
+        if col == line.len() {
+            self.move_right();
+            return;
+        }
+        let mut col = col;
+        while col < line.len() && is_space(line[col]) {
+            col += 1;
+        }
+        while col < line.len() && !is_space(line[col]) {
+            col += 1;
+        }
+        self.cursor = (row, col);
+    }
+
+    // moving runs a cursor movement, extending the selection when selecting
+    fn moving(&mut self, select: bool, movement: fn(&mut Self)) {
+        match select {
+            true if self.anchor.is_none() => self.anchor = Some(self.cursor),
+            true => {}
+            false => self.anchor = None,
+        }
+        movement(self);
+        self.last_edit = None;
+    }
+
+    // input handles the editing keys, other keys are left to the widget
+    pub fn input(&mut self, key: Key) -> EventState {
+        match key {
+            Key::Char(c) => {
+                self.edit(EditKind::Insert);
+                self.delete_selection();
+                self.insert_text(&c.to_string());
+            }
+            // A key sequence typed into the text is just its characters
+            Key::Chord(first, second) => {
+                self.edit(EditKind::Insert);
+                self.delete_selection();
+                self.insert_text(&format!("{first}{second}"));
+            }
+            Key::Enter if !self.single_line => {
+                self.edit(EditKind::Other);
+                self.delete_selection();
+                self.insert_text("\n");
+            }
+            Key::Backspace => self.backspace(),
+            Key::Delete => self.delete_forward(),
+            Key::Ctrl('w') => self.delete_word_backward(),
+            Key::Ctrl('z') => self.undo(),
+            Key::Ctrl('y') => self.redo(),
+            Key::Left => self.moving(false, Self::move_left),
+            Key::Right => self.moving(false, Self::move_right),
+            Key::Up if !self.single_line => self.moving(false, Self::move_up),
+            Key::Down if !self.single_line => self.moving(false, Self::move_down),
+            Key::Home => self.moving(false, |s| s.cursor.1 = 0),
+            Key::End => self.moving(false, |s| s.cursor.1 = s.line_len(s.cursor.0)),
+            Key::CtrlLeft | Key::Alt('b') => self.moving(false, Self::move_word_left),
+            Key::CtrlRight | Key::Alt('f') => self.moving(false, Self::move_word_right),
+            Key::ShiftLeft => self.moving(true, Self::move_left),
+            Key::ShiftRight => self.moving(true, Self::move_right),
+            Key::ShiftUp => self.moving(true, Self::move_up),
+            Key::ShiftDown => self.moving(true, Self::move_down),
+            Key::ShiftHome => self.moving(true, |s| s.cursor.1 = 0),
+            Key::ShiftEnd => self.moving(true, |s| s.cursor.1 = s.line_len(s.cursor.0)),
+            _ => return EventState::NotConsumed,
+        }
+        EventState::Consumed
+    }
+
+    // visual_rows are the wrapped rows of every line as the line and its range
+    // of graphemes
+    fn visual_rows(&self, width: usize) -> Vec<(usize, usize, usize)> {
+        self.lines
+            .iter()
+            .enumerate()
+            .flat_map(|(row, line)| {
+                wrap(&graphemes(line), width)
+                    .into_iter()
+                    .map(move |(start, end)| (row, start, end))
+            })
+            .collect()
+    }
+
+    // cursor_position is the row and column of the cursor once wrapped
+    fn cursor_position(&self, rows: &[(usize, usize, usize)], width: usize) -> (usize, usize) {
+        let (row, col) = self.cursor;
+        let index = rows
+            .iter()
+            .rposition(|(line, start, _)| *line == row && *start <= col)
+            .unwrap_or(0);
+        let (_, start, _) = rows[index];
+        let line = graphemes(&self.lines[row]);
+        let x: usize = line[start..col].iter().map(|g| g.width()).sum();
+        match width > 0 && x >= width {
+            true => (index + 1, 0),
+            false => (index, x),
+        }
+    }
+
+    pub fn draw<B: Backend>(
+        &mut self,
+        f: &mut Frame<B>,
+        rect: Rect,
+        block: Block,
+        style: Style,
+        editing: bool,
+    ) {
+        let inner = block.inner(rect);
+        let width = inner.width as usize;
+        let height = (inner.height as usize).max(1);
+        let rows = self.visual_rows(width);
+        let (cursor_y, cursor_x) = self.cursor_position(&rows, width);
+        if cursor_y < self.scroll {
+            self.scroll = cursor_y;
+        } else if cursor_y >= self.scroll + height {
+            self.scroll = cursor_y + 1 - height;
+        }
+
+        let selection = self.selection();
+        let selected_style = style.add_modifier(Modifier::REVERSED);
+        let text: Vec<Spans> = rows
+            .iter()
+            .skip(self.scroll)
+            .take(height)
+            .map(|&(row, start, end)| {
+                let line = graphemes(&self.lines[row]);
+                let spans: Vec<Span> = (start..end)
+                    .map(|col| {
+                        let selected = selection
+                            .is_some_and(|(from, to)| from <= (row, col) && (row, col) < to);
+                        match selected {
+                            true => Span::styled(line[col].to_string(), selected_style),
+                            false => Span::styled(line[col].to_string(), style),
+                        }
+                    })
+                    .collect();
+                Spans::from(spans)
+            })
+            .collect();
+        f.render_widget(Paragraph::new(text).style(style).block(block), rect);
+        if editing {
+            f.set_cursor(
+                inner.x + cursor_x as u16,
+                inner.y + (cursor_y - self.scroll) as u16,
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn typed(text: &str) -> TextArea {
+        let mut area = TextArea::new();
+        for c in text.chars() {
+            area.input(if c == '\n' { Key::Enter } else { Key::Char(c) });
+        }
+        area
+    }
+
+    #[test]
+    fn edits_graphemes_at_the_cursor() {
+        let mut area = typed("héllo 👍🏽 wörld");
+        area.input(Key::CtrlLeft);
+        area.input(Key::Left);
+        area.input(Key::Backspace);
+        assert_eq!(area.text(), "héllo  wörld");
+        area.input(Key::Home);
+        area.input(Key::Right);
+        area.input(Key::Delete);
+        assert_eq!(area.text(), "hllo  wörld");
+        area.input(Key::End);
+        area.input(Key::Ctrl('w'));
+        assert_eq!(area.text(), "hllo  ");
+    }
+
+    #[test]
+    fn lines_selection_and_undo() {
+        let mut area = typed("first\nsecond");
+        area.input(Key::Up);
+        area.input(Key::End);
+        area.input(Key::ShiftDown);
+        area.input(Key::ShiftEnd);
+        area.input(Key::Char('!'));
+        assert_eq!(area.text(), "first!");
+        area.input(Key::Ctrl('z'));
+        assert_eq!(area.text(), "first\nsecond");
+        area.input(Key::Ctrl('y'));
+        assert_eq!(area.text(), "first!");
+        area.input(Key::Ctrl('z'));
+        area.input(Key::Ctrl('z'));
+        assert_eq!(area.text(), "first\n");
+    }
+
+    #[test]
+    fn paste_is_undone_at_once() {
+        let mut area = TextArea::new();
+        area.input(Key::Char('>'));
+        area.insert_str("at main.rs:1\r\n\tat app.rs:2");
+        assert_eq!(area.text(), ">at main.rs:1\n\tat app.rs:2");
+        area.input(Key::Ctrl('z'));
+        assert_eq!(area.text(), ">");
+    }
+
+    #[test]
+    fn single_line_leaves_enter_to_the_widget() {
+        let mut area = TextArea::single_line();
+        area.set_text("one\ntwo");
+        assert_eq!(area.input(Key::Enter), EventState::NotConsumed);
+        assert_eq!(area.text(), "one two");
+    }
+
+    #[test]
+    fn wraps_after_spaces() {
+        let line = graphemes("the quick brown fox");
+        assert_eq!(wrap(&line, 8), vec![(0, 4), (4, 10), (10, 16), (16, 19)]);
+        assert_eq!(wrap(&graphemes("abcdef"), 4), vec![(0, 4), (4, 6)]);
+        assert_eq!(wrap(&[], 4), vec![(0, 0)]);
+    }
+}
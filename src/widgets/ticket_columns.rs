@@ -0,0 +1,325 @@
+use std::cmp::Ordering;
+
+use tui::layout::Constraint;
+
+use crate::{
+    config::{ColumnWidth, JiraConfigTicketTable},
+    jira::{
+        fields::{format_datetime, CustomField},
+        jql::SortOrder,
+        tickets::TicketData,
+    },
+};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ColumnKind {
+    Key,
+    Summary,
+    Priority,
+    Type,
+    Status,
+    Assignee,
+    Creator,
+    Reporter,
+    Created,
+    Updated,
+    Labels,
+    Components,
+    Project,
+    Custom(CustomField),
+}
+
+impl ColumnKind {
+    // from_name reads a column name from the config, custom fields go by the
+    // name given to them under [custom_fields]
+    pub fn from_name(name: &str, custom_fields: &[CustomField]) -> Option<Self> {
+        let kind = match name.to_lowercase().as_str() {
+            "key" => Self::Key,
+            "summary" => Self::Summary,
+            "priority" => Self::Priority,
+            "type" => Self::Type,
+            "status" => Self::Status,
+            "assignee" => Self::Assignee,
+            "creator" => Self::Creator,
+            "reporter" => Self::Reporter,
+            "created" => Self::Created,
+            "updated" => Self::Updated,
+            "labels" => Self::Labels,
+            "components" => Self::Components,
+            "project" => Self::Project,
+            _ => custom_fields
+                .iter()
+                .find(|custom_field| custom_field.name.eq_ignore_ascii_case(name))
+                .map(|custom_field| Self::Custom(custom_field.clone()))?,
+        };
+        Some(kind)
+    }
+
+    pub fn header(&self) -> &str {
+        match self {
+            Self::Key => "Key",
+            Self::Summary => "Summary",
+            Self::Priority => "Priority",
+            Self::Type => "Type",
+            Self::Status => "Status",
+            Self::Assignee => "Assignee",
+            Self::Creator => "Creator",
+            Self::Reporter => "Reporter",
+            Self::Created => "Created",
+            Self::Updated => "Updated",
+            Self::Labels => "Labels",
+            Self::Components => "Components",
+            Self::Project => "Project",
+            Self::Custom(custom_field) => &custom_field.name,
+        }
+    }
+
+    pub fn value(&self, ticket: &TicketData) -> String {
+        let fields = &ticket.fields;
+        match self {
+            Self::Key => ticket.key.clone(),
+            Self::Summary => fields.summary.clone(),
+            Self::Priority => fields
+                .priority
+                .as_ref()
+                .map(|p| p.name.clone())
+                .unwrap_or_default(),
+            Self::Type => fields.issuetype.name.clone(),
+            Self::Status => fields.status.name.clone(),
+            Self::Assignee => fields
+                .assignee
+                .as_ref()
+                .map(|a| a.display_name.clone())
+                .unwrap_or(String::from("Unassigned")),
+            Self::Creator => fields
+                .creator
+                .as_ref()
+                .map(|c| c.display_name.clone())
+                .unwrap_or_default(),
+            Self::Reporter => fields
+                .reporter
+                .as_ref()
+                .map(|r| r.display_name.clone())
+                .unwrap_or_default(),
+            Self::Created => fields
+                .created
+                .as_deref()
+                .map(format_datetime)
+                .unwrap_or_default(),
+            Self::Updated => fields
+                .updated
+                .as_deref()
+                .map(format_datetime)
+                .unwrap_or_default(),
+            Self::Labels => fields.labels.join(", "),
+            Self::Components => fields
+                .components
+                .iter()
+                .map(|c| c.name.as_str())
+                .collect::<Vec<&str>>()
+                .join(", "),
+            Self::Project => fields.project.key.clone(),
+            Self::Custom(custom_field) => {
+                custom_field.format(fields.custom_fields.get(&custom_field.field_id))
+            }
+        }
+    }
+
+    // compare orders tickets by this column, keys by their number and numeric
+    // custom fields by value instead of as text
+    pub fn compare(&self, a: &TicketData, b: &TicketData) -> Ordering {
+        match self {
+            Self::Key => key_order(&a.key).cmp(&key_order(&b.key)),
+            Self::Created => a.fields.created.cmp(&b.fields.created),
+            Self::Updated => a.fields.updated.cmp(&b.fields.updated),
+            Self::Custom(custom_field) => {
+                let number = |ticket: &TicketData| {
+                    ticket
+                        .fields
+                        .custom_fields
+                        .get(&custom_field.field_id)
+                        .and_then(|value| value.as_f64())
+                };
+                match (number(a), number(b)) {
+                    (Some(x), Some(y)) => x.total_cmp(&y),
+                    _ => self.text_compare(a, b),
+                }
+            }
+            _ => self.text_compare(a, b),
+        }
+    }
+
+    fn text_compare(&self, a: &TicketData, b: &TicketData) -> Ordering {
+        self.value(a)
+            .to_lowercase()
+            .cmp(&self.value(b).to_lowercase())
+    }
+}
+
+fn key_order(key: &str) -> (&str, u64) {
+    match key.rsplit_once('-') {
+        Some((project, number)) => (project, number.parse().unwrap_or(0)),
+        None => (key, 0),
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TicketColumn {
+    pub kind: ColumnKind,
+    pub width: Constraint,
+}
+
+impl TicketColumn {
+    fn new(kind: ColumnKind, width: Constraint) -> Self {
+        Self { kind, width }
+    }
+}
+
+// default_columns are shown when [ticket_table] has no columns, the custom
+// fields are added after the built in ones
+pub fn default_columns(custom_fields: &[CustomField]) -> Vec<TicketColumn> {
+    let mut columns = vec![
+        TicketColumn::new(ColumnKind::Key, Constraint::Percentage(12)),
+        TicketColumn::new(ColumnKind::Priority, Constraint::Percentage(10)),
+        TicketColumn::new(ColumnKind::Type, Constraint::Percentage(12)),
+        TicketColumn::new(ColumnKind::Status, Constraint::Percentage(16)),
+        TicketColumn::new(ColumnKind::Assignee, Constraint::Percentage(17)),
+        TicketColumn::new(ColumnKind::Creator, Constraint::Percentage(16)),
+        TicketColumn::new(ColumnKind::Reporter, Constraint::Percentage(17)),
+    ];
+    columns.extend(custom_fields.iter().map(|custom_field| {
+        TicketColumn::new(
+            ColumnKind::Custom(custom_field.clone()),
+            Constraint::Min(10),
+        )
+    }));
+    columns
+}
+
+fn constraint(width: &Option<ColumnWidth>) -> Option<Constraint> {
+    match width {
+        None => Some(Constraint::Min(8)),
+        Some(ColumnWidth::Length(length)) => Some(Constraint::Length(*length)),
+        Some(ColumnWidth::Percentage(percentage)) => percentage
+            .trim()
+            .strip_suffix('%')
+            .and_then(|p| p.trim().parse().ok())
+            .map(Constraint::Percentage),
+    }
+}
+
+// from_config builds the ticket table columns and the default sort of the
+// [ticket_table] config. Entries that can not be used are reported back.
+pub fn from_config(
+    config: &JiraConfigTicketTable,
+    custom_fields: &[CustomField],
+) -> (Vec<TicketColumn>, Option<(usize, SortOrder)>, Vec<String>) {
+    let mut problems = vec![];
+    let columns = match &config.columns {
+        Some(configured) if !configured.is_empty() => configured
+            .iter()
+            .filter_map(|column| {
+                let Some(kind) = ColumnKind::from_name(&column.name, custom_fields) else {
+                    problems.push(format!("unknown column {}", column.name));
+                    return None;
+                };
+                let Some(width) = constraint(&column.width) else {
+                    problems.push(format!("invalid width for column {}", column.name));
+                    return Some(TicketColumn::new(kind, Constraint::Min(8)));
+                };
+                Some(TicketColumn::new(kind, width))
+            })
+            .collect(),
+        _ => default_columns(custom_fields),
+    };
+
+    let order = match config
+        .sort_order
+        .as_deref()
+        .map(str::to_lowercase)
+        .as_deref()
+    {
+        None | Some("asc") => SortOrder::Asc,
+        Some("desc") => SortOrder::Desc,
+        Some(order) => {
+            problems.push(format!("unknown sort order {order}"));
+            SortOrder::Asc
+        }
+    };
+    let sort = config.sort_by.as_ref().and_then(|sort_by| {
+        let index = columns
+            .iter()
+            .position(|column| column.kind.header().eq_ignore_ascii_case(sort_by));
+        if index.is_none() {
+            problems.push(format!("sort column {sort_by} is not in the table"));
+        }
+        index.map(|index| (index, order))
+    });
+
+    (columns, sort, problems)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::JiraConfigColumn;
+
+    fn column(name: &str, width: Option<ColumnWidth>) -> JiraConfigColumn {
+        JiraConfigColumn {
+            name: name.to_string(),
+            width,
+        }
+    }
+
+    #[test]
+    fn columns_and_sort_from_config() {
+        let points = CustomField {
+            name: "Points".to_string(),
+            field_id: "customfield_10016".to_string(),
+            schema: None,
+        };
+        let config = JiraConfigTicketTable {
+            columns: Some(vec![
+                column("key", Some(ColumnWidth::Length(10))),
+                column("Summary", Some(ColumnWidth::Percentage("40%".to_string()))),
+                column("points", None),
+                column("resolution", None),
+                column("updated", Some(ColumnWidth::Percentage("wide".to_string()))),
+            ]),
+            sort_by: Some("Updated".to_string()),
+            sort_order: Some("DESC".to_string()),
+        };
+        let (columns, sort, problems) = from_config(&config, std::slice::from_ref(&points));
+        assert_eq!(
+            columns,
+            vec![
+                TicketColumn::new(ColumnKind::Key, Constraint::Length(10)),
+                TicketColumn::new(ColumnKind::Summary, Constraint::Percentage(40)),
+                TicketColumn::new(ColumnKind::Custom(points), Constraint::Min(8)),
+                TicketColumn::new(ColumnKind::Updated, Constraint::Min(8)),
+            ]
+        );
+        assert_eq!(sort, Some((3, SortOrder::Desc)));
+        assert_eq!(
+            problems,
+            vec![
+                "unknown column resolution".to_string(),
+                "invalid width for column updated".to_string()
+            ]
+        );
+    }
+
+    #[test]
+    fn default_columns_have_one_width_each() {
+        let (columns, sort, problems) = from_config(&JiraConfigTicketTable::default(), &[]);
+        assert_eq!(columns.len(), 7);
+        assert_eq!(sort, None);
+        assert!(problems.is_empty());
+    }
+
+    #[test]
+    fn keys_sort_by_number() {
+        assert!(key_order("ABC-9") < key_order("ABC-10"));
+        assert!(key_order("ABC-10") < key_order("ABD-1"));
+    }
+}
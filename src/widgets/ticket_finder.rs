@@ -0,0 +1,300 @@
+use std::collections::HashMap;
+
+use html2md::parse_html;
+use simsearch::SimSearch;
+use tui::{
+    backend::Backend,
+    layout::{Constraint, Direction, Layout},
+    style::{Modifier, Style},
+    text::{Span, Spans, Text},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph, Wrap},
+    Frame,
+};
+
+use crate::{config::KeyConfig, events::key::Key, jira::tickets::TicketData, theme::theme};
+
+use super::{draw_edit_style, draw_highlight_style, text_area::TextArea, EventState};
+
+#[derive(Debug, Clone, Copy)]
+pub enum Action {
+    Close,
+    Jump,
+    Next(usize),
+    Previous(usize),
+}
+
+// FinderRequest is what the app is asked to do once a key is handled
+pub enum FinderRequest {
+    Close,
+    Jump(Box<TicketData>),
+}
+
+// TicketFinderWidget searches every cached ticket, whatever its project, by
+// key, summary, labels and assignee
+pub struct TicketFinderWidget {
+    editor: TextArea,
+    engine: SimSearch<usize>,
+    // tickets are the cached tickets, last updated first
+    tickets: Vec<TicketData>,
+    // matches are the indexes of the tickets matching the input, best first
+    matches: Vec<usize>,
+    state: ListState,
+    // preview is the description of the previewed ticket, parsed once
+    preview: Option<(usize, String)>,
+    pub request: Option<FinderRequest>,
+    // key_mappings only hold keys that can not be typed in the search, the
+    // rest goes to the input
+    pub key_mappings: HashMap<Key, Action>,
+}
+
+impl TicketFinderWidget {
+    pub fn new(key_config: KeyConfig) -> Self {
+        let key_mappings = HashMap::from([
+            (key_config.esc, Action::Close),
+            (key_config.enter, Action::Jump),
+            (key_config.move_down, Action::Next(1)),
+            (key_config.next, Action::Next(1)),
+            (key_config.move_up, Action::Previous(1)),
+            (key_config.previous, Action::Previous(1)),
+            (key_config.scroll_down_multiple_lines, Action::Next(10)),
+            (key_config.scroll_up_multiple_lines, Action::Previous(10)),
+        ]);
+        Self {
+            key_mappings,
+            editor: TextArea::single_line(),
+            engine: SimSearch::new(),
+            tickets: vec![],
+            matches: vec![],
+            state: ListState::default(),
+            preview: None,
+            request: None,
+        }
+    }
+
+    pub fn open(&mut self, mut tickets: Vec<TicketData>) {
+        tickets.sort_by(|a, b| b.fields.updated.cmp(&a.fields.updated));
+        self.engine = SimSearch::new();
+        for (index, ticket) in tickets.iter().enumerate() {
+            let mut tokens = vec![ticket.key.as_str(), ticket.fields.summary.as_str()];
+            tokens.extend(ticket.fields.labels.iter().map(String::as_str));
+            if let Some(assignee) = &ticket.fields.assignee {
+                tokens.push(&assignee.display_name);
+            }
+            self.engine.insert_tokens(index, &tokens);
+        }
+        self.tickets = tickets;
+        self.editor.clear();
+        self.preview = None;
+        self.search();
+    }
+
+    // search ranks the tickets by how close their words are to the input,
+    // tickets whose key starts with the input come first
+    fn search(&mut self) {
+        let input = self.editor.text();
+        let input = input.trim();
+        self.matches = match input.is_empty() {
+            true => (0..self.tickets.len()).collect(),
+            false => self.engine.search(input),
+        };
+        let prefix = input.to_lowercase();
+        self.matches
+            .sort_by_key(|&index| !self.tickets[index].key.to_lowercase().starts_with(&prefix));
+        self.state.select((!self.matches.is_empty()).then_some(0));
+    }
+
+    pub fn selected(&self) -> Option<&TicketData> {
+        let index = self.matches.get(self.state.selected()?)?;
+        self.tickets.get(*index)
+    }
+
+    fn select(&mut self, lines: usize, forward: bool) {
+        let Some(selected) = self.state.selected() else {
+            return;
+        };
+        let last = self.matches.len().saturating_sub(1);
+        self.state.select(Some(match forward {
+            true => (selected + lines).min(last),
+            false => selected.saturating_sub(lines),
+        }));
+    }
+
+    pub fn event(&mut self, key: Key) -> EventState {
+        let Some(action) = self.key_mappings.get(&key).copied() else {
+            if !self.editor.input(key).is_consumed() {
+                return EventState::NotConsumed;
+            }
+            self.search();
+            return EventState::Consumed;
+        };
+        match action {
+            Action::Close => self.request = Some(FinderRequest::Close),
+            Action::Jump => {
+                if let Some(ticket) = self.selected() {
+                    self.request = Some(FinderRequest::Jump(Box::new(ticket.clone())));
+                }
+            }
+            Action::Next(lines) => self.select(lines, true),
+            Action::Previous(lines) => self.select(lines, false),
+        }
+        EventState::Consumed
+    }
+
+    pub fn paste(&mut self, text: &str) -> EventState {
+        self.editor.insert_str(text);
+        self.search();
+        EventState::Consumed
+    }
+
+    pub fn draw<B: Backend>(&mut self, f: &mut Frame<B>) -> anyhow::Result<()> {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .margin(2)
+            .constraints([Constraint::Length(3), Constraint::Min(1)])
+            .split(f.size());
+        f.render_widget(Clear, f.size());
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .title("Find ticket in all projects");
+        self.editor
+            .draw(f, chunks[0], block, draw_edit_style(true), true);
+
+        let body = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(45), Constraint::Percentage(55)])
+            .split(chunks[1]);
+
+        let items: Vec<ListItem> = self
+            .matches
+            .iter()
+            .map(|&index| {
+                let ticket = &self.tickets[index];
+                ListItem::new(Spans::from(vec![
+                    Span::styled(format!("{:<12}", ticket.key), theme().muted),
+                    Span::raw(ticket.fields.summary.clone()),
+                ]))
+            })
+            .collect();
+        let title = format!("Tickets {}/{}", self.matches.len(), self.tickets.len());
+        let list = List::new(items)
+            .block(Block::default().borders(Borders::ALL).title(title))
+            .highlight_style(draw_highlight_style());
+        f.render_stateful_widget(list, body[0], &mut self.state);
+
+        let preview = self.preview_text();
+        let paragraph = Paragraph::new(preview)
+            .block(Block::default().borders(Borders::ALL).title("Preview"))
+            .wrap(Wrap { trim: true });
+        f.render_widget(paragraph, body[1]);
+        Ok(())
+    }
+
+    fn preview_text(&mut self) -> Text<'static> {
+        let Some(&index) = self.state.selected().and_then(|i| self.matches.get(i)) else {
+            return Text::raw("No cached ticket matches");
+        };
+        let description = match &self.preview {
+            Some((previewed, description)) if *previewed == index => description.clone(),
+            _ => {
+                let description = parse_html(&self.tickets[index].rendered_fields.description);
+                self.preview = Some((index, description.clone()));
+                description
+            }
+        };
+        let fields = &self.tickets[index].fields;
+        let field = |name: &str, value: String| {
+            Spans::from(vec![
+                Span::styled(
+                    format!("{name}: "),
+                    Style::default().add_modifier(Modifier::BOLD),
+                ),
+                Span::raw(value),
+            ])
+        };
+        let mut text = Text::from(vec![
+            Spans::from(Span::styled(
+                fields.summary.clone(),
+                Style::default().add_modifier(Modifier::BOLD),
+            )),
+            Spans::default(),
+            field(
+                "Project",
+                format!("{} ({})", fields.project.name, fields.project.key),
+            ),
+            Spans::from(vec![
+                Span::styled("Status: ", Style::default().add_modifier(Modifier::BOLD)),
+                Span::styled(
+                    fields.status.name.clone(),
+                    theme().status_style(&fields.status),
+                ),
+            ]),
+            field(
+                "Assignee",
+                fields
+                    .assignee
+                    .as_ref()
+                    .map(|a| a.display_name.clone())
+                    .unwrap_or_else(|| String::from("Unassigned")),
+            ),
+            field("Labels", fields.labels.join(", ")),
+            Spans::default(),
+        ]);
+        text.extend(Text::raw(description));
+        text
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn ticket(key: &str, summary: &str, labels: &[&str], assignee: &str) -> TicketData {
+        serde_json::from_value(json!({
+            "key": key,
+            "renderedFields": { "description": "" },
+            "fields": {
+                "assignee": { "displayName": assignee },
+                "components": [],
+                "issuelinks": [],
+                "issuetype": { "id": "10004", "name": "Bug", "subtask": false },
+                "labels": labels,
+                "project": { "key": key.split('-').next().unwrap(), "name": "" },
+                "status": { "name": "To Do" },
+                "summary": summary,
+            }
+        }))
+        .unwrap()
+    }
+
+    fn found(finder: &mut TicketFinderWidget, input: &str) -> Vec<String> {
+        finder.editor.set_text(input);
+        finder.search();
+        finder
+            .matches
+            .iter()
+            .map(|&i| finder.tickets[i].key.clone())
+            .collect()
+    }
+
+    #[test]
+    fn finds_tickets_across_projects() {
+        let mut finder = TicketFinderWidget::new(KeyConfig::default());
+        finder.open(vec![
+            ticket("WEB-12", "Checkout button misaligned", &["frontend"], "Ada"),
+            ticket("API-3", "Payment webhook retries", &["payments"], "Grace"),
+            ticket("OPS-7", "Rotate API keys", &[], "Ada"),
+        ]);
+        assert_eq!(found(&mut finder, "paymnt"), vec!["API-3"]);
+        assert_eq!(found(&mut finder, "frontend"), vec!["WEB-12"]);
+        assert_eq!(found(&mut finder, "grace"), vec!["API-3"]);
+        assert_eq!(found(&mut finder, "api"), vec!["API-3", "OPS-7"]);
+
+        finder.event(Key::Enter);
+        assert!(matches!(
+            finder.request.take(),
+            Some(FinderRequest::Jump(ticket)) if ticket.key == "API-3"
+        ));
+    }
+}
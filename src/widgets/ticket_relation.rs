@@ -11,13 +11,18 @@ use crate::{
     jira::tickets::{Links, TicketData},
 };
 
-use super::{commands::CommandInfo, draw_block_style, draw_highlight_style, Component, EventState};
+use super::{
+    clicked_table_row, commands::CommandInfo, draw_block_style, draw_highlight_style, row_height,
+    Component, EventState,
+};
 
 #[derive(Debug)]
 pub struct RelationWidget {
     jira_domain: String,
     key_config: KeyConfig,
     state: TableState,
+    // row_heights are the lines of every drawn row
+    row_heights: Vec<u16>,
     pub ticket_links: Vec<Links>,
 }
 
@@ -46,6 +51,7 @@ impl RelationWidget {
         let header_cells = ["Relation", "Key", "Summary", "Priority", "Type", "Status"];
         let headers = Row::new(header_cells);
         self.ticket_links = ticket.fields.issuelinks.clone();
+        let mut row_heights = Vec::with_capacity(self.ticket_links.len());
         let rows = self.ticket_links.iter().map(|link_details| {
             let link_relation_detail;
             let link_relation = match (&link_details.outward_issue, &link_details.inward_issue) {
@@ -71,14 +77,10 @@ impl RelationWidget {
                 link_relation.fields.issuetype.name.as_str(),
                 link_relation.fields.status.name.as_str(),
             ];
-            let height = item
-                .iter()
-                .map(|content| content.chars().filter(|c| *c == '\n').count())
-                .max()
-                .unwrap_or(0)
-                + 1;
+            let height = row_height(&item);
+            row_heights.push(height);
             let cells = item.iter().map(|c| Cell::from(*c));
-            Row::new(cells).height(height as u16)
+            Row::new(cells).height(height)
         });
         let table = Table::new(rows)
             .header(headers)
@@ -92,6 +94,7 @@ impl RelationWidget {
                 Constraint::Percentage(20),
                 Constraint::Percentage(20),
             ]);
+        self.row_heights = row_heights;
 
         f.render_widget(Clear, rect);
         f.render_stateful_widget(table, rect, &mut self.state);
@@ -107,6 +110,7 @@ impl RelationWidget {
         Self {
             jira_domain: jira_domain.to_string(),
             key_config,
+            row_heights: vec![],
             ticket_links: vec![],
             state,
         }
@@ -154,6 +158,17 @@ impl RelationWidget {
         }
     }
 
+    // click selects the link drawn at the row of the rect
+    pub fn click(&mut self, rect: Rect, row: u16) -> bool {
+        let offset = self.state.offset();
+        let index = clicked_table_row(rect, 1, offset, &self.row_heights, row)
+            .filter(|i| *i < self.ticket_links.len());
+        if index.is_some() {
+            self.state.select(index);
+        }
+        index.is_some()
+    }
+
     pub fn open_browser(&mut self) {
         if self.selected().is_some() {
             let link_details = self.selected().unwrap().clone();
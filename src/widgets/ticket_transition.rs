@@ -3,9 +3,9 @@ use log::{debug, trace};
 use tui::{
     backend::Backend,
     layout::{Constraint, Direction, Layout, Rect},
-    style::{Color, Style},
+    style::Style,
     text::{Span, Spans},
-    widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph, Wrap},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState},
     Frame,
 };
 
@@ -16,7 +16,8 @@ use crate::{
 };
 
 use super::{
-    commands::CommandInfo, draw_block_style, draw_highlight_style, Component, EventState, InputMode,
+    commands::CommandInfo, draw_block_style, draw_edit_style, draw_highlight_style,
+    text_area::TextArea, Component, EventState, InputMode,
 };
 
 #[derive(Debug)]
@@ -27,7 +28,7 @@ pub struct TransitionWidget {
     focus_float_screen: Option<bool>,
     key_config: KeyConfig,
     state: ListState,
-    pub comment_float_screen: String,
+    pub comment_float_screen: TextArea,
     pub push_transition: bool,
     pub push_transition_reason: Option<String>,
     pub transitions: Vec<TicketTransition>,
@@ -144,16 +145,12 @@ impl TransitionWidget {
             .block(draw_block_style(true, title))
             .highlight_style(draw_highlight_style());
 
-        let comment = self.comment_float_screen.clone();
-        let input = Paragraph::new(comment)
-            .wrap(Wrap { trim: true })
-            .style(match self.input_mode {
-                InputMode::Normal => Style::default(),
-                InputMode::Editing => Style::default().fg(Color::Yellow),
-            })
-            .block(Block::default().borders(Borders::ALL).title("Comment"));
+        let editing = self.input_mode == InputMode::Editing;
+        let style = draw_edit_style(editing);
+        let block = Block::default().borders(Borders::ALL).title("Comment");
         f.render_widget(Clear, chunks[1]);
-        f.render_widget(input, chunks[1]);
+        self.comment_float_screen
+            .draw(f, chunks[1], block, style, editing);
         f.render_stateful_widget(list, chunks[0], &mut self.float_screen_list_state);
 
         Ok(())
@@ -170,7 +167,7 @@ impl TransitionWidget {
         }
         Self {
             draw_list_float_screen: None,
-            comment_float_screen: String::new(),
+            comment_float_screen: TextArea::new(),
             float_screen_list_state,
             float_screen_list: None,
             focus_float_screen: None,
@@ -298,27 +295,28 @@ impl TransitionWidget {
 }
 
 impl TransitionWidget {
+    pub fn paste(&mut self, text: &str) -> EventState {
+        if self.input_mode != InputMode::Editing {
+            return EventState::NotConsumed;
+        }
+        self.comment_float_screen.insert_str(text);
+        EventState::Consumed
+    }
+
     fn float_screen_event(&mut self, key: Key) -> anyhow::Result<EventState> {
         if self.input_mode == InputMode::Editing {
             match key {
-                Key::Char(c) => {
-                    let mut comment = self.comment_float_screen.clone();
-                    comment.push(c);
-                    self.comment_float_screen = comment;
-                    return Ok(EventState::Consumed);
-                }
-                Key::Backspace => {
-                    let mut comment = self.comment_float_screen.clone();
-                    comment.pop();
-                    self.comment_float_screen = comment;
-                    return Ok(EventState::Consumed);
-                }
                 Key::Esc => {
                     self.input_mode = InputMode::Normal;
                     return Ok(EventState::Consumed);
                 }
-                // _ => self.movement(key), // _ => return Ok(EventState::NotConsumed)
-                _ => {}
+                // Enter still picks the reason while writing the comment
+                Key::Enter => {}
+                _ => {
+                    if self.comment_float_screen.input(key).is_consumed() {
+                        return Ok(EventState::Consumed);
+                    }
+                }
             }
         }
         if key == self.key_config.scroll_down {
@@ -1,17 +1,26 @@
-use crate::{config::KeyConfig, events::key::Key, jira::tickets::TicketData};
-use std::collections::HashMap;
+use crate::{
+    config::KeyConfig,
+    events::key::Key,
+    jira::{fields::CustomField, jql::SortOrder, tickets::TicketData},
+    theme::theme,
+};
+use std::collections::{BTreeSet, HashMap, HashSet};
 
 use html2md::parse_html;
 use tui::{
     backend::Backend,
     layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::Style,
     widgets::{Cell, Clear, Paragraph, Row, Table, TableState, Wrap},
     Frame,
 };
 
 use super::{
+    clicked_table_row,
     commands::{CommandInfo, CommandText},
-    draw_block_style, draw_highlight_style, Component, EventState,
+    draw_block_style, draw_highlight_style, row_height,
+    ticket_columns::{default_columns, ColumnKind, TicketColumn},
+    Component, EventState,
 };
 
 #[derive(Debug, Clone, Copy)]
@@ -23,6 +32,11 @@ pub enum Action {
     First,
     ScrollDownDescription(u16),
     ScrollUpDescription(u16),
+    SortNextColumn,
+    ToggleSortOrder,
+    ToggleMark,
+    MarkAll,
+    ClearMarks,
 }
 
 impl Action {
@@ -46,6 +60,22 @@ impl Action {
                 format!("Scroll up description {line} [{key}]"),
                 CMD_GROUP_GENERAL,
             ),
+            Self::SortNextColumn => {
+                CommandText::new(format!("Sort by next column [{key}]"), CMD_GROUP_GENERAL)
+            }
+            Self::ToggleSortOrder => {
+                CommandText::new(format!("Toggle sort order [{key}]"), CMD_GROUP_GENERAL)
+            }
+            Self::ToggleMark => {
+                CommandText::new(format!("Select ticket [{key}]"), CMD_GROUP_GENERAL)
+            }
+            Self::MarkAll => CommandText::new(
+                format!("Select all visible tickets [{key}]"),
+                CMD_GROUP_GENERAL,
+            ),
+            Self::ClearMarks => {
+                CommandText::new(format!("Clear selection [{key}]"), CMD_GROUP_GENERAL)
+            }
         }
     }
 }
@@ -55,9 +85,26 @@ pub struct TicketWidget {
     jira_domain: String,
     state: TableState,
     scroll: u16,
+    // jira_order is the position of every ticket in the Jira results, used
+    // when the sort is turned off again
+    jira_order: HashMap<String, usize>,
+    pub columns: Vec<TicketColumn>,
+    pub context: Option<String>,
+    pub custom_fields: Vec<CustomField>,
+    // rank_offset is the rank of the first row when the list follows the board rank
+    pub rank_offset: Option<u32>,
+    pub sort: Option<(usize, SortOrder)>,
     pub ticket_description: Option<String>,
     pub tickets: Vec<TicketData>,
     pub key_mappings: HashMap<Key, Action>,
+    // marked_keys are the tickets selected for a bulk operation, only on the
+    // page shown as they are cleared when the list or the page changes
+    pub marked_keys: BTreeSet<String>,
+    // row_heights are the lines of every drawn row, cells can span several
+    row_heights: Vec<u16>,
+    pub unread_notifications: usize,
+    // updated_keys are the tickets changed since they were last opened
+    pub updated_keys: HashSet<String>,
 }
 
 impl TicketWidget {
@@ -68,70 +115,130 @@ impl TicketWidget {
         focused: bool,
         rect: Rect,
     ) -> anyhow::Result<()> {
-        let title = "Tickets";
+        let mut title = match &self.context {
+            Some(context) => format!("Tickets - {context}"),
+            None => String::from("Tickets"),
+        };
+        if !self.marked_keys.is_empty() {
+            title = format!("{title} - {} selected", self.marked_keys.len());
+        }
+        if self.unread_notifications > 0 {
+            title = format!(
+                "{title} - {} unread notifications",
+                self.unread_notifications
+            );
+        }
 
-        let header_cells = [
-            "Key", "Priority", "Type", "Status", "Assignee", "Creator", "Reporter",
-        ];
+        let columns = self.columns.clone();
+        let sort = self.sort.filter(|_| self.rank_offset.is_none());
+        let mut header_cells: Vec<String> = columns
+            .iter()
+            .enumerate()
+            .map(|(index, column)| match sort {
+                Some((sorted, SortOrder::Asc)) if sorted == index => {
+                    format!("{} ▲", column.kind.header())
+                }
+                Some((sorted, SortOrder::Desc)) if sorted == index => {
+                    format!("{} ▼", column.kind.header())
+                }
+                _ => column.kind.header().to_string(),
+            })
+            .collect();
+        let mut widths: Vec<Constraint> = columns.iter().map(|column| column.width).collect();
+        if self.rank_offset.is_some() {
+            header_cells.insert(0, String::from("Rank"));
+            widths.insert(0, Constraint::Length(5));
+        }
+        let marking = !self.marked_keys.is_empty();
+        if marking {
+            header_cells.insert(0, String::new());
+            widths.insert(0, Constraint::Length(1));
+        }
+        let custom_fields = self.custom_fields.clone();
         let headers = Row::new(header_cells);
         let tickets = self.tickets.clone();
-        let rows = tickets.iter().map(|ticket| {
-            let assignee = match &ticket.fields.assignee {
-                Some(i) => i.display_name.as_str(),
-                _ => "Unassigned",
-            };
-            let creator = match &ticket.fields.creator {
-                Some(i) => i.display_name.as_str(),
-                _ => "",
-            };
-            let reporter = match &ticket.fields.reporter {
-                Some(i) => i.display_name.as_str(),
-                _ => "",
-            };
-            let priority = match &ticket.fields.priority {
-                Some(i) => i.name.as_str(),
-                _ => "",
-            };
-            let item = [
-                ticket.key.as_str(),
-                priority,
-                ticket.fields.issuetype.name.as_str(),
-                ticket.fields.status.name.as_str(),
-                assignee,
-                creator,
-                reporter,
-            ];
-            let height = item
+        let ranks: Vec<String> = match self.rank_offset {
+            Some(offset) => (0..tickets.len())
+                .map(|i| (offset as usize + i + 1).to_string())
+                .collect(),
+            None => vec![],
+        };
+        let mut row_heights = Vec::with_capacity(tickets.len());
+        let rows = tickets.iter().enumerate().map(|(index, ticket)| {
+            let mut item: Vec<String> = columns
+                .iter()
+                .map(|column| column.kind.value(ticket))
+                .collect();
+            let mut styles: Vec<Style> = columns
                 .iter()
-                .map(|content| content.chars().filter(|c| *c == '\n').count())
-                .max()
-                .unwrap_or(0)
-                + 1;
-            let cells = item.iter().map(|c| Cell::from(*c));
-            Row::new(cells).height(height as u16)
+                .map(|column| match column.kind {
+                    ColumnKind::Status => theme().status_style(&ticket.fields.status),
+                    ColumnKind::Priority => match &ticket.fields.priority {
+                        Some(priority) => theme().priority_style(&priority.name),
+                        None => Style::default(),
+                    },
+                    _ => Style::default(),
+                })
+                .collect();
+            if let Some(rank) = ranks.get(index) {
+                item.insert(0, rank.clone());
+                styles.insert(0, Style::default());
+            }
+            if marking {
+                let marker = match self.marked_keys.contains(&ticket.key) {
+                    true => "✓",
+                    false => "",
+                };
+                item.insert(0, marker.to_string());
+                styles.insert(0, Style::default());
+            }
+            let height = row_height(&item);
+            row_heights.push(height);
+            let cells = item
+                .into_iter()
+                .zip(styles)
+                .map(|(content, style)| Cell::from(content).style(style));
+            let row = Row::new(cells).height(height);
+            match self.updated_keys.contains(&ticket.key) {
+                true => row.style(theme().updated),
+                false => row,
+            }
         });
         let table = Table::new(rows)
             .header(headers)
-            .block(draw_block_style(focused, title))
+            .block(draw_block_style(focused, &title))
             .highlight_style(draw_highlight_style())
-            .widths(&[
-                Constraint::Percentage(15),
-                Constraint::Percentage(10),
-                Constraint::Percentage(15),
-                Constraint::Percentage(20),
-                Constraint::Percentage(20),
-                Constraint::Percentage(20),
-            ]);
+            .widths(&widths);
+        self.row_heights = row_heights;
 
         match self.selected() {
             Some(ticket) => {
                 let summary = format!("{:} - {:}", ticket.key, ticket.fields.summary.clone());
                 let description = ticket.rendered_fields.description.clone();
-                self.draw_description(f, focused, description_frame, summary, description)
-            }
-            None => {
-                self.draw_description(f, focused, description_frame, String::new(), String::new())
+                let custom_values = custom_fields
+                    .iter()
+                    .map(|custom_field| {
+                        let value = ticket.fields.custom_fields.get(&custom_field.field_id);
+                        format!("{}: {}", custom_field.name, custom_field.format(value))
+                    })
+                    .collect();
+                self.draw_description(
+                    f,
+                    focused,
+                    description_frame,
+                    summary,
+                    description,
+                    custom_values,
+                )
             }
+            None => self.draw_description(
+                f,
+                focused,
+                description_frame,
+                String::new(),
+                String::new(),
+                vec![],
+            ),
         }?;
 
         f.render_widget(Clear, rect);
@@ -147,6 +254,7 @@ impl TicketWidget {
         rect: Rect,
         summary: String,
         description: String,
+        custom_values: Vec<String>,
     ) -> anyhow::Result<()> {
         f.render_widget(Clear, rect);
         let summary_title = "Summary";
@@ -170,13 +278,27 @@ impl TicketWidget {
             .wrap(Wrap { trim: true })
             .scroll((self.scroll, 0));
 
+        let fields_height = match self.custom_fields.is_empty() {
+            true => 0,
+            false => self.custom_fields.len() as u16 + 2,
+        };
         let main_chunks = Layout::default()
             .direction(Direction::Vertical)
-            .constraints([Constraint::Length(5), Constraint::Percentage(85)])
+            .constraints([
+                Constraint::Length(5),
+                Constraint::Length(fields_height),
+                Constraint::Percentage(85),
+            ])
             .split(rect);
 
         f.render_widget(summary_paragraph, main_chunks[0]);
-        f.render_widget(paragraph, main_chunks[1]);
+        if fields_height > 0 {
+            let fields_paragraph = Paragraph::new(custom_values.join("\n"))
+                .block(draw_block_style(focused, "Fields"))
+                .wrap(Wrap { trim: true });
+            f.render_widget(fields_paragraph, main_chunks[1]);
+        }
+        f.render_widget(paragraph, main_chunks[2]);
 
         Ok(())
     }
@@ -198,16 +320,31 @@ impl TicketWidget {
             map.insert(key_config.scroll_to_top, Action::First);
             map.insert(key_config.page_down, Action::ScrollDownDescription(1));
             map.insert(key_config.page_up, Action::ScrollUpDescription(1));
+            map.insert(key_config.sort_column, Action::SortNextColumn);
+            map.insert(key_config.sort_order, Action::ToggleSortOrder);
+            map.insert(key_config.toggle_select, Action::ToggleMark);
+            map.insert(key_config.select_all, Action::MarkAll);
+            map.insert(key_config.clear_selection, Action::ClearMarks);
             map
         };
 
         Self {
+            columns: default_columns(&[]),
+            context: None,
+            custom_fields: vec![],
             jira_domain,
+            jira_order: HashMap::new(),
             key_mappings,
+            marked_keys: BTreeSet::new(),
+            rank_offset: None,
+            row_heights: vec![],
             scroll: 0,
+            sort: None,
             state,
             ticket_description: None,
             tickets: vec![],
+            unread_notifications: 0,
+            updated_keys: HashSet::new(),
         }
     }
 
@@ -285,6 +422,26 @@ impl TicketWidget {
         }
     }
 
+    // click selects the ticket drawn at the row of the rect
+    pub fn click(&mut self, rect: Rect, row: u16) -> bool {
+        let offset = self.state.offset();
+        let Some(index) = clicked_table_row(rect, 1, offset, &self.row_heights, row)
+            .filter(|i| *i < self.tickets.len())
+        else {
+            return false;
+        };
+        if self.state.selected() != Some(index) {
+            self.ticket_description = None;
+            self.scroll = 0;
+        }
+        self.select(Some(index));
+        true
+    }
+
+    pub fn selected_index(&self) -> Option<usize> {
+        self.state.selected().filter(|i| *i < self.tickets.len())
+    }
+
     pub fn select(&mut self, index: Option<usize>) {
         if index.is_some() {
             self.state.select(index)
@@ -301,6 +458,55 @@ impl TicketWidget {
         Ok(())
     }
 
+    pub fn toggle_mark(&mut self) {
+        let Some(ticket_key) = self.selected().map(|ticket| ticket.key.clone()) else {
+            return;
+        };
+        if !self.marked_keys.remove(&ticket_key) {
+            self.marked_keys.insert(ticket_key);
+        }
+    }
+
+    // mark_all selects every loaded ticket, or clears them when they all are
+    pub fn mark_all(&mut self) {
+        let keys: Vec<String> = self.tickets.iter().map(|t| t.key.clone()).collect();
+        if keys.iter().all(|key| self.marked_keys.contains(key)) {
+            for key in &keys {
+                self.marked_keys.remove(key);
+            }
+        } else {
+            self.marked_keys.extend(keys);
+        }
+    }
+
+    // mark_matching selects the loaded tickets with the text in their key,
+    // summary, status, assignee or labels and returns how many matched
+    pub fn mark_matching(&mut self, text: &str) -> usize {
+        let text = text.to_lowercase();
+        let matching: Vec<String> = self
+            .tickets
+            .iter()
+            .filter(|ticket| {
+                let fields = &ticket.fields;
+                let assignee = fields.assignee.as_ref().map(|a| a.display_name.as_str());
+                [
+                    Some(ticket.key.as_str()),
+                    Some(fields.summary.as_str()),
+                    Some(fields.status.name.as_str()),
+                    assignee,
+                ]
+                .into_iter()
+                .flatten()
+                .chain(fields.labels.iter().map(String::as_str))
+                .any(|value| value.to_lowercase().contains(&text))
+            })
+            .map(|ticket| ticket.key.clone())
+            .collect();
+        let count = matching.len();
+        self.marked_keys.extend(matching);
+        count
+    }
+
     pub fn scroll_down_description(&mut self, lines: u16) {
         if self.selected().is_some() {
             self.scroll = self.scroll.saturating_add(lines);
@@ -314,6 +520,19 @@ impl TicketWidget {
         self.scroll = self.scroll.saturating_sub(lines);
     }
 
+    // replace_ticket swaps the ticket in place so the list order is kept,
+    // returning false when the ticket is not part of the list
+    pub fn replace_ticket(&mut self, ticket: TicketData) -> bool {
+        match self.tickets.iter_mut().find(|t| t.key == ticket.key) {
+            Some(t) => {
+                *t = ticket;
+                self.ticket_description = None;
+                true
+            }
+            None => false,
+        }
+    }
+
     pub async fn update(
         &mut self,
         mut tickets: Vec<TicketData>,
@@ -321,10 +540,66 @@ impl TicketWidget {
     ) -> anyhow::Result<()> {
         if clear {
             self.tickets.clear();
+            self.jira_order.clear();
+        }
+        for ticket in &tickets {
+            let position = self.jira_order.len();
+            self.jira_order
+                .entry(ticket.key.clone())
+                .or_insert(position);
         }
         self.tickets.append(&mut tickets);
+        self.sort_tickets();
         Ok(())
     }
+
+    // sort_tickets orders the loaded tickets by the sort column and keeps the
+    // selected ticket selected. Lists ranked on a board keep the board order.
+    pub fn sort_tickets(&mut self) {
+        if self.rank_offset.is_some() {
+            return;
+        }
+        let selected_key = self.selected().map(|ticket| ticket.key.clone());
+        match self.sort.and_then(|(index, order)| {
+            self.columns
+                .get(index)
+                .map(|column| (column.kind.clone(), order))
+        }) {
+            Some((kind, order)) => self.tickets.sort_by(|a, b| match order {
+                SortOrder::Asc => kind.compare(a, b),
+                SortOrder::Desc => kind.compare(b, a),
+            }),
+            None => {
+                let jira_order = &self.jira_order;
+                self.tickets
+                    .sort_by_key(|ticket| jira_order.get(&ticket.key).copied());
+            }
+        }
+        if let Some(key) = selected_key {
+            let index = self.tickets.iter().position(|ticket| ticket.key == key);
+            self.select(index);
+        }
+    }
+
+    // sort_next_column walks the sort through the columns and back to the
+    // Jira order after the last one
+    pub fn sort_next_column(&mut self) {
+        self.sort = match self.sort {
+            None if !self.columns.is_empty() => Some((0, SortOrder::Asc)),
+            Some((index, order)) if index + 1 < self.columns.len() => Some((index + 1, order)),
+            _ => None,
+        };
+        self.sort_tickets();
+    }
+
+    pub fn toggle_sort_order(&mut self) {
+        self.sort = match self.sort {
+            Some((index, SortOrder::Asc)) => Some((index, SortOrder::Desc)),
+            Some((index, SortOrder::Desc)) => Some((index, SortOrder::Asc)),
+            None => None,
+        };
+        self.sort_tickets();
+    }
 }
 
 impl Component for TicketWidget {
@@ -341,6 +616,11 @@ impl Component for TicketWidget {
                 First => self.go_to_top(),
                 ScrollDownDescription(line) => self.scroll_down_description(line),
                 ScrollUpDescription(line) => self.scroll_up_description(line),
+                SortNextColumn => self.sort_next_column(),
+                ToggleSortOrder => self.toggle_sort_order(),
+                ToggleMark => self.toggle_mark(),
+                MarkAll => self.mark_all(),
+                ClearMarks => self.marked_keys.clear(),
             }
             Ok(EventState::Consumed)
         } else {